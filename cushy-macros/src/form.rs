@@ -0,0 +1,308 @@
+use manyhow::{bail, ensure};
+use quote::ToTokens;
+use syn::{Attribute, Data, DeriveInput, Expr, Field, Fields, LitStr, Path, Variant};
+
+use crate::*;
+
+#[derive(Default)]
+struct FormOptions {
+    label: Option<LitStr>,
+    hint: Option<LitStr>,
+    range: Option<Expr>,
+    validator: Option<Expr>,
+    skip: bool,
+    select: Option<Path>,
+}
+
+impl FormOptions {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("form")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    options.label = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("hint") {
+                    options.hint = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("range") {
+                    options.range = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("validator") {
+                    options.validator = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("select") {
+                    options.select = Some(meta.path);
+                } else {
+                    return Err(meta.error("unknown form attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    fn ensure_not_select(&self) -> Result<()> {
+        if let Some(select) = &self.select {
+            bail!(select, "`select` is only supported on enums");
+        }
+        Ok(())
+    }
+}
+
+/// Converts `snake_case` or `CamelCase` identifiers into a label with each word
+/// capitalized and separated by spaces.
+fn label_for(ident: &impl ToString) -> String {
+    let ident = ident.to_string();
+    let mut label = String::with_capacity(ident.len());
+    let mut capitalize = true;
+    for ch in ident.trim_start_matches("r#").chars() {
+        if ch == '_' {
+            capitalize = true;
+            continue;
+        }
+        if ch.is_uppercase() && !label.is_empty() {
+            capitalize = true;
+        }
+        if capitalize {
+            if !label.is_empty() {
+                label.push(' ');
+            }
+            label.extend(ch.to_uppercase());
+            capitalize = false;
+        } else {
+            label.push(ch);
+        }
+    }
+    label
+}
+
+pub fn form(
+    DeriveInput {
+        ident: item_ident,
+        attrs,
+        generics,
+        data,
+        ..
+    }: DeriveInput,
+) -> Result<TokenStream> {
+    if let Some(generic) = generics.params.first() {
+        bail!(generic, "generics not supported");
+    }
+
+    match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => {
+                FormOptions::parse(&attrs)?.ensure_not_select()?;
+                struct_form(&item_ident, fields.named)
+            }
+            fields => bail!(fields, "only structs with named fields are supported"),
+        },
+        Data::Enum(data) => enum_form(&item_ident, &attrs, data.variants),
+        Data::Union(union) => bail!(union.union_token, "unions not supported"),
+    }
+}
+
+fn struct_form(
+    item_ident: &syn::Ident,
+    fields: impl IntoIterator<Item = Field>,
+) -> Result<TokenStream> {
+    let fields = fields
+        .into_iter()
+        .map(|Field { ident, ty, attrs, .. }| {
+            let options = FormOptions::parse(&attrs)?;
+            options.ensure_not_select()?;
+            if options.skip {
+                return Ok(None);
+            }
+            let ident = ident.expect("named field");
+            let label = options
+                .label
+                .map_or_else(|| label_for(&ident), |label| label.value());
+            let range = options
+                .range
+                .map(|range| quote!(::cushy::widgets::form::in_range(#range)));
+            let validators = range.into_iter().chain(
+                options
+                    .validator
+                    .map(ToTokens::into_token_stream),
+            );
+            let hint = options.hint.map(|hint| quote!(.hint(#hint)));
+            Ok(Some(quote! {
+                .field(#label, {
+                    let __field = __value.project(|__value| &mut __value.#ident);
+                    <#ty as ::cushy::widgets::form::FormField>::form_field(&__field, __validations)
+                        #(.validated_by(__validations.validate(&__field, #validators)))*
+                        #hint
+                })
+            }))
+        })
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl ::cushy::widgets::form::Form for #item_ident {
+            fn form(
+                __value: &::cushy::value::Dynamic<Self>,
+                __validations: &::cushy::value::Validations,
+            ) -> ::cushy::widget::WidgetInstance {
+                ::cushy::widget::MakeWidget::make_widget(
+                    ::cushy::widgets::form::FormFields::new()
+                        #(#fields)*
+                )
+            }
+        }
+
+        impl ::cushy::widgets::form::FormField for #item_ident {
+            fn form_field(
+                __value: &::cushy::value::Dynamic<Self>,
+                __validations: &::cushy::value::Validations,
+            ) -> ::cushy::widgets::form::FieldEditor {
+                ::cushy::widgets::form::FieldEditor::new(
+                    <Self as ::cushy::widgets::form::Form>::form(__value, __validations)
+                )
+            }
+        }
+    })
+}
+
+fn enum_form(
+    item_ident: &syn::Ident,
+    attrs: &[Attribute],
+    variants: impl IntoIterator<Item = Variant>,
+) -> Result<TokenStream> {
+    let options = FormOptions::parse(attrs)?;
+    let (constructor, layout) = if options.select.is_some() {
+        (quote!(new_select), quote!(into_columns))
+    } else {
+        (quote!(new_radio), quote!(into_rows))
+    };
+
+    let choices = variants
+        .into_iter()
+        .map(
+            |Variant {
+                 ident,
+                 fields,
+                 attrs,
+                 ..
+             }| {
+                ensure!(fields.is_empty(), fields, "enum fields are not supported");
+                let options = FormOptions::parse(&attrs)?;
+                options.ensure_not_select()?;
+                let label = options
+                    .label
+                    .map_or_else(|| label_for(&ident), |label| label.value());
+                Ok(quote!(.and(__value.#constructor(#item_ident::#ident, #label))))
+            },
+        )
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl ::cushy::widgets::form::FormField for #item_ident {
+            fn form_field(
+                __value: &::cushy::value::Dynamic<Self>,
+                _validations: &::cushy::value::Validations,
+            ) -> ::cushy::widgets::form::FieldEditor {
+                ::cushy::widgets::form::FieldEditor::new(
+                    ::cushy::widget::WidgetList::new()
+                        #(#choices)*
+                        .#layout()
+                )
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use syn::parse_quote;
+
+    use super::*;
+    expansion_snapshot! {struct_
+        #[derive(form)]
+        struct Account {
+            #[form(label = "Name", hint = "Shown to other users")]
+            username: String,
+            #[form(range = 13..)]
+            age: u8,
+            #[form(validator = |email: &String| if email.contains('@') { Ok(()) } else { Err("invalid email") })]
+            email: String,
+            #[form(skip)]
+            id: u64,
+            theme: Theme,
+        }
+    }
+    expansion_snapshot! {enum_
+        #[derive(form)]
+        enum Theme {
+            #[form(label = "Follow System")]
+            System,
+            Light,
+            Dark,
+        }
+    }
+    expansion_snapshot! {select_enum
+        #[derive(form)]
+        #[form(select)]
+        enum Theme {
+            Light,
+            Dark,
+        }
+    }
+
+    #[test]
+    fn unsupported() {
+        for input in [
+            parse_quote!(
+                struct Tuple(String);
+            ),
+            parse_quote!(
+                struct Generic<T> {
+                    value: T,
+                }
+            ),
+            parse_quote!(
+                enum Fields {
+                    A(u32),
+                }
+            ),
+            parse_quote!(
+                union Union {
+                    a: u32,
+                }
+            ),
+            parse_quote!(
+                #[form(select)]
+                struct Select {
+                    value: String,
+                }
+            ),
+            parse_quote!(
+                struct SelectField {
+                    #[form(select)]
+                    value: String,
+                }
+            ),
+            parse_quote!(
+                enum SelectVariant {
+                    #[form(select)]
+                    A,
+                }
+            ),
+            parse_quote!(
+                struct Unknown {
+                    #[form(unknown)]
+                    value: String,
+                }
+            ),
+        ] {
+            assert!(form(input).is_err());
+        }
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(label_for(&"first_name"), "First Name");
+        assert_eq!(label_for(&"DarkMode"), "Dark Mode");
+        assert_eq!(label_for(&"r#type"), "Type");
+    }
+}
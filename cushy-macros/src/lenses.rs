@@ -0,0 +1,214 @@
+use manyhow::bail;
+use quote::format_ident;
+use syn::{Data, DeriveInput, Field, Fields, Index};
+
+use crate::*;
+
+/// The methods that can be called on a `Dynamic`. A projection function with
+/// one of these names would be shadowed by the inherent method or conflict with
+/// the trait method, so fields with these names are rejected.
+const DYNAMIC_METHODS: &[&str] = &[
+    "clone",
+    "compare_swap",
+    "create_reader",
+    "debounced_every",
+    "debounced_with_delay",
+    "distinct_by",
+    "downgrade",
+    "eq",
+    "filter",
+    "for_each",
+    "for_each_cloned",
+    "for_each_cloned_try",
+    "for_each_generational",
+    "for_each_generational_cloned_try",
+    "for_each_generational_try",
+    "for_each_subsequent",
+    "for_each_subsequent_generational",
+    "for_each_subsequent_generational_try",
+    "for_each_subsequent_try",
+    "for_each_try",
+    "generation",
+    "get",
+    "get_tracking_invalidate",
+    "get_tracking_redraw",
+    "instances",
+    "into_reader",
+    "linked",
+    "linked_string",
+    "lock",
+    "map_each",
+    "map_each_cloned",
+    "map_each_generational",
+    "map_each_into",
+    "map_each_to",
+    "map_generational",
+    "map_mut",
+    "map_ref",
+    "ne",
+    "new_radio",
+    "new_select",
+    "previous",
+    "project",
+    "read",
+    "readers",
+    "replace",
+    "scan",
+    "set",
+    "set_source",
+    "take",
+    "take_if_not_default",
+    "throttled",
+    "to_stream",
+    "toggle",
+    "transition_to",
+    "try_compare_swap",
+    "try_get",
+    "try_lock",
+    "try_map_generational",
+    "try_map_mut",
+    "try_map_ref",
+    "try_replace",
+    "validate_with",
+    "weak_clone",
+    "with_clone",
+    "with_for_each",
+];
+
+pub fn lenses(
+    DeriveInput {
+        ident: item_ident,
+        vis,
+        generics,
+        data,
+        ..
+    }: DeriveInput,
+) -> Result<TokenStream> {
+    if let Some(generic) = generics.params.first() {
+        bail!(generic, "generics not supported");
+    }
+
+    let fields = match data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            Fields::Unnamed(fields) => fields.unnamed,
+            Fields::Unit => bail!(item_ident, "unit structs have no fields to project"),
+        },
+        Data::Enum(data) => bail!(data.enum_token, "enums are not supported"),
+        Data::Union(union) => bail!(union.union_token, "unions not supported"),
+    };
+
+    let trait_ident = format_ident!("{item_ident}Lenses");
+    let trait_doc = format!("Dynamic projections of each field of [`{item_ident}`].");
+    let (signatures, bodies): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, Field { ident, ty, .. })| {
+            let (function, member) = match ident {
+                Some(ident) => {
+                    if DYNAMIC_METHODS.contains(&ident.to_string().as_str()) {
+                        bail!(
+                            ident,
+                            "`{ident}` conflicts with `Dynamic::{ident}`, rename the field or \
+                             use `Dynamic::project` to project it"
+                        );
+                    }
+                    (ident.clone(), quote!(#ident))
+                }
+                None => {
+                    let member = Index::from(index);
+                    (format_ident!("field_{index}"), quote!(#member))
+                }
+            };
+            let doc = format!("Returns a dynamic that is kept in sync with `{member}`.");
+            let signature = quote!(fn #function(&self) -> ::cushy::value::Dynamic<#ty>);
+            Ok((
+                quote!(#[doc = #doc] #signature;),
+                quote!(#signature { self.project(|__value| &mut __value.#member) }),
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    Ok(quote! {
+        #[doc = #trait_doc]
+        #vis trait #trait_ident {
+            #(#signatures)*
+        }
+
+        impl #trait_ident for ::cushy::value::Dynamic<#item_ident> {
+            #(#bodies)*
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use manyhow::ToTokensError;
+    use syn::parse_quote;
+
+    use super::*;
+    expansion_snapshot! {named_struct
+        #[derive(lenses)]
+        pub struct Settings {
+            name: String,
+            volume: u8,
+        }
+    }
+    expansion_snapshot! {tuple_struct
+        #[derive(lenses)]
+        struct Point(i32, i32);
+    }
+
+    #[test]
+    fn unsupported() {
+        for input in [
+            parse_quote!(
+                struct Unit;
+            ),
+            parse_quote!(
+                struct Generic<T> {
+                    value: T,
+                }
+            ),
+            parse_quote!(
+                enum Enum {
+                    A,
+                }
+            ),
+            parse_quote!(
+                union Union {
+                    a: u32,
+                }
+            ),
+        ] {
+            assert!(lenses(input).is_err());
+        }
+    }
+
+    #[test]
+    fn conflicting_fields() {
+        for input in [
+            parse_quote!(
+                struct Shadowed {
+                    get: u32,
+                }
+            ),
+            parse_quote!(
+                struct Shadowed {
+                    name: String,
+                    map_ref: u32,
+                }
+            ),
+            parse_quote!(
+                struct Shadowed {
+                    take: u32,
+                }
+            ),
+        ] {
+            let error = lenses(input).unwrap_err().to_token_stream().to_string();
+            assert!(error.contains("conflicts with `Dynamic::"), "{error}");
+        }
+    }
+}
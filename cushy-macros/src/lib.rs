@@ -25,8 +25,14 @@ macro_rules! expansion_snapshot {
 
 mod animation;
 mod cushy_main;
+mod form;
+mod lenses;
 
 #[manyhow(proc_macro_derive(LinearInterpolate))]
 pub use animation::linear_interpolate;
 #[manyhow(proc_macro_attribute)]
 pub use cushy_main::main;
+#[manyhow(proc_macro_derive(Form, attributes(form)))]
+pub use form::form;
+#[manyhow(proc_macro_derive(Lenses))]
+pub use lenses::lenses;
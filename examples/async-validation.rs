@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use cushy::figures::units::Lp;
+use cushy::value::{Dynamic, Validations};
+use cushy::widget::MakeWidget;
+use cushy::widgets::input::InputValue;
+use cushy::{Open, PendingApp, TokioRuntime};
+use tokio::time::sleep;
+
+fn main() -> cushy::Result {
+    let app = PendingApp::new(TokioRuntime::default());
+    let username = Dynamic::<String>::default();
+    let validations = Validations::default();
+
+    // The check is only started once the username has stopped changing for a
+    // moment, and a spinner is shown while it is pending.
+    let validation = validations.validate_async(&app, &username, |username: &String| {
+        let username = username.clone();
+        async move { check_username(&username).await }
+    });
+
+    "Username"
+        .and(
+            username
+                .to_input()
+                .validation(validation)
+                .hint("try \"admin\" to see an error"),
+        )
+        .and("Register".into_button().on_click({
+            let validations = validations.clone();
+            move |_| {
+                let validations = validations.clone();
+                tokio::spawn(async move {
+                    // Wait for any pending checks before deciding whether the
+                    // form can be submitted.
+                    if validations.wait_for_pending().await {
+                        println!("Registered!");
+                    }
+                });
+            }
+        }))
+        .into_rows()
+        .pad()
+        .width(Lp::inches(4))
+        .centered()
+        .run_in(app)
+}
+
+async fn check_username(username: &str) -> Result<(), &'static str> {
+    // Simulate a request to a server.
+    sleep(Duration::from_millis(500)).await;
+    if username.is_empty() {
+        Err("A username is required")
+    } else if username == "admin" {
+        Err("This username is not available")
+    } else {
+        Ok(())
+    }
+}
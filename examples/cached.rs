@@ -0,0 +1,41 @@
+use cushy::figures::units::Lp;
+use cushy::value::{Destination, Dynamic, Source};
+use cushy::widget::{MakeWidget, WidgetList};
+use cushy::widgets::cached::RenderTarget;
+use cushy::widgets::Cached;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let offset = Dynamic::new(0_usize);
+    let target = RenderTarget::new();
+
+    // Drawing hundreds of labels is relatively expensive. By caching the
+    // rendered labels, they are only drawn again when the contents change.
+    let labels = offset
+        .map_each(|offset| {
+            (0..500)
+                .map(|index| (index + offset).to_string())
+                .collect::<WidgetList>()
+        })
+        .into_wrap();
+    let cached = Cached::with_target(target.clone(), labels)
+        .vertical_scroll()
+        .expand();
+
+    "Shift Labels"
+        .into_button()
+        .on_click(move |_| {
+            offset.map_mut(|mut offset| *offset += 1);
+        })
+        .and(
+            target
+                .thumbnail()
+                .width(Lp::inches(2))
+                .height(Lp::inches(1)),
+        )
+        .into_columns()
+        .and(cached)
+        .into_rows()
+        .expand()
+        .run()
+}
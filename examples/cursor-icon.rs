@@ -1,17 +1,35 @@
+use cushy::cursor::{CursorImage, CursorTheme, CustomCursors};
+use cushy::figures::Point;
 use cushy::kludgine::app::winit::window::CursorIcon;
 use cushy::value::Dynamic;
 use cushy::widget::MakeWidget;
 use cushy::widgets::input::InputValue;
 use cushy::widgets::Custom;
 use cushy::Run;
+use image::{Rgba, RgbaImage};
 
 fn main() -> cushy::Result {
+    let target = target_cursor();
+
     Custom::new(
         "Try hovering the mouse cursor around this window"
             .and(
                 Dynamic::new(String::from("Input fields show the text selection cursor"))
                     .into_input(),
             )
+            .and(
+                Custom::new("This area uses a custom cursor image".contain())
+                    .on_hover(move |_location, _context| Some(target.clone()))
+                    .on_hit_test(|_location, _context| true),
+            )
+            .and(
+                Dynamic::new(String::from("This input's text cursor has been replaced"))
+                    .into_input()
+                    .with(
+                        &CustomCursors,
+                        CursorTheme::new().with(CursorIcon::Text, i_beam_cursor()),
+                    ),
+            )
             .into_rows()
             .pad()
             .centered(),
@@ -22,3 +40,32 @@ fn main() -> cushy::Result {
     .centered()
     .run()
 }
+
+/// Returns a cursor drawn as a ring with its hotspot in the center.
+fn target_cursor() -> CursorImage {
+    const SIZE: u32 = 21;
+    let center = (SIZE / 2) as f32;
+    let image = RgbaImage::from_fn(SIZE, SIZE, |x, y| {
+        let distance = (x as f32 - center).hypot(y as f32 - center);
+        if (distance - center + 2.).abs() < 1.5 {
+            Rgba([255, 64, 64, 255])
+        } else if distance < 1.5 {
+            Rgba([255, 255, 255, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    CursorImage::new(image, Point::new(SIZE / 2, SIZE / 2))
+}
+
+/// Returns a thick, green text cursor.
+fn i_beam_cursor() -> CursorImage {
+    let image = RgbaImage::from_fn(9, 19, |x, y| {
+        if x == 4 || y == 0 || y == 18 {
+            Rgba([64, 200, 64, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    CursorImage::new(image, Point::new(4_u32, 9))
+}
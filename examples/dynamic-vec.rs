@@ -0,0 +1,67 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use cushy::value::{Dynamic, DynamicVec, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::Stack;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    let items = DynamicVec::new((1..=3).map(|i| format!("Item {i}")).collect());
+    let next_item = Arc::new(AtomicUsize::new(4));
+
+    // Count how many times a widget has been created for an item. Only
+    // inserted and updated items are mapped, so this number only grows by the
+    // number of items that actually changed.
+    let widgets_created = Dynamic::new(0_usize);
+    let list = items.map_each_item({
+        let widgets_created = widgets_created.clone();
+        move |item: &String| {
+            *widgets_created.lock() += 1;
+            item.clone().contain()
+        }
+    });
+
+    let controls = "Push"
+        .into_button()
+        .on_click({
+            let items = items.clone();
+            let next_item = next_item.clone();
+            move |_| {
+                let next = next_item.fetch_add(1, Ordering::Relaxed);
+                items.push(format!("Item {next}"));
+            }
+        })
+        .and("Remove First".into_button().on_click({
+            let items = items.clone();
+            move |_| {
+                if !items.is_empty() {
+                    items.remove(0);
+                }
+            }
+        }))
+        .and("Rotate".into_button().on_click({
+            let items = items.clone();
+            move |_| {
+                if items.len() > 1 {
+                    items.move_item(0, items.len() - 1);
+                }
+            }
+        }))
+        .and("Rename First".into_button().on_click({
+            let items = items.clone();
+            move |_| {
+                if !items.is_empty() {
+                    items.update(0, |item| item.push('!'));
+                }
+            }
+        }))
+        .into_columns();
+
+    controls
+        .and(widgets_created.map_each(|count| format!("Widgets created: {count}")))
+        .and(Stack::rows(list).vertical_scroll().expand())
+        .into_rows()
+        .expand()
+        .run()
+}
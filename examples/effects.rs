@@ -0,0 +1,56 @@
+use cushy::animation::ZeroToOne;
+use cushy::figures::units::Lp;
+use cushy::figures::Angle;
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::effects::Transform;
+use cushy::widgets::layers::Modal;
+use cushy::widgets::slider::Slidable;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    // The content beneath the modal is blurred while it is presented, creating
+    // a frosted-glass backdrop.
+    let modal = Modal::new().backdrop_blur(Lp::points(6));
+    let opacity = Dynamic::new(0.5_f32);
+    let rotation = Dynamic::new(0_f32);
+
+    // With group opacity, the overlapping widgets do not show through each
+    // other.
+    let group = "Behind"
+        .contain()
+        .width(Lp::inches(2))
+        .height(Lp::inches(1))
+        .and("In Front".contain().centered())
+        .into_layers()
+        .with_group_opacity(opacity.map_each(|opacity| ZeroToOne::new(*opacity)));
+
+    let transformed = "Transformed".into_button().transformed(
+        rotation.map_each(|degrees| Transform::IDENTITY.rotated_by(Angle::degrees_f(*degrees))),
+    );
+
+    let show_modal = "Show Modal".into_button().on_click({
+        let modal = modal.clone();
+        move |_| {
+            modal.present(
+                "Frosted Glass"
+                    .and("Dismiss".into_button().on_click(modal.dismiss_callback()))
+                    .into_rows()
+                    .contain()
+                    .centered(),
+            );
+        }
+    });
+
+    group
+        .and(opacity.slider_between(0., 1.))
+        .and(transformed)
+        .and(rotation.slider_between(0., 360.))
+        .and(show_modal)
+        .into_rows()
+        .width(Lp::inches(3))
+        .centered()
+        .and(modal)
+        .into_layers()
+        .run()
+}
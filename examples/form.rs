@@ -0,0 +1,54 @@
+use cushy::figures::units::Lp;
+use cushy::value::{Dynamic, Source, Validations};
+use cushy::widget::MakeWidget;
+use cushy::widgets::form::Form;
+use cushy::Run;
+
+#[derive(Form, Debug, Default, Clone, PartialEq)]
+struct Registration {
+    #[form(hint = "* required", validator = required)]
+    username: String,
+    #[form(label = "Age (years)", range = 13..=130)]
+    age: u8,
+    #[form(label = "Subscribe to updates")]
+    subscribed: bool,
+    plan: Plan,
+}
+
+#[derive(Form, Debug, Default, Clone, Copy, PartialEq)]
+#[form(select)]
+enum Plan {
+    #[default]
+    Free,
+    Pro,
+    #[form(label = "Enterprise (contact us)")]
+    Enterprise,
+}
+
+fn main() -> cushy::Result {
+    let registration = Dynamic::<Registration>::default();
+    let validations = Validations::default();
+
+    Registration::form(&registration, &validations)
+        .and(
+            "Register"
+                .into_button()
+                .on_click(validations.when_valid(move |_| {
+                    println!("Registered: {:?}", registration.get());
+                })),
+        )
+        .into_rows()
+        .pad()
+        .width(Lp::inches(6))
+        .centered()
+        .run()
+}
+
+#[allow(clippy::ptr_arg)] // Changing &String to &str breaks type inference
+fn required(value: &String) -> Result<(), &'static str> {
+    if value.trim().is_empty() {
+        Err("This field cannot be empty")
+    } else {
+        Ok(())
+    }
+}
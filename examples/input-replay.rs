@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use cushy::clock::ManualClock;
+use cushy::figures::Size;
+use cushy::replay::InputRecorder;
+use cushy::value::{Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::input::InputValue;
+use cushy::widgets::Input;
+use cushy::window::VirtualRecorderError;
+
+#[macro_use]
+mod shared;
+
+struct State {
+    clicks: Dynamic<u32>,
+    text: Dynamic<String>,
+}
+
+impl State {
+    fn new() -> Self {
+        Self {
+            clicks: Dynamic::new(0),
+            text: Dynamic::default(),
+        }
+    }
+
+    fn ui(&self) -> impl MakeWidget {
+        "Click Me"
+            .into_button()
+            .on_click({
+                let clicks = self.clicks.clone();
+                move |_| *clicks.lock() += 1
+            })
+            .and(self.text.clone().into_input())
+            .into_rows()
+            .centered()
+    }
+}
+
+fn main() -> Result<(), VirtualRecorderError> {
+    // A manual clock makes the recorded timestamps, and therefore the replay,
+    // deterministic.
+    let clock = ManualClock::install();
+    let size = Size::new(320, 240);
+
+    // Record the input delivered to a window while interacting with it.
+    let input = InputRecorder::new();
+    let original = State::new();
+    let mut recorder = original
+        .ui()
+        .build_recorder()
+        .size(size)
+        .record_input(&input)
+        .finish()?;
+    let button = recorder
+        .window
+        .find_text("Click Me")
+        .expect("button not found");
+    let field = recorder
+        .window
+        .find_widget::<Input<String>>()
+        .expect("input not found");
+    recorder.click(&button)?;
+    clock.advance(Duration::from_millis(250));
+    recorder.click(&button)?;
+    clock.advance(Duration::from_millis(250));
+    // Clicking the input focuses it, which is part of the recording. Focusing
+    // the input directly would not be recorded.
+    recorder.click(&field)?;
+    recorder.type_into(&field, "replayed")?;
+    assert_eq!(original.clicks.get(), 2);
+    assert_eq!(original.text.get(), "replayed");
+    let recording = input.recording();
+
+    // Replaying the recording into a new window with the same interface
+    // reproduces the same state.
+    let replayed = State::new();
+    let mut recorder = replayed.ui().build_recorder().size(size).finish()?;
+    recorder.replay(&recording)?;
+    assert_eq!(replayed.clicks.get(), 2);
+    assert_eq!(replayed.text.get(), "replayed");
+
+    // The replay can also be captured as an animation.
+    let animated = State::new();
+    let mut recorder = animated.ui().build_recorder().size(size).finish()?;
+    recorder.set_cursor_visible(true);
+    let mut animation = recorder.record_animated_png(30);
+    animation.replay(&recording)?;
+    animation.write_to("examples/input-replay.png")?;
+    assert_eq!(animated.clicks.get(), 2);
+    assert_eq!(animated.text.get(), "replayed");
+    Ok(())
+}
+
+adapter_required_test!(main);
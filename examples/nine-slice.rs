@@ -0,0 +1,46 @@
+use cushy::figures::units::{Lp, UPx};
+use cushy::kludgine::include_texture;
+use cushy::kludgine::wgpu::FilterMode;
+use cushy::widget::MakeWidget;
+use cushy::widgets::button::ButtonImages;
+use cushy::widgets::image::NineSlice;
+use cushy::Run;
+
+fn panel(texture: cushy::kludgine::Texture) -> NineSlice {
+    // The panel textures have 8 pixel borders that contain the rounded
+    // corners.
+    NineSlice::new(texture, UPx::new(8))
+}
+
+fn main() -> cushy::Result {
+    let normal =
+        panel(include_texture!("assets/panel.png", FilterMode::Nearest).expect("valid image"));
+    let hovered = panel(
+        include_texture!("assets/panel-hovered.png", FilterMode::Nearest).expect("valid image"),
+    );
+    let active = panel(
+        include_texture!("assets/panel-active.png", FilterMode::Nearest).expect("valid image"),
+    );
+
+    let button = "Click Me".into_button().images(
+        ButtonImages::new(normal.clone())
+            .hovered(hovered)
+            .active(active),
+    );
+
+    "Stretched Panel"
+        .and(button)
+        .into_rows()
+        .contain()
+        .background_image(normal.clone())
+        .and(
+            "Tiled Panel"
+                .contain()
+                .background_image(normal.tiled().scaled(2.))
+                .width(Lp::inches(3))
+                .height(Lp::inches(2)),
+        )
+        .into_rows()
+        .centered()
+        .run()
+}
@@ -0,0 +1,74 @@
+use cushy::figures::Size;
+use cushy::value::{Destination, Dynamic, Source};
+use cushy::widget::MakeWidget;
+use cushy::widgets::input::InputValue;
+use cushy::widgets::{Button, Input};
+use cushy::window::VirtualRecorderError;
+
+#[macro_use]
+mod shared;
+
+fn ui(clicks: &Dynamic<u32>, name: &Dynamic<String>) -> impl MakeWidget {
+    let greeting = name.map_each(|name| format!("Hello, {name}!"));
+    "Increment"
+        .into_button()
+        .on_click({
+            let clicks = clicks.clone();
+            move |_| *clicks.lock() += 1
+        })
+        .and(clicks.map_each(|clicks| format!("Clicked {clicks} times")))
+        .and(name.clone().into_input())
+        .and(greeting)
+        .into_rows()
+        .centered()
+}
+
+fn main() -> Result<(), VirtualRecorderError> {
+    let clicks = Dynamic::new(0);
+    let name = Dynamic::<String>::default();
+    let mut recorder = ui(&clicks, &name)
+        .build_recorder()
+        .size(Size::new(320, 240))
+        .finish()?;
+
+    // Widgets can be located by their type...
+    assert_eq!(recorder.window.find_widgets::<Button>().len(), 1);
+    let input = recorder
+        .window
+        .find_widget::<Input<String>>()
+        .expect("input not found");
+
+    // ...or by the text they display. Clicking the button's label clicks the
+    // button.
+    let increment = recorder
+        .window
+        .find_text("Increment")
+        .expect("button not found");
+    recorder.window.click(&increment);
+    assert_eq!(clicks.get(), 1);
+    recorder.click(&increment)?;
+    assert_eq!(clicks.get(), 2);
+    assert!(recorder.window.find_text("Clicked 2 times").is_some());
+
+    // Typing enters text into the focused input, updating its dynamic.
+    recorder.window.type_into(&input, "Cushy");
+    assert_eq!(name.get(), "Cushy");
+    // Once redrawn, a focused input selects its contents, so typing replaces
+    // them.
+    recorder.focus(&input)?;
+    recorder.type_into(&input, "Recorder")?;
+    assert_eq!(name.get(), "Recorder");
+    assert!(recorder.window.find_text("Hello, Recorder!").is_some());
+
+    name.set(String::from("World"));
+    recorder.refresh()?;
+    assert!(recorder.window.find_text("Hello, World!").is_some());
+
+    recorder
+        .image()
+        .save("examples/offscreen-interaction.png")
+        .unwrap();
+    Ok(())
+}
+
+adapter_required_test!(main);
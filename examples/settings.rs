@@ -0,0 +1,29 @@
+use cushy::settings::{Json, Settings};
+use cushy::value::Dynamic;
+use cushy::widget::MakeWidget;
+use cushy::window::ThemeMode;
+use cushy::Run;
+
+fn main() -> cushy::Result {
+    // Settings are loaded before the application starts, so they can be used
+    // to configure the window before it is opened.
+    let settings =
+        Settings::<Json>::for_app("cushy-settings-example").expect("error loading settings");
+    println!("Settings are stored at {}", settings.path().display());
+
+    let theme_mode = settings.get_or_default::<ThemeMode>("theme-mode");
+    let volume: Dynamic<u8> = settings.get("volume", 50);
+
+    theme_mode
+        .new_radio(ThemeMode::Light, "Light")
+        .and(theme_mode.new_radio(ThemeMode::Dark, "Dark"))
+        .into_columns()
+        .and("Volume")
+        .and(volume.slider_between(0, 100))
+        .into_rows()
+        .centered()
+        .into_window()
+        .remember_state(&settings, "window")
+        .themed_mode(theme_mode)
+        .run()
+}
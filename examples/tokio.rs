@@ -10,11 +10,16 @@ fn main() {
     let app = PendingApp::new(TokioRuntime::default());
     let progress = Dynamic::new(0_u8);
     let progress_bar = progress.clone().progress_bar();
-    "Press Me"
-        .into_button()
-        .on_click(move |_| {
+    // Futures can drive a dynamic directly. The future is executed using the
+    // app's runtime, and is cancelled if the dynamic is dropped.
+    let greeting = Dynamic::from_future(&app, String::from("Loading..."), async {
+        sleep(Duration::from_secs(1)).await;
+        String::from("Loaded!")
+    });
+    greeting
+        .and("Press Me".into_button().on_click(move |_| {
             tokio::spawn(do_something(progress.clone()));
-        })
+        }))
         .and(progress_bar)
         .into_rows()
         .centered()
@@ -0,0 +1,89 @@
+use cushy::drawing::{
+    DashPattern, FillRule, LinearGradient, Path, PathBuilder, RadialGradient, Stroke,
+};
+use cushy::figures::units::Px;
+use cushy::figures::{Angle, IntoSigned, Point, Px2D, Size};
+use cushy::kludgine::Color;
+use cushy::widgets::Canvas;
+use cushy::{Run, Tick};
+
+fn main() -> cushy::Result<()> {
+    let mut dash_offset = Px::new(0);
+    Canvas::new(move |context| {
+        dash_offset += Px::new(1);
+        let size = context.gfx.size().into_signed();
+
+        // A wave filled with a vertical gradient, similar to an area chart.
+        let mut wave = PathBuilder::new(Point::new(Px::new(0), size.height));
+        for segment in 0..=8 {
+            let x = size.width * segment / 8;
+            let y = size.height / 2 + Px::new(if segment % 2 == 0 { -40 } else { 40 });
+            wave = wave.quadratic_curve_to(Point::new(x - size.width / 16, y), Point::new(x, y));
+        }
+        let wave = wave.line_to(Point::from(size)).close().build();
+        context.gfx.fill_path(
+            &wave,
+            LinearGradient::new(Point::px(0, 0), Point::new(Px::new(0), size.height))
+                .with_stop(0., Color::CLEAR_BLACK)
+                .with_stop(0.5, Color::CORNFLOWERBLUE)
+                .with_stop(1., Color::MIDNIGHTBLUE),
+        );
+
+        // A circle with a radial gradient and a "marching ants" outline.
+        let center = Point::from(size) / 2;
+        let circle = Path::circle(center, Px::new(80));
+        context.gfx.fill_path(
+            &circle,
+            RadialGradient::new(center - Point::px(20, 20), Px::new(100))
+                .with_stop(0., Color::WHITE)
+                .with_stop(1., Color::ORANGERED),
+        );
+        context.gfx.stroke_path(
+            &circle,
+            &Stroke::new(Color::WHITE, Px::new(3))
+                .dashed(DashPattern::new([Px::new(12), Px::new(6)]).offset(dash_offset)),
+        );
+
+        // A ring, whose inner circle is a hole due to the even-odd fill rule.
+        let ring_center = center - Point::px(200, 0);
+        let ring = PathBuilder::new(ring_center + Point::px(60, 0))
+            .arc(
+                ring_center,
+                Size::squared(Px::new(60)),
+                Angle::degrees(0),
+                Angle::degrees(360),
+            )
+            .close()
+            .move_to(ring_center + Point::px(30, 0))
+            .arc(
+                ring_center,
+                Size::squared(Px::new(30)),
+                Angle::degrees(0),
+                Angle::degrees(360),
+            )
+            .close()
+            .fill_rule(FillRule::EvenOdd)
+            .build();
+        context.gfx.fill_path(&ring, Color::GOLD);
+
+        // Stripes clipped to a rotated square.
+        let square = PathBuilder::new(center + Point::px(150, 0))
+            .line_to(center + Point::px(200, -50))
+            .line_to(center + Point::px(250, 0))
+            .line_to(center + Point::px(200, 50))
+            .close()
+            .build();
+        let mut clipped = context.gfx.clipped_to_path(&square);
+        for stripe in 0..10 {
+            let x = center.x + Px::new(150 + stripe * 10);
+            clipped.stroke_path(
+                &PathBuilder::new(Point::new(x, center.y - Px::new(60)))
+                    .line_to(Point::new(x, center.y + Px::new(60)))
+                    .build(),
+                &Stroke::new(Color::LIGHTGREEN, Px::new(4)),
+            );
+        }
+    })
+    .tick(Tick::redraws_per_second(30))
+    .run()
+}
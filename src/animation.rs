@@ -55,6 +55,7 @@ use kludgine::Color;
 use parking_lot::{Condvar, Mutex, MutexGuard};
 
 use crate::animation::easings::Linear;
+use crate::clock::Clock;
 use crate::styles::{Component, RequireInvalidation};
 use crate::utils::run_in_bg;
 use crate::value::{Destination, Dynamic, Source};
@@ -80,7 +81,8 @@ fn animation_thread(app: Option<&Cushy>) {
     let _guard = app.as_ref().map(|app| app.enter_runtime());
     let mut state = thread_state(None);
     loop {
-        if state.running.is_empty() {
+        // Animations using a manual clock are only advanced by their clock.
+        if !state.is_animating(&Clock::default()) {
             state.last_updated = None;
             NEW_ANIMATIONS.wait(&mut state);
         } else {
@@ -89,19 +91,7 @@ fn animation_thread(app: Option<&Cushy>) {
             let elapsed = start - last_tick;
             state.last_updated = Some(start);
 
-            let mut index = 0;
-            while index < state.running.len() {
-                let animation_id = *state.running.member(index).expect("index in bounds");
-                let animation_state = &mut state.animations[animation_id];
-                if animation_state.animation.animate(elapsed).is_break() {
-                    if !animation_state.handle_attached {
-                        state.animations.remove(animation_id);
-                    }
-                    state.running.remove_member(index);
-                } else {
-                    index += 1;
-                }
-            }
+            state.animate(&Clock::default(), elapsed);
 
             drop(state);
             let next_tick = last_tick + Duration::from_millis(16);
@@ -115,9 +105,39 @@ fn animation_thread(app: Option<&Cushy>) {
     }
 }
 
+/// Advances all running animations using `clock` by `elapsed`.
+///
+/// This is used by [`ManualClock`](crate::clock::ManualClock) to drive
+/// animations.
+pub(crate) fn advance(clock: &Clock, elapsed: Duration) {
+    ANIMATIONS.lock().animate(clock, elapsed);
+}
+
+/// Switches all animations using `clock` to the system clock.
+pub(crate) fn release(clock: &Clock) {
+    let mut state = ANIMATIONS.lock();
+    let Animating {
+        animations,
+        running,
+        ..
+    } = &mut *state;
+    let mut released = false;
+    for id in running.iter() {
+        let animation = &mut animations[*id];
+        if animation.clock.is(clock) {
+            animation.clock = Clock::default();
+            released = true;
+        }
+    }
+    if released {
+        NEW_ANIMATIONS.notify_one();
+    }
+}
+
 struct AnimationState {
     animation: Box<dyn Animate>,
     handle_attached: bool,
+    clock: Clock,
 }
 
 struct Animating {
@@ -136,20 +156,47 @@ impl Animating {
     }
 
     fn spawn(&mut self, animation: Box<dyn Animate>) -> AnimationHandle {
+        // Animations use the clock of the thread that spawned them.
+        let clock = Clock::current();
+        if clock.is_system() && !self.is_animating(&clock) {
+            NEW_ANIMATIONS.notify_one();
+        }
+
         let id = self.animations.push(AnimationState {
             animation,
             handle_attached: true,
+            clock,
         });
 
-        if self.running.is_empty() {
-            NEW_ANIMATIONS.notify_one();
-        }
-
         self.running.insert(id);
 
         AnimationHandle(Some(id))
     }
 
+    fn is_animating(&self, clock: &Clock) -> bool {
+        self.running
+            .iter()
+            .any(|id| self.animations[*id].clock.is(clock))
+    }
+
+    fn animate(&mut self, clock: &Clock, elapsed: Duration) {
+        let mut index = 0;
+        while index < self.running.len() {
+            let animation_id = *self.running.member(index).expect("index in bounds");
+            let animation_state = &mut self.animations[animation_id];
+            if !animation_state.clock.is(clock) {
+                index += 1;
+            } else if animation_state.animation.animate(elapsed).is_break() {
+                if !animation_state.handle_attached {
+                    self.animations.remove(animation_id);
+                }
+                self.running.remove_member(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
     fn remove_animation(&mut self, id: LotId) {
         self.animations.remove(id);
         self.running.remove(&id);
@@ -1,4 +1,6 @@
+use std::future::Future;
 use std::marker::PhantomData;
+use std::pin::Pin;
 use std::process::exit;
 use std::sync::Arc;
 use std::thread;
@@ -206,8 +208,20 @@ pub trait AppRuntime: Send + Clone + 'static {
 
     /// Enter the application's rutime context.
     fn enter(&self) -> Self::Guard<'_>;
+
+    /// Executes `future` in the background.
+    ///
+    /// The default implementation executes `future` to completion on a newly
+    /// spawned thread.
+    fn spawn(&self, future: BoxedFuture) {
+        thread::spawn(move || pollster::block_on(future));
+    }
 }
 
+/// A boxed future that can be [spawned](AppRuntime::spawn) by an
+/// [`AppRuntime`].
+pub type BoxedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
 /// A default application runtime.
 ///
 /// When the `tokio` feature is enabled, a tokio runtime is spawned when this
@@ -229,6 +243,11 @@ impl AppRuntime for DefaultRuntime {
             _phantom: PhantomData,
         }
     }
+
+    #[cfg(feature = "tokio")]
+    fn spawn(&self, future: BoxedFuture) {
+        self.tokio.spawn(future);
+    }
 }
 
 pub struct DefaultRuntimeGuard<'a> {
@@ -246,7 +265,7 @@ mod tokio {
 
     use tokio::runtime::{self, Handle};
 
-    use super::AppRuntime;
+    use super::{AppRuntime, BoxedFuture};
     use crate::Lazy;
 
     /// A spawned `tokio` runtime.
@@ -314,6 +333,10 @@ mod tokio {
         fn enter(&self) -> Self::Guard<'_> {
             self.handle.enter()
         }
+
+        fn spawn(&self, future: BoxedFuture) {
+            self.handle.spawn(future);
+        }
     }
 }
 
@@ -330,6 +353,7 @@ impl Clone for BoxedRuntime {
 
 trait BoxableRuntime: Send {
     fn enter_runtime(&self) -> RuntimeGuard<'_>;
+    fn spawn(&self, future: BoxedFuture);
     fn cloned(&self) -> BoxedRuntime;
 }
 
@@ -342,6 +366,10 @@ where
         RuntimeGuard(Box::new(AppRuntime::enter(self)))
     }
 
+    fn spawn(&self, future: BoxedFuture) {
+        AppRuntime::spawn(self, future);
+    }
+
     fn cloned(&self) -> BoxedRuntime {
         BoxedRuntime(Box::new(self.clone()))
     }
@@ -416,6 +444,15 @@ impl Cushy {
     pub fn enter_runtime(&self) -> RuntimeGuard<'_> {
         self.runtime.0.enter_runtime()
     }
+
+    /// Executes `future` in the background using the application's
+    /// [`AppRuntime`].
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.runtime.0.spawn(Box::pin(future));
+    }
 }
 
 impl Default for Cushy {
@@ -0,0 +1,352 @@
+//! The source of time for animations and timers.
+//!
+//! By default, Cushy measures time using the system's clock. A
+//! [`ManualClock`] can be installed on the current thread to freeze time so
+//! that it only moves forward when [`ManualClock::advance()`] is called. While
+//! a manual clock is installed, the following advance deterministically when
+//! they are created or driven by the same thread:
+//!
+//! - [`Animation`](crate::animation::Animation)s, including
+//!   [`Cycle`](crate::animation::Cycle)s, delays, and anything built upon them
+//!   such as debounced [`Dynamic`](crate::value::Dynamic)s and scrollbar fades.
+//! - [`Tick`](crate::Tick)s.
+//! - The elapsed time reported when rendering a
+//!   [`VirtualWindow`](crate::window::VirtualWindow), which drives effects such
+//!   as the input cursor blinking.
+//! - [`AnimationRecorder`](crate::window::AnimationRecorder), which advances
+//!   the clock one frame at a time instead of sleeping.
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use cushy::animation::{AnimationTarget, Spawn};
+//! use cushy::clock::ManualClock;
+//! use cushy::value::{Dynamic, Source};
+//!
+//! let clock = ManualClock::install();
+//! let value = Dynamic::new(0);
+//! value
+//!     .transition_to(100)
+//!     .over(Duration::from_millis(100))
+//!     .launch();
+//!
+//! clock.advance(Duration::from_millis(50));
+//! assert_eq!(value.get(), 50);
+//! clock.advance(Duration::from_millis(50));
+//! assert_eq!(value.get(), 100);
+//! ```
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Condvar, Mutex};
+
+use crate::animation;
+
+thread_local! {
+    static CURRENT: RefCell<Clock> = const { RefCell::new(Clock(None)) };
+}
+
+/// The clock used to measure time on a thread.
+///
+/// Each thread uses the system clock unless a [`ManualClock`] is installed on
+/// it. Work that outlives the current call, such as animations and ticks,
+/// captures the current clock when it is created.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Clock(Option<Arc<ManualState>>);
+
+impl Clock {
+    /// Returns the clock used by the current thread.
+    pub(crate) fn current() -> Self {
+        CURRENT.with_borrow(Clone::clone)
+    }
+
+    /// Uses this clock for the remainder of the current thread's life.
+    pub(crate) fn enter(self) {
+        CURRENT.set(self);
+    }
+
+    /// Returns true if this is the system clock.
+    pub(crate) fn is_system(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns true if `self` and `other` are the same clock.
+    pub(crate) fn is(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(this), Some(other)) => Arc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the current time of this clock.
+    pub(crate) fn now(&self) -> Instant {
+        self.0
+            .as_ref()
+            .and_then(|manual| manual.state.lock().now())
+            .unwrap_or_else(Instant::now)
+    }
+
+    /// Blocks the current thread until this clock reaches `deadline`.
+    ///
+    /// When this is a manual clock, [`ManualClock::advance()`] will not return
+    /// until the returned guard is dropped. This allows work performed in
+    /// response to time passing to complete before the caller observes its
+    /// results.
+    pub(crate) fn sleep_until(&self, deadline: Instant) -> Awake {
+        let Some(manual) = self
+            .0
+            .as_ref()
+            .filter(|manual| manual.state.lock().installed)
+        else {
+            if let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+                std::thread::sleep(remaining);
+            }
+            return Awake(None);
+        };
+
+        let mut state = manual.state.lock();
+        state.sleeping.push(deadline);
+        while state.now().is_some_and(|now| now < deadline) {
+            manual.changed.wait(&mut state);
+        }
+        let index = state
+            .sleeping
+            .iter()
+            .position(|sleeping| *sleeping == deadline)
+            .expect("registered above");
+        state.sleeping.swap_remove(index);
+        let installed = state.installed;
+        if installed {
+            state.awake += 1;
+        }
+        drop(state);
+        manual.changed.notify_all();
+        Awake(installed.then(|| manual.clone()))
+    }
+}
+
+#[derive(Debug)]
+struct ManualState {
+    state: Mutex<ManualClockState>,
+    changed: Condvar,
+}
+
+impl ManualState {
+    fn advance(self: &Arc<Self>, duration: Duration) {
+        let mut state = self.state.lock();
+        state.now += duration;
+        let now = state.now;
+        drop(state);
+
+        animation::advance(&Clock(Some(self.clone())), duration);
+
+        let mut state = self.state.lock();
+        self.changed.notify_all();
+        while state.awake > 0 || state.sleeping.iter().any(|deadline| *deadline <= now) {
+            self.changed.wait(&mut state);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ManualClockState {
+    now: Instant,
+    installed: bool,
+    sleeping: Vec<Instant>,
+    awake: usize,
+}
+
+impl ManualClockState {
+    fn now(&self) -> Option<Instant> {
+        self.installed.then_some(self.now)
+    }
+}
+
+/// Returns the current time.
+///
+/// If a [`ManualClock`] is installed on the current thread, its time is
+/// returned. Otherwise, [`Instant::now()`] is returned.
+#[must_use]
+pub fn now() -> Instant {
+    CURRENT.with_borrow(Clock::now)
+}
+
+/// Returns true if a [`ManualClock`] is installed on the current thread.
+#[must_use]
+pub fn is_manual() -> bool {
+    CURRENT.with_borrow(|clock| {
+        clock
+            .0
+            .as_ref()
+            .is_some_and(|manual| manual.state.lock().installed)
+    })
+}
+
+/// Blocks the current thread until the current thread's clock reaches
+/// `deadline`.
+///
+/// See [`Clock::sleep_until`] for more information.
+pub(crate) fn sleep_until(deadline: Instant) -> Awake {
+    Clock::current().sleep_until(deadline)
+}
+
+/// A guard that signals that a thread is performing work after being woken by
+/// a manual clock.
+#[must_use]
+pub(crate) struct Awake(Option<Arc<ManualState>>);
+
+impl Drop for Awake {
+    fn drop(&mut self) {
+        if let Some(manual) = &self.0 {
+            manual.state.lock().awake -= 1;
+            manual.changed.notify_all();
+        }
+    }
+}
+
+/// Advances the manual clock installed on the current thread by `duration`.
+///
+/// Returns false if no manual clock is installed on the current thread.
+pub(crate) fn advance(duration: Duration) -> bool {
+    let Some(manual) = Clock::current()
+        .0
+        .filter(|manual| manual.state.lock().installed)
+    else {
+        return false;
+    };
+    manual.advance(duration);
+    true
+}
+
+/// A clock that only moves forward when advanced.
+///
+/// A manual clock is installed on the thread that calls
+/// [`ManualClock::install()`]. It is used by that thread, and by any
+/// animations and ticks created on that thread while it is installed. Other
+/// threads continue to use the system clock, which allows tests that install
+/// a manual clock to run in parallel with tests that rely on time passing.
+///
+/// Installing a manual clock while another is installed on the same thread
+/// temporarily replaces the existing clock. The previous clock is restored
+/// when this value is dropped, and anything still using this clock switches
+/// to the system clock.
+#[derive(Debug)]
+#[must_use]
+pub struct ManualClock {
+    clock: Arc<ManualState>,
+    previous: Clock,
+    // The previous clock is restored on the installing thread when dropped.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ManualClock {
+    /// Installs a new manual clock on the current thread, starting at the
+    /// thread's current time.
+    pub fn install() -> Self {
+        let clock = Arc::new(ManualState {
+            state: Mutex::new(ManualClockState {
+                now: now(),
+                installed: true,
+                sleeping: Vec::new(),
+                awake: 0,
+            }),
+            changed: Condvar::new(),
+        });
+        let previous = CURRENT.replace(Clock(Some(clock.clone())));
+        Self {
+            clock,
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the current time of this clock.
+    #[must_use]
+    pub fn now(&self) -> Instant {
+        self.clock.state.lock().now
+    }
+
+    /// Advances this clock by `duration`.
+    ///
+    /// All running animations using this clock are updated before this
+    /// function returns, and any [`Tick`](crate::Tick)s using this clock that
+    /// became due have finished invoking their callbacks.
+    pub fn advance(&self, duration: Duration) {
+        self.clock.advance(duration);
+    }
+}
+
+impl Drop for ManualClock {
+    fn drop(&mut self) {
+        CURRENT.set(std::mem::take(&mut self.previous));
+        self.clock.state.lock().installed = false;
+        self.clock.changed.notify_all();
+        animation::release(&Clock(Some(self.clock.clone())));
+    }
+}
+
+#[test]
+fn manual_animations() {
+    use crate::animation::{AnimationTarget, IntoAnimate, Spawn};
+    use crate::value::{Destination, Dynamic, Source};
+
+    let clock = ManualClock::install();
+    let value = Dynamic::new(0);
+    let completed = Dynamic::new(false);
+    value
+        .transition_to(100)
+        .over(Duration::from_millis(100))
+        .and_then(Duration::from_millis(100))
+        .on_complete({
+            let completed = completed.clone();
+            move || completed.set(true)
+        })
+        .launch();
+
+    clock.advance(Duration::from_millis(25));
+    assert_eq!(value.get(), 25);
+    clock.advance(Duration::from_millis(75));
+    assert_eq!(value.get(), 100);
+    assert!(!completed.get());
+    clock.advance(Duration::from_millis(100));
+    assert!(completed.get());
+}
+
+#[test]
+fn manual_clocks_are_thread_scoped() {
+    use crate::animation::{AnimationTarget, Spawn};
+    use crate::value::{Dynamic, Source};
+
+    let clock = ManualClock::install();
+    // Installing a second clock does not panic, and restores the first clock
+    // when dropped.
+    let nested = ManualClock::install();
+    nested.advance(Duration::from_secs(1));
+    assert_eq!(now(), nested.now());
+    drop(nested);
+    assert_eq!(now(), clock.now());
+
+    // Animations launched on other threads continue using the system clock.
+    let value = Dynamic::new(0);
+    std::thread::spawn({
+        let value = value.clone();
+        move || {
+            assert!(!is_manual());
+            value
+                .transition_to(100)
+                .over(Duration::from_millis(1))
+                .launch();
+        }
+    })
+    .join()
+    .unwrap();
+    let started = Instant::now();
+    while value.get() != 100 {
+        assert!(started.elapsed() < Duration::from_secs(5));
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
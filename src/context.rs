@@ -11,8 +11,10 @@ use kludgine::shapes::{Shape, StrokeOptions};
 use kludgine::{Color, Kludgine, KludgineId};
 
 use crate::animation::ZeroToOne;
+use crate::cursor::{CustomCursors, MouseCursor};
+use crate::drawing::{Path, Stroke};
 use crate::fonts::{LoadedFont, LoadedFontFace};
-use crate::graphics::{FontState, Graphics};
+use crate::graphics::{FontState, Graphics, OffscreenTarget};
 use crate::styles::components::{
     CornerRadius, FontFamily, FontStyle, FontWeight, HighlightColor, LayoutOrder, LineHeight,
     Opacity, TextSize, WidgetBackground,
@@ -172,8 +174,8 @@ impl<'context> EventContext<'context> {
                 cursor = widget_cursor;
             }
         }
-        self.window_mut()
-            .set_cursor(cursor.unwrap_or_default().into());
+        let cursor = cursor.unwrap_or_default().themed(&self.get(&CustomCursors));
+        self.set_mouse_cursor(cursor);
 
         for unhovered in changes.unhovered {
             let mut context = self.for_other(&unhovered);
@@ -190,7 +192,45 @@ impl<'context> EventContext<'context> {
             old_hover.lock().as_widget().unhover(&mut old_hover_context);
         }
 
+        self.set_mouse_cursor(MouseCursor::default());
+    }
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        let image = match cursor {
+            MouseCursor::Icon(icon) => {
+                self.cursor.image = None;
+                self.window_mut().set_cursor(icon.into());
+                return;
+            }
+            MouseCursor::Image(image) => image,
+        };
+        if self.cursor.image.as_ref() == Some(&image) {
+            return;
+        }
+        self.cursor.image = Some(image.clone());
+
+        if let Some(custom) = self.cursor.custom.get(&image).cloned() {
+            self.window_mut().set_cursor(Cursor::Custom(custom));
+            return;
+        }
+
+        // Native cursors can only be created on the event loop thread. The
+        // default cursor is shown until the custom cursor has been created.
         self.window_mut().set_cursor(Cursor::default());
+        let (Some(source), Some(app)) = (image.source().cloned(), self.window().app().cloned())
+        else {
+            return;
+        };
+        let window = self.window().handle();
+        app.execute(move |app| {
+            let custom = app.create_custom_cursor(source);
+            window.execute(move |context| {
+                context.cursor.custom.insert(image.clone(), custom.clone());
+                if context.cursor.image.as_ref() == Some(&image) {
+                    context.window_mut().set_cursor(Cursor::Custom(custom));
+                }
+            });
+        });
     }
 
     fn apply_pending_activation(&mut self) {
@@ -620,6 +660,74 @@ impl<'context, 'clip, 'gfx, 'pass> GraphicsContext<'context, 'clip, 'gfx, 'pass>
         }
     }
 
+    /// Returns a new graphics context whose path drawing is clipped to the
+    /// inside of `path`.
+    ///
+    /// See [`Graphics::clipped_to_path`] for more information.
+    pub fn clipped_to_path(&mut self, path: &Path) -> GraphicsContext<'_, '_, 'gfx, 'pass> {
+        GraphicsContext {
+            widget: self.widget.borrowed(),
+            gfx: Exclusive::Owned(self.gfx.clipped_to_path(path)),
+        }
+    }
+
+    /// Redraws `child` into `target` rather than into this context.
+    ///
+    /// Returns false if nothing was rendered. This happens when `child` has not
+    /// been laid out, or when its layout is empty or too large to render
+    /// offscreen.
+    pub(crate) fn redraw_offscreen(
+        &mut self,
+        child: &MountedWidget,
+        target: &mut OffscreenTarget,
+    ) -> bool {
+        let Some(layout) = child.last_layout() else {
+            return false;
+        };
+        let region = layout - self.gfx.region().origin;
+
+        // The offscreen renderer has its own font state.
+        self.widget.font_state.current_font_family = None;
+        let widget = &mut self.widget;
+        let rendered = self.gfx.render_offscreen(target, region, |gfx| {
+            GraphicsContext {
+                widget: widget.for_other(child),
+                gfx: Exclusive::Owned(gfx),
+            }
+            .redraw();
+        });
+        self.widget.font_state.current_font_family = None;
+        rendered
+    }
+
+    /// Redraws `children` into `target`, covering this widget's entire region,
+    /// rather than into this context.
+    ///
+    /// Returns false if nothing was rendered. This happens when this widget's
+    /// region is empty or too large to render offscreen.
+    pub(crate) fn redraw_children_offscreen<'child>(
+        &mut self,
+        children: impl IntoIterator<Item = &'child MountedWidget>,
+        target: &mut OffscreenTarget,
+    ) -> bool {
+        let region = Rect::from(self.gfx.region().size);
+
+        // The offscreen renderer has its own font state.
+        self.widget.font_state.current_font_family = None;
+        let widget = &mut self.widget;
+        let rendered = self.gfx.render_offscreen(target, region, |gfx| {
+            let mut context = GraphicsContext {
+                widget: widget.borrowed(),
+                gfx: Exclusive::Owned(gfx),
+            };
+            for child in children {
+                context.for_other(child).redraw();
+            }
+        });
+        self.widget.font_state.current_font_family = None;
+        rendered
+    }
+
     /// Fills the background of this widget with `color`, honoring the current
     /// [`CornerRadius`] setting.
     ///
@@ -664,6 +772,27 @@ impl<'context, 'clip, 'gfx, 'pass> GraphicsContext<'context, 'clip, 'gfx, 'pass>
         }
     }
 
+    /// Strokes an outline around this widget's contents using `stroke`,
+    /// honoring the current [`CornerRadius`] setting.
+    ///
+    /// Unlike [`stroke_outline()`](Self::stroke_outline), this function
+    /// supports gradients and dashed outlines.
+    pub fn stroke_outline_with(&mut self, stroke: &Stroke) {
+        let half_width = stroke.width / 2;
+        let outline = Rect::new(
+            Point::squared(half_width),
+            self.gfx.region().size - Point::squared(stroke.width),
+        );
+        let radii = self.get(&CornerRadius);
+        let radii = radii.map(|r| r.into_px(self.gfx.scale()));
+        let path = if radii.is_zero() {
+            Path::rect(outline)
+        } else {
+            Path::round_rect(outline, radii)
+        };
+        self.gfx.stroke_path(&path, stroke);
+    }
+
     /// Renders the default focus ring for this widget.
     pub fn draw_focus_ring(&mut self) {
         // If this is the root widget, don't draw a focus ring. It's redundant.
@@ -696,6 +825,9 @@ impl<'context, 'clip, 'gfx, 'pass> GraphicsContext<'context, 'clip, 'gfx, 'pass>
         }
 
         self.tree.note_widget_rendered(self.current_node.node_id);
+        if let Some(profiler) = self.tree.profiler() {
+            profiler.note_redraw(&self.current_node);
+        }
         let widget = self.current_node.clone();
         let mut widget = widget.lock();
         if !widget.as_widget().full_control_redraw() {
@@ -784,6 +916,9 @@ impl<'context, 'clip, 'gfx, 'pass> LayoutContext<'context, 'clip, 'gfx, 'pass> {
                 return cached;
             }
         }
+        if let Some(profiler) = self.graphics.widget.tree.profiler() {
+            profiler.note_layout(&self.graphics.current_node);
+        }
         let result = self
             .graphics
             .current_node
@@ -1012,8 +1147,16 @@ impl<'context> WidgetContext<'context> {
     }
 
     /// Ensures that this widget will be redrawn when `value` has been updated.
+    ///
+    /// If this widget is contained within a
+    /// [`Cached`](crate::widgets::Cached) widget, this widget is invalidated
+    /// instead so that the cached contents are re-rendered.
     pub fn redraw_when_changed(&self, value: &impl Trackable) {
-        value.inner_redraw_when_changed(self.handle());
+        if self.tree.is_cached(self.current_node.node_id) {
+            self.invalidate_when_changed(value);
+        } else {
+            value.inner_redraw_when_changed(self.handle());
+        }
     }
 
     /// Ensures that this widget will be redrawn when `value` has been updated.
@@ -1424,11 +1567,12 @@ impl<T> Trackable for T where T: sealed::Trackable {}
 
 pub(crate) mod sealed {
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::Arc;
+    use std::sync::{Arc, OnceLock};
 
     use kempt::Set;
     use parking_lot::{Mutex, MutexGuard};
 
+    use crate::profiler::{Invalidation, Profiler};
     use crate::widget::WidgetId;
     use crate::window::WindowHandle;
 
@@ -1443,6 +1587,7 @@ pub(crate) mod sealed {
         refresh_sent: Arc<AtomicBool>,
         sync_sent: Arc<AtomicBool>,
         invalidated: Arc<Mutex<Set<WidgetId>>>,
+        profiler: Arc<OnceLock<Profiler>>,
     }
 
     impl InvalidationStatus {
@@ -1474,6 +1619,17 @@ pub(crate) mod sealed {
         pub fn invalidations(&self) -> MutexGuard<'_, Set<WidgetId>> {
             self.invalidated.lock()
         }
+
+        pub fn set_profiler(&self, profiler: Profiler) {
+            profiler.note_attached();
+            let _ = self.profiler.set(profiler);
+        }
+
+        pub fn note_invalidation(&self, invalidation: Invalidation) {
+            if let Some(profiler) = self.profiler.get() {
+                profiler.note_invalidation(invalidation);
+            }
+        }
     }
 
     impl Eq for InvalidationStatus {}
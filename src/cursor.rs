@@ -0,0 +1,236 @@
+//! Types for customizing the mouse cursor.
+//!
+//! Widgets choose the cursor to display by returning a [`MouseCursor`] from
+//! [`Widget::hover`](crate::widget::Widget::hover). A [`MouseCursor`] can
+//! either be one of the operating system's standard [`CursorIcon`]s or a
+//! [`CursorImage`].
+//!
+//! Applications can replace standard cursors with custom images using the
+//! [`CustomCursors`] component:
+//!
+//! ```rust
+//! use cushy::cursor::{CursorImage, CursorTheme, CustomCursors};
+//! use cushy::figures::Point;
+//! use cushy::kludgine::app::winit::window::CursorIcon;
+//! use cushy::widget::MakeWidget;
+//! use image::RgbaImage;
+//!
+//! let i_beam = CursorImage::new(RgbaImage::new(8, 16), Point::new(4_u32, 8));
+//! let widget = "Hello".with(
+//!     &CustomCursors,
+//!     CursorTheme::new().with(CursorIcon::Text, i_beam),
+//! );
+//! ```
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use figures::units::UPx;
+use figures::{Point, Size};
+use image::RgbaImage;
+use kludgine::app::winit::window::{CursorIcon, CustomCursor, CustomCursorSource};
+
+use crate::styles::{Component, RequireInvalidation};
+
+/// A mouse cursor displayed while a widget is hovered.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MouseCursor {
+    /// A standard cursor provided by the operating system.
+    Icon(CursorIcon),
+    /// A custom cursor image.
+    Image(CursorImage),
+}
+
+impl MouseCursor {
+    /// Returns the cursor to display after replacing standard icons using
+    /// `theme`.
+    #[must_use]
+    pub fn themed(self, theme: &CursorTheme) -> Self {
+        match self {
+            MouseCursor::Icon(icon) => theme
+                .get(icon)
+                .cloned()
+                .map_or(MouseCursor::Icon(icon), MouseCursor::Image),
+            image @ MouseCursor::Image(_) => image,
+        }
+    }
+}
+
+impl Default for MouseCursor {
+    fn default() -> Self {
+        Self::Icon(CursorIcon::Default)
+    }
+}
+
+impl From<CursorIcon> for MouseCursor {
+    fn from(icon: CursorIcon) -> Self {
+        Self::Icon(icon)
+    }
+}
+
+impl From<CursorImage> for MouseCursor {
+    fn from(image: CursorImage) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// A custom image to use as a mouse cursor.
+///
+/// Custom cursors are displayed by the operating system. The native cursor is
+/// created the first time the image is shown in a window and cached by that
+/// window for future use. Virtual windows do not display custom cursors.
+///
+/// This type is cheap to clone. Two cursor images are only equal if one is a
+/// clone of the other.
+#[derive(Clone)]
+pub struct CursorImage(Arc<CursorImageData>);
+
+struct CursorImageData {
+    source: Option<CustomCursorSource>,
+    size: Size<UPx>,
+    hotspot: Point<UPx>,
+}
+
+impl CursorImage {
+    /// Returns a cursor that displays `image`.
+    ///
+    /// `hotspot` is the pixel within `image` that is placed at the cursor's
+    /// location.
+    ///
+    /// If the operating system does not accept `image` as a cursor, such as
+    /// when it is too large or `hotspot` is outside of its bounds, a warning
+    /// is logged and the default cursor is shown in its place.
+    #[must_use]
+    pub fn new(image: RgbaImage, hotspot: Point<impl Into<UPx>>) -> Self {
+        let size = Size::new(image.width(), image.height()).map(UPx::new);
+        let hotspot = hotspot.map(Into::into);
+        let source = match (
+            u16::try_from(image.width()),
+            u16::try_from(image.height()),
+            u16::try_from(hotspot.x.get()),
+            u16::try_from(hotspot.y.get()),
+        ) {
+            (Ok(width), Ok(height), Ok(hotspot_x), Ok(hotspot_y)) => {
+                CustomCursor::from_rgba(image.into_raw(), width, height, hotspot_x, hotspot_y)
+                    .map_err(|err| tracing::warn!("invalid cursor image: {err}"))
+                    .ok()
+            }
+            _ => {
+                tracing::warn!("cursor image is too large");
+                None
+            }
+        };
+        Self(Arc::new(CursorImageData {
+            source,
+            size,
+            hotspot,
+        }))
+    }
+
+    /// Returns the size of this cursor's image, in pixels.
+    #[must_use]
+    pub fn size(&self) -> Size<UPx> {
+        self.0.size
+    }
+
+    /// Returns the location within the image that is placed at the cursor's
+    /// location.
+    #[must_use]
+    pub fn hotspot(&self) -> Point<UPx> {
+        self.0.hotspot
+    }
+
+    pub(crate) fn source(&self) -> Option<&CustomCursorSource> {
+        self.0.source.as_ref()
+    }
+}
+
+impl Debug for CursorImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorImage")
+            .field("size", &self.0.size)
+            .field("hotspot", &self.0.hotspot)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for CursorImage {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CursorImage {}
+
+impl Hash for CursorImage {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state);
+    }
+}
+
+/// A set of custom images that replace standard [`CursorIcon`]s.
+///
+/// This type is used by the [`CustomCursors`] component.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CursorTheme(Arc<AHashMap<CursorIcon, CursorImage>>);
+
+impl CursorTheme {
+    /// Returns an empty theme that does not replace any cursors.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces `icon` with `image` and returns self.
+    #[must_use]
+    pub fn with(mut self, icon: CursorIcon, image: CursorImage) -> Self {
+        self.insert(icon, image);
+        self
+    }
+
+    /// Replaces `icon` with `image`.
+    pub fn insert(&mut self, icon: CursorIcon, image: CursorImage) {
+        Arc::make_mut(&mut self.0).insert(icon, image);
+    }
+
+    /// Returns the image that replaces `icon`, if one has been provided.
+    #[must_use]
+    pub fn get(&self, icon: CursorIcon) -> Option<&CursorImage> {
+        self.0.get(&icon)
+    }
+}
+
+impl From<CursorTheme> for Component {
+    fn from(theme: CursorTheme) -> Self {
+        Component::custom(theme)
+    }
+}
+
+impl TryFrom<Component> for CursorTheme {
+    type Error = Component;
+
+    fn try_from(value: Component) -> Result<Self, Self::Error> {
+        match value {
+            Component::Custom(custom) => custom
+                .downcast()
+                .cloned()
+                .ok_or_else(|| Component::Custom(custom)),
+            other => Err(other),
+        }
+    }
+}
+
+impl RequireInvalidation for CursorTheme {
+    fn requires_invalidation(&self) -> bool {
+        false
+    }
+}
+
+define_components! {
+    Cursor {
+        /// Custom images to display in place of standard [`CursorIcon`]s.
+        CustomCursors(CursorTheme, "custom_cursors", CursorTheme::default())
+    }
+}
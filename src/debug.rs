@@ -1,13 +1,24 @@
 //! Utililies to help debug Cushy apps.
 
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
+use std::sync::Arc;
 
 use alot::OrderedLots;
+use figures::units::{Lp, Px};
+use figures::{Point, Rect, Round, ScreenScale, Zero};
+use kludgine::app::winit::event::ElementState;
+use kludgine::app::winit::keyboard::{Key, NamedKey};
+use kludgine::shapes::{Shape, StrokeOptions};
+use parking_lot::Mutex;
 
-use crate::value::{Dynamic, DynamicReader, ForEach, Source, WeakDynamic};
-use crate::widget::{MakeWidget, WidgetInstance, WidgetList};
+use crate::app::{App, Open};
+use crate::context::GraphicsContext;
+use crate::styles::components::{HighlightColor, IntrinsicPadding};
+use crate::value::{Destination, Dynamic, DynamicReader, ForEach, Source, WeakDynamic};
+use crate::widget::{MakeWidget, MountedWidget, WidgetId, WidgetInstance, WidgetList};
+use crate::widgets::button::ButtonKind;
 use crate::widgets::grid::{Grid, GridWidgets};
-use crate::window::{MakeWindow, Window};
+use crate::window::{KeyEvent, MakeWindow, Window, WindowHandle};
 
 /// A widget that can provide extra information when debugging.
 #[derive(Clone, Default)]
@@ -247,6 +258,436 @@ impl DebugSection {
     }
 }
 
+/// A tool for inspecting the widgets of a window.
+///
+/// Attach an inspector to a window using
+/// [`Window::inspector`](crate::window::Window::inspector) or
+/// [`StandaloneWindowBuilder::inspector`](crate::window::StandaloneWindowBuilder::inspector).
+/// Pressing the inspector's hotkey ([`NamedKey::F12`] by default) while the
+/// window is focused toggles the inspector. While enabled:
+///
+/// - The widget under the mouse cursor is highlighted by outlining its layout
+///   rectangle and its resolved [`IntrinsicPadding`].
+/// - A separate "Cushy Inspector" window shows the inspected widget's type,
+///   [`WidgetId`](crate::widget::WidgetId), layout, focus/hover/active state,
+///   and effective style components.
+/// - The inspector window also contains a collapsible tree of every widget
+///   mounted in the window, which is rebuilt when widgets are mounted or
+///   unmounted. Clicking a widget in the tree inspects it.
+///
+/// This type is cheap to clone. All clones share the same state.
+#[derive(Clone)]
+pub struct Inspector(Arc<InspectorData>);
+
+struct InspectorData {
+    hotkey: Key,
+    enabled: Dynamic<bool>,
+    root: Dynamic<Option<MountedWidget>>,
+    inspected: Dynamic<Option<MountedWidget>>,
+    details: Dynamic<Vec<(String, String)>>,
+    details_for: Mutex<Option<(WidgetId, Px)>>,
+    tree_generation: Dynamic<usize>,
+    window: Mutex<Option<WindowHandle>>,
+}
+
+impl Inspector {
+    /// Returns a new inspector that is toggled using [`NamedKey::F12`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hotkey(Key::Named(NamedKey::F12))
+    }
+
+    /// Returns a new inspector that is toggled using `hotkey`.
+    #[must_use]
+    pub fn with_hotkey(hotkey: Key) -> Self {
+        Self(Arc::new(InspectorData {
+            hotkey,
+            enabled: Dynamic::new(false),
+            root: Dynamic::new(None),
+            inspected: Dynamic::new(None),
+            details: Dynamic::default(),
+            details_for: Mutex::new(None),
+            tree_generation: Dynamic::default(),
+            window: Mutex::new(None),
+        }))
+    }
+
+    /// Returns true if this inspector is currently enabled.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.0.enabled.get()
+    }
+
+    /// Returns a dynamic containing the widget currently being inspected.
+    #[must_use]
+    pub fn inspected(&self) -> &Dynamic<Option<MountedWidget>> {
+        &self.0.inspected
+    }
+
+    /// Handles `input` if it is a press of this inspector's hotkey. Returns
+    /// true if the event was handled.
+    pub(crate) fn handle_key(&self, input: &KeyEvent, root: &MountedWidget, app: &App) -> bool {
+        if input.logical_key != self.0.hotkey {
+            return false;
+        }
+
+        if input.state == ElementState::Pressed && !input.repeat {
+            let enabled = !self.0.enabled.get();
+            self.0.enabled.set(enabled);
+            let mut window = self.0.window.lock();
+            if enabled {
+                self.0.root.set(Some(root.clone()));
+                if window.is_none() {
+                    *window = self.clone().open(&mut app.clone()).ok();
+                }
+            } else if let Some(window) = window.take() {
+                window.request_close();
+            }
+        }
+        true
+    }
+
+    /// Inspects `widget` if this inspector is enabled.
+    pub(crate) fn hover(&self, widget: Option<MountedWidget>) {
+        if widget.is_some() && self.0.enabled.get() {
+            // The details include the widget's hover state, so they are
+            // refreshed the next time the window is redrawn.
+            self.0.details_for.lock().take();
+            self.0.inspected.set(widget);
+        }
+    }
+
+    /// Highlights the inspected widget, if this inspector is enabled.
+    pub(crate) fn redraw(&self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.redraw_when_changed(&self.0.enabled);
+        context.redraw_when_changed(&self.0.inspected);
+        if !self.0.enabled.get() {
+            return;
+        }
+        self.0
+            .tree_generation
+            .set(context.tree.structure_generation());
+        let Some(inspected) = self.0.inspected.get() else {
+            return;
+        };
+        let Some(mut context) = context.for_other(&inspected.id()) else {
+            return;
+        };
+
+        let color = context.get(&HighlightColor);
+        context.stroke_outline::<Lp>(color, StrokeOptions::lp_wide(Lp::points(1)));
+
+        let padding = context
+            .get(&IntrinsicPadding)
+            .into_px(context.gfx.scale())
+            .round();
+        let size = context.gfx.region().size;
+        if padding > Px::ZERO && size.width > padding * 2 && size.height > padding * 2 {
+            let inner = Rect::new(Point::squared(padding), size - Point::squared(padding * 2));
+            context.gfx.draw_shape(&Shape::stroked_rect(
+                inner,
+                StrokeOptions::px_wide(Px::new(1)).colored(color.with_alpha(color.alpha() / 2)),
+            ));
+        }
+
+        let mut details_for = self.0.details_for.lock();
+        if *details_for != Some((inspected.id(), padding)) {
+            *details_for = Some((inspected.id(), padding));
+            drop(details_for);
+            self.0.details.set(Self::details(&inspected, padding));
+        }
+    }
+
+    fn details(widget: &MountedWidget, padding: Px) -> Vec<(String, String)> {
+        let mut details = vec![
+            (
+                String::from("Type"),
+                String::from(widget.lock().as_widget().type_name()),
+            ),
+            (String::from("ID"), format!("{:?}", widget.id())),
+            (
+                String::from("Layout"),
+                widget
+                    .last_layout()
+                    .map_or_else(|| String::from("None"), |layout| format!("{layout:?}")),
+            ),
+            (String::from("Padding"), format!("{padding:?}")),
+            (String::from("Focused"), widget.focused().to_string()),
+            (String::from("Hovered"), widget.hovered().to_string()),
+            (String::from("Active"), widget.active().to_string()),
+        ];
+        let mut styles = widget
+            .effective_styles()
+            .into_iter()
+            .map(|(name, value)| {
+                let name = if name.group.is_empty() {
+                    name.name.to_string()
+                } else {
+                    format!("{}.{}", &*name.group, &*name.name)
+                };
+                (name, format!("{value:?}"))
+            })
+            .collect::<Vec<_>>();
+        styles.sort();
+        details.extend(styles);
+        details
+    }
+
+    fn tree_widget(&self, widget: &MountedWidget, depth: usize) -> WidgetInstance {
+        let label = short_type_name(widget.lock().as_widget().type_name())
+            .into_button()
+            .kind(ButtonKind::Transparent)
+            .on_click({
+                let inspected = self.0.inspected.clone();
+                let widget = widget.clone();
+                move |_| inspected.set(Some(widget.clone()))
+            });
+        let children = widget
+            .tree
+            .upgrade()
+            .map(|tree| tree.children(widget.node_id))
+            .unwrap_or_default();
+        if children.is_empty() {
+            label.make_widget()
+        } else {
+            children
+                .iter()
+                .map(|child| self.tree_widget(child, depth + 1))
+                .collect::<WidgetList>()
+                .into_rows()
+                .disclose()
+                .labelled_by(label)
+                .collapsed(depth > 1)
+                .make_widget()
+        }
+    }
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MakeWindow for Inspector {
+    type Behavior = WidgetInstance;
+
+    fn make_window(self) -> Window<Self::Behavior> {
+        let details = Grid::from_rows(self.0.details.map_each(|details| {
+            details
+                .iter()
+                .map(|(label, value)| (label.as_str(), value.as_str().align_left()))
+                .collect::<GridWidgets<2>>()
+        }));
+
+        let tree = (&self.0.root, &self.0.tree_generation)
+            .map_each({
+                let this = self.clone();
+                move |(root, _)| match root {
+                    Some(root) if root.is_mounted() => this.tree_widget(root, 0),
+                    _ => "No window is being inspected".make_widget(),
+                }
+            })
+            .into_switcher();
+
+        let this = self.clone();
+        details
+            .vertical_scroll()
+            .expand()
+            .and(tree.vertical_scroll().expand())
+            .into_columns()
+            .make_window()
+            .titled("Cushy Inspector")
+            .on_close(move || {
+                this.0.window.lock().take();
+                this.0.enabled.set(false);
+            })
+    }
+}
+
+/// A snapshot of the widgets mounted in a window.
+///
+/// Dumps can be created using
+/// [`VirtualWindow::dump_tree`](crate::window::VirtualWindow::dump_tree),
+/// [`CushyWindow::dump_tree`](crate::window::CushyWindow::dump_tree), or
+/// [`WindowHandle::dump_tree`].
+///
+/// The [`Display`] implementation writes one line per widget, indenting
+/// children beneath their parents. This makes dumps useful for comparing
+/// layouts in tests and attaching to bug reports. When the `serde` feature is
+/// enabled, dumps can also be serialized, for example as JSON.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WidgetTreeDump {
+    /// The widgets that have no parent, typically the window's root widget.
+    pub roots: Vec<WidgetDump>,
+}
+
+impl Display for WidgetTreeDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for root in &self.roots {
+            root.write_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+/// A snapshot of a mounted widget and its children.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WidgetDump {
+    /// The Rust type name of the widget.
+    ///
+    /// If the widget was locked when the dump was created, this is
+    /// `"<locked>"`.
+    pub type_name: &'static str,
+    /// The widget's unique id.
+    pub id: WidgetId,
+    /// The widget's most recent layout, in window coordinates.
+    pub layout: Option<Rect<Px>>,
+    /// True if the widget was drawn during the most recent redraw.
+    pub visible: bool,
+    /// True if the widget currently has keyboard focus.
+    pub focused: bool,
+    /// The output of [`Widget::summarize`](crate::widget::Widget::summarize).
+    pub summary: String,
+    /// The widget's children.
+    pub children: Vec<WidgetDump>,
+}
+
+impl WidgetDump {
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} {:?}",
+            "",
+            short_type_name(self.type_name),
+            self.id,
+            indent = depth * 2
+        )?;
+        match &self.layout {
+            Some(layout) => write!(f, " {layout:?}")?,
+            None => f.write_str(" (no layout)")?,
+        }
+        if !self.visible {
+            f.write_str(" hidden")?;
+        }
+        if self.focused {
+            f.write_str(" focused")?;
+        }
+        writeln!(f, ": {}", self.summary)?;
+        for child in &self.children {
+            child.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `name` with module paths removed.
+fn short_type_name(name: &str) -> String {
+    name.split_inclusive(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == ':'))
+        .filter_map(|part| part.rsplit("::").next())
+        .collect()
+}
+
+#[test]
+fn short_type_names() {
+    assert_eq!(
+        short_type_name("cushy::widgets::label::Label<alloc::string::String>"),
+        "Label<String>"
+    );
+    assert_eq!(
+        short_type_name("core::option::Option<(u8, alloc::sync::Arc<str>)>"),
+        "Option<(u8, Arc<str>)>"
+    );
+}
+
+#[test]
+fn tree_dump_display() {
+    use figures::Size;
+
+    use crate::widget::WidgetTag;
+
+    let parent = WidgetTag::unique().id();
+    let child = WidgetTag::unique().id();
+    let dump = WidgetTreeDump {
+        roots: vec![WidgetDump {
+            type_name: "cushy::widgets::align::Align",
+            id: parent,
+            layout: Some(Rect::new(Point::px(0, 0), Size::px(100, 50))),
+            visible: true,
+            focused: false,
+            summary: String::from("Align"),
+            children: vec![WidgetDump {
+                type_name: "cushy::widgets::label::Label<alloc::string::String>",
+                id: child,
+                layout: None,
+                visible: false,
+                focused: true,
+                summary: String::from("Label"),
+                children: Vec::new(),
+            }],
+        }],
+    };
+    let layout = Rect::new(Point::px(0, 0), Size::px(100, 50));
+    assert_eq!(
+        dump.to_string(),
+        format!(
+            "Align {parent:?} {layout:?}: Align\n  Label<String> {child:?} (no layout) hidden focused: Label\n"
+        )
+    );
+}
+
+#[test]
+fn mounted_tree_dump() {
+    use figures::Size;
+
+    use crate::tree::Tree;
+
+    let hello = "Hello".make_widget();
+    let hidden = "Hidden".make_widget();
+    let stack = hello.clone().and(hidden.clone()).into_rows().make_widget();
+    let root = stack.clone().centered().make_widget();
+
+    let tree = Tree::default();
+    let mounted_root = tree.push_boxed(root.clone(), None);
+    let mounted_stack = tree.push_boxed(stack.clone(), Some(&mounted_root));
+    let mounted_hello = tree.push_boxed(hello.clone(), Some(&mounted_stack));
+    tree.push_boxed(hidden.clone(), Some(&mounted_stack));
+
+    let layout = Rect::new(Point::px(0, 0), Size::px(100, 50));
+    tree.set_layout(mounted_hello.node_id, layout);
+    tree.note_widget_rendered(mounted_hello.node_id);
+    tree.focus(Some(hello.id())).unwrap();
+
+    let dump = tree.dump();
+    let [root_dump] = &dump.roots[..] else {
+        unreachable!("expected one root: {dump}")
+    };
+    assert_eq!(root_dump.id, root.id());
+    assert!(!root_dump.visible);
+    let [stack_dump] = &root_dump.children[..] else {
+        unreachable!("expected one child: {dump}")
+    };
+    assert_eq!(stack_dump.id, stack.id());
+    let [hello_dump, hidden_dump] = &stack_dump.children[..] else {
+        unreachable!("expected two children: {dump}")
+    };
+    assert_eq!(hello_dump.id, hello.id());
+    assert_eq!(hello_dump.layout, Some(layout));
+    assert!(hello_dump.visible);
+    assert!(hello_dump.focused);
+    assert!(hello_dump.summary.contains("Hello"));
+    assert_eq!(hidden_dump.id, hidden.id());
+    assert_eq!(hidden_dump.layout, None);
+    assert!(!hidden_dump.visible);
+    assert!(!hidden_dump.focused);
+
+    // Summaries of parents do not repeat their children's summaries.
+    assert!(!root_dump.summary.contains("Hello"));
+    assert!(!stack_dump.summary.contains("Hidden"));
+}
+
 #[test]
 fn empty_child_clears_on_drop() {
     let root = DebugContext::default();
@@ -0,0 +1,1299 @@
+//! Vector path drawing with gradient fills and dashed strokes.
+//!
+//! Kludgine's [`Shape`](kludgine::shapes::Shape) type covers rectangles,
+//! circles, and simple paths. This module provides a higher level API for
+//! drawing arbitrary vector graphics in a
+//! [`Graphics`](crate::Graphics) context:
+//!
+//! - [`PathBuilder`] constructs [`Path`]s from lines, quadratic and cubic
+//!   curves, and arcs.
+//! - [`Paint`] describes how a path is filled: a solid [`Color`], a
+//!   [`LinearGradient`], or a [`RadialGradient`].
+//! - [`Stroke`] describes how a path's outline is drawn, including optional
+//!   [`DashPattern`]s.
+//! - [`Graphics::clipped_to_path`](crate::Graphics::clipped_to_path) restricts
+//!   path drawing to the inside of another path.
+//!
+//! ```rust
+//! use cushy::drawing::{DashPattern, LinearGradient, PathBuilder, Stroke};
+//! use cushy::figures::units::Px;
+//! use cushy::figures::{Point, Px2D};
+//! use cushy::kludgine::Color;
+//! use cushy::widgets::Canvas;
+//!
+//! Canvas::new(|context| {
+//!     let triangle = PathBuilder::new(Point::px(50, 0))
+//!         .line_to(Point::px(100, 100))
+//!         .line_to(Point::px(0, 100))
+//!         .close()
+//!         .build();
+//!     context.gfx.fill_path(
+//!         &triangle,
+//!         LinearGradient::new(Point::px(0, 0), Point::px(0, 100))
+//!             .with_stop(0., Color::RED)
+//!             .with_stop(1., Color::BLUE),
+//!     );
+//!     context.gfx.stroke_path(
+//!         &triangle,
+//!         &Stroke::new(Color::WHITE, Px::new(2))
+//!             .dashed(DashPattern::new([Px::new(6), Px::new(3)])),
+//!     );
+//! });
+//! ```
+use std::cmp::Ordering;
+use std::f32::consts::{PI, TAU};
+
+use figures::units::Px;
+use figures::{Angle, FloatConversion, Point, Rect, Size, Zero};
+use intentional::Cast;
+use kludgine::shapes::{CornerRadii, PathBuilder as ShapeBuilder, StrokeOptions};
+use kludgine::Color;
+
+use crate::animation::{LinearInterpolate, ZeroToOne};
+
+/// The default maximum distance, in pixels, between a flattened curve and the
+/// true curve.
+const DEFAULT_TOLERANCE: f32 = 0.25;
+
+/// The maximum number of line segments a single curve is flattened into.
+const MAX_CURVE_SEGMENTS: usize = 1024;
+
+/// The minimum height of a row when decomposing a filled path.
+const ROW_EPSILON: f32 = 1. / 256.;
+
+/// A vector path composed of one or more subpaths.
+///
+/// Curves are flattened into line segments when the path is built, which
+/// allows paths to be filled with gradients, dashed, and used as clipping
+/// regions. Paths are created using a [`PathBuilder`].
+///
+/// All coordinates are relative to the origin of the
+/// [`Graphics`](crate::Graphics) context the path is drawn in. Flattened
+/// points are stored with sub-pixel precision, but are converted to whole
+/// pixels when the path is drawn.
+///
+/// When a path is filled or used as a clipping region, each subpath is
+/// treated as closed, and the path's [`FillRule`] determines which areas are
+/// inside of the path. This allows paths to contain holes.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    subpaths: Vec<SubPath>,
+    fill_rule: FillRule,
+}
+
+impl Path {
+    /// Returns a closed path outlining `rect`.
+    #[must_use]
+    pub fn rect(rect: Rect<Px>) -> Self {
+        let (top_left, bottom_right) = rect.extents();
+        PathBuilder::new(top_left)
+            .line_to(Point::new(bottom_right.x, top_left.y))
+            .line_to(bottom_right)
+            .line_to(Point::new(top_left.x, bottom_right.y))
+            .close()
+            .build()
+    }
+
+    /// Returns a closed path outlining `rect` with its corners rounded using
+    /// `radii`.
+    #[must_use]
+    pub fn round_rect(rect: Rect<Px>, radii: CornerRadii<Px>) -> Self {
+        let (top_left, bottom_right) = rect.extents();
+        let max_radius = rect.size.width.min(rect.size.height) / 2;
+        let radii = radii.map(|r| r.min(max_radius).max(Px::ZERO));
+
+        PathBuilder::new(Point::new(top_left.x + radii.top_left, top_left.y))
+            .line_to(Point::new(bottom_right.x - radii.top_right, top_left.y))
+            .arc(
+                Point::new(
+                    bottom_right.x - radii.top_right,
+                    top_left.y + radii.top_right,
+                ),
+                Size::squared(radii.top_right),
+                Angle::degrees(270),
+                Angle::degrees(90),
+            )
+            .line_to(Point::new(
+                bottom_right.x,
+                bottom_right.y - radii.bottom_right,
+            ))
+            .arc(
+                bottom_right - Point::squared(radii.bottom_right),
+                Size::squared(radii.bottom_right),
+                Angle::degrees(0),
+                Angle::degrees(90),
+            )
+            .line_to(Point::new(top_left.x + radii.bottom_left, bottom_right.y))
+            .arc(
+                Point::new(
+                    top_left.x + radii.bottom_left,
+                    bottom_right.y - radii.bottom_left,
+                ),
+                Size::squared(radii.bottom_left),
+                Angle::degrees(90),
+                Angle::degrees(90),
+            )
+            .line_to(Point::new(top_left.x, top_left.y + radii.top_left))
+            .arc(
+                top_left + Point::squared(radii.top_left),
+                Size::squared(radii.top_left),
+                Angle::degrees(180),
+                Angle::degrees(90),
+            )
+            .close()
+            .build()
+    }
+
+    /// Returns a closed path approximating a circle.
+    #[must_use]
+    pub fn circle(center: Point<Px>, radius: Px) -> Self {
+        PathBuilder::new(center + Point::new(radius, Px::ZERO))
+            .arc(
+                center,
+                Size::squared(radius),
+                Angle::degrees(0),
+                Angle::degrees(360),
+            )
+            .close()
+            .build()
+    }
+
+    /// Returns true if this path contains no subpaths.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    /// Returns the rule used to determine which areas are inside of this
+    /// path.
+    #[must_use]
+    pub const fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Sets the rule used to determine which areas are inside of this path
+    /// and returns self.
+    #[must_use]
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// Returns the smallest rectangle that contains every point in this path,
+    /// or `None` if the path is empty.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Rect<Px>> {
+        let (min, max) = bounds_of(self.subpaths.iter().flat_map(|s| s.points.iter()))?;
+        let min = Point::new(Px::from(min.x.floor()), Px::from(min.y.floor()));
+        let max = Point::new(Px::from(max.x.ceil()), Px::from(max.y.ceil()));
+        Some(Rect::from_extents(min, max))
+    }
+
+    fn polygons(&self) -> impl Iterator<Item = &[Point<f32>]> + '_ {
+        self.subpaths
+            .iter()
+            .filter(|subpath| subpath.points.len() > 2)
+            .map(|subpath| subpath.points.as_slice())
+    }
+
+    pub(crate) fn polylines(&self) -> impl Iterator<Item = (&[Point<f32>], bool)> + '_ {
+        self.subpaths
+            .iter()
+            .filter(|subpath| subpath.points.len() > 1)
+            .map(|subpath| (subpath.points.as_slice(), subpath.closed))
+    }
+
+    /// Decomposes the area inside of this path into non-overlapping
+    /// trapezoids, honoring this path's [`FillRule`].
+    ///
+    /// Each trapezoid has horizontal top and bottom edges, and its points are
+    /// ordered top-left, top-right, bottom-right, bottom-left.
+    pub(crate) fn trapezoids(&self) -> Vec<[Point<f32>; 4]> {
+        let edges = self
+            .polygons()
+            .flat_map(|polygon| {
+                polygon
+                    .iter()
+                    .zip(polygon.iter().cycle().skip(1))
+                    .filter_map(|(from, to)| Edge::new(*from, *to))
+            })
+            .collect::<Vec<_>>();
+
+        // Rows are split at every vertex and at every point where two edges
+        // cross. This guarantees that edges never cross within a row, which
+        // allows each row to be filled with trapezoids.
+        let mut rows = edges
+            .iter()
+            .flat_map(|edge| [edge.top.y, edge.bottom.y])
+            .collect::<Vec<_>>();
+        for (index, edge) in edges.iter().enumerate() {
+            rows.extend(
+                edges[index + 1..]
+                    .iter()
+                    .filter_map(|other| edge.crossing(other)),
+            );
+        }
+        rows.sort_by(f32::total_cmp);
+        rows.dedup_by(|next, previous| *next - *previous <= ROW_EPSILON);
+
+        let mut trapezoids = Vec::new();
+        let mut crossings = Vec::new();
+        for row in rows.windows(2) {
+            let (top, bottom) = (row[0], row[1]);
+            let middle = (top + bottom) / 2.;
+            crossings.clear();
+            crossings.extend(
+                edges
+                    .iter()
+                    .filter(|edge| edge.top.y <= middle && edge.bottom.y > middle)
+                    .map(|edge| (edge.x_at(middle), edge)),
+            );
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            let mut left = None;
+            for (_, edge) in &crossings {
+                let was_inside = self.fill_rule.is_inside(winding);
+                winding += edge.winding;
+                match (was_inside, self.fill_rule.is_inside(winding)) {
+                    (false, true) => left = Some(*edge),
+                    (true, false) => {
+                        if let Some(left) = left.take() {
+                            trapezoids.push([
+                                Point::new(left.x_at(top), top),
+                                Point::new(edge.x_at(top), top),
+                                Point::new(edge.x_at(bottom), bottom),
+                                Point::new(left.x_at(bottom), bottom),
+                            ]);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        trapezoids
+    }
+}
+
+/// The rule used to determine which areas are inside of a [`Path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Areas that the path's subpaths wind around a non-zero number of times
+    /// are inside of the path.
+    ///
+    /// A subpath wound in the opposite direction of its surrounding subpath
+    /// creates a hole.
+    #[default]
+    NonZero,
+    /// Areas that the path's subpaths wind around an odd number of times are
+    /// inside of the path.
+    ///
+    /// Any subpath contained within another subpath creates a hole,
+    /// regardless of the direction it is wound.
+    EvenOdd,
+}
+
+impl FillRule {
+    const fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// A non-horizontal edge of a filled [`Path`].
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    top: Point<f32>,
+    bottom: Point<f32>,
+    winding: i32,
+}
+
+impl Edge {
+    fn new(from: Point<f32>, to: Point<f32>) -> Option<Self> {
+        match from.y.total_cmp(&to.y) {
+            Ordering::Less => Some(Self {
+                top: from,
+                bottom: to,
+                winding: 1,
+            }),
+            Ordering::Greater => Some(Self {
+                top: to,
+                bottom: from,
+                winding: -1,
+            }),
+            Ordering::Equal => None,
+        }
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        self.top.x + (self.bottom.x - self.top.x) * t
+    }
+
+    /// Returns the y coordinate where `self` and `other` cross, if they cross
+    /// between their shared vertical extents.
+    fn crossing(&self, other: &Self) -> Option<f32> {
+        let top = self.top.y.max(other.top.y);
+        let bottom = self.bottom.y.min(other.bottom.y);
+        if bottom - top <= ROW_EPSILON {
+            return None;
+        }
+        let top_delta = self.x_at(top) - other.x_at(top);
+        let bottom_delta = self.x_at(bottom) - other.x_at(bottom);
+        (top_delta * bottom_delta < 0.)
+            .then(|| top + (bottom - top) * top_delta / (top_delta - bottom_delta))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SubPath {
+    points: Vec<Point<f32>>,
+    closed: bool,
+}
+
+/// Builds a [`Path`] out of line segments, curves, and arcs.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PathBuilder {
+    subpaths: Vec<SubPath>,
+    current: SubPath,
+    tolerance: f32,
+    fill_rule: FillRule,
+}
+
+impl PathBuilder {
+    /// Returns a new builder whose first subpath begins at `start`.
+    pub fn new(start: Point<Px>) -> Self {
+        Self {
+            subpaths: Vec::new(),
+            current: SubPath {
+                points: vec![to_float(start)],
+                closed: false,
+            },
+            tolerance: DEFAULT_TOLERANCE,
+            fill_rule: FillRule::NonZero,
+        }
+    }
+
+    /// Sets the maximum distance, in pixels, that flattened curves may deviate
+    /// from the true curve, and returns self.
+    ///
+    /// Smaller values produce smoother curves at the cost of more geometry.
+    /// The default tolerance is a quarter of a pixel.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance.max(0.01);
+        self
+    }
+
+    fn last_point(&self) -> Point<f32> {
+        *self
+            .current
+            .points
+            .last()
+            .expect("subpaths always have a point")
+    }
+
+    fn push(&mut self, point: Point<f32>) {
+        if self.current.closed {
+            // Continuing after closing begins a new subpath at the start of the
+            // closed subpath.
+            let start = self.current.points[0];
+            self.finish_subpath();
+            self.current.points.push(start);
+        }
+        if self.last_point() != point {
+            self.current.points.push(point);
+        }
+    }
+
+    fn finish_subpath(&mut self) {
+        let finished = std::mem::replace(
+            &mut self.current,
+            SubPath {
+                points: Vec::new(),
+                closed: false,
+            },
+        );
+        if finished.points.len() > 1 {
+            self.subpaths.push(finished);
+        }
+    }
+
+    /// Begins a new subpath at `point`.
+    pub fn move_to(mut self, point: Point<Px>) -> Self {
+        self.finish_subpath();
+        self.current.points.push(to_float(point));
+        self
+    }
+
+    /// Adds a straight line from the current point to `end`.
+    pub fn line_to(mut self, end: Point<Px>) -> Self {
+        self.push(to_float(end));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve from the current point to `end` using
+    /// `control` as the control point.
+    pub fn quadratic_curve_to(mut self, control: Point<Px>, end: Point<Px>) -> Self {
+        let start = self.last_point();
+        let control = to_float(control);
+        let end = to_float(end);
+        let deviation = length(Point::new(
+            start.x - 2. * control.x + end.x,
+            start.y - 2. * control.y + end.y,
+        ));
+        let segments = segment_count((deviation / (8. * self.tolerance)).sqrt());
+        for step in 1..=segments {
+            let t = step.cast::<f32>() / segments.cast::<f32>();
+            let inv = 1. - t;
+            self.push(Point::new(
+                inv * inv * start.x + 2. * inv * t * control.x + t * t * end.x,
+                inv * inv * start.y + 2. * inv * t * control.y + t * t * end.y,
+            ));
+        }
+        self
+    }
+
+    /// Adds a cubic Bézier curve from the current point to `end` using
+    /// `control1` and `control2` as control points.
+    pub fn cubic_curve_to(
+        mut self,
+        control1: Point<Px>,
+        control2: Point<Px>,
+        end: Point<Px>,
+    ) -> Self {
+        let start = self.last_point();
+        let control1 = to_float(control1);
+        let control2 = to_float(control2);
+        let end = to_float(end);
+        let deviation = length(Point::new(
+            start.x - 2. * control1.x + control2.x,
+            start.y - 2. * control1.y + control2.y,
+        ))
+        .max(length(Point::new(
+            control1.x - 2. * control2.x + end.x,
+            control1.y - 2. * control2.y + end.y,
+        )));
+        let segments = segment_count((0.75 * deviation / self.tolerance).sqrt());
+        for step in 1..=segments {
+            let t = step.cast::<f32>() / segments.cast::<f32>();
+            let inv = 1. - t;
+            let a = inv * inv * inv;
+            let b = 3. * inv * inv * t;
+            let c = 3. * inv * t * t;
+            let d = t * t * t;
+            self.push(Point::new(
+                a * start.x + b * control1.x + c * control2.x + d * end.x,
+                a * start.y + b * control1.y + c * control2.y + d * end.y,
+            ));
+        }
+        self
+    }
+
+    /// Adds an elliptical arc around `center`.
+    ///
+    /// The arc begins at `start_angle` and sweeps `sweep_angle` clockwise. A
+    /// straight line is added from the current point to the start of the
+    /// arc. Angles are measured from the positive x axis.
+    pub fn arc(
+        mut self,
+        center: Point<Px>,
+        radii: Size<Px>,
+        start_angle: Angle,
+        sweep_angle: Angle,
+    ) -> Self {
+        let center = to_float(center);
+        let radii = Point::new(radii.width.into_float(), radii.height.into_float());
+        let start = start_angle.into_degrees::<f32>().to_radians();
+        let sweep = sweep_angle.into_degrees::<f32>().to_radians();
+        let radius = radii.x.max(radii.y);
+        let segments = if radius > self.tolerance {
+            let max_step = 2. * (1. - self.tolerance / radius).acos();
+            segment_count(sweep.abs() / max_step)
+        } else {
+            1
+        };
+        for step in 0..=segments {
+            let angle = start + sweep * step.cast::<f32>() / segments.cast::<f32>();
+            self.push(Point::new(
+                center.x + radii.x * angle.cos(),
+                center.y + radii.y * angle.sin(),
+            ));
+        }
+        self
+    }
+
+    /// Closes the current subpath by connecting its last point to its first
+    /// point.
+    ///
+    /// Adding more segments after closing a subpath begins a new subpath at
+    /// the closed subpath's starting point.
+    pub fn close(mut self) -> Self {
+        if self.current.points.len() > 1
+            && self.current.points.first() == self.current.points.last()
+        {
+            self.current.points.pop();
+        }
+        self.current.closed = true;
+        self
+    }
+
+    /// Sets the rule used to determine which areas are inside of the built
+    /// path and returns self.
+    ///
+    /// The default rule is [`FillRule::NonZero`].
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// Returns the built path.
+    #[must_use]
+    pub fn build(mut self) -> Path {
+        self.finish_subpath();
+        Path {
+            subpaths: self.subpaths,
+            fill_rule: self.fill_rule,
+        }
+    }
+}
+
+/// A color stop within a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// The location of this stop along the gradient.
+    pub offset: ZeroToOne,
+    /// The color at this stop.
+    pub color: Color,
+}
+
+/// A list of [`GradientStop`]s sorted by offset.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct GradientStops(Vec<GradientStop>);
+
+impl GradientStops {
+    fn insert(&mut self, offset: ZeroToOne, color: Color) {
+        let index = self.0.partition_point(|stop| stop.offset <= offset);
+        self.0.insert(index, GradientStop { offset, color });
+    }
+
+    fn color_at(&self, offset: f32) -> Color {
+        let Some(first) = self.0.first() else {
+            return Color::CLEAR_BLACK;
+        };
+        if offset <= *first.offset {
+            return first.color;
+        }
+
+        for pair in self.0.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if offset <= *to.offset {
+                let span = *to.offset - *from.offset;
+                if span <= f32::EPSILON {
+                    return to.color;
+                }
+                return from.color.lerp(&to.color, (offset - *from.offset) / span);
+            }
+        }
+
+        self.0.last().expect("not empty").color
+    }
+
+    /// Returns the offsets where the gradient's interpolation changes.
+    fn boundaries(&self) -> impl Iterator<Item = f32> + '_ {
+        self.0.iter().map(|stop| *stop.offset)
+    }
+}
+
+/// A gradient that varies color along a line.
+///
+/// Colors before `start` are painted using the first stop's color, and
+/// colors after `end` are painted using the last stop's color.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct LinearGradient {
+    /// The location of offset 0.0.
+    pub start: Point<Px>,
+    /// The location of offset 1.0.
+    pub end: Point<Px>,
+    stops: GradientStops,
+}
+
+impl LinearGradient {
+    /// Returns a gradient from `start` to `end` with no color stops.
+    pub fn new(start: Point<Px>, end: Point<Px>) -> Self {
+        Self {
+            start,
+            end,
+            stops: GradientStops::default(),
+        }
+    }
+
+    /// Adds a color stop at `offset` and returns self.
+    pub fn with_stop(mut self, offset: impl Into<ZeroToOne>, color: Color) -> Self {
+        self.stops.insert(offset.into(), color);
+        self
+    }
+
+    /// Returns the color stops of this gradient.
+    #[must_use]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops.0
+    }
+}
+
+/// A gradient that varies color outward from a center point.
+///
+/// Colors beyond `radius` are painted using the last stop's color.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct RadialGradient {
+    /// The location of offset 0.0.
+    pub center: Point<Px>,
+    /// The distance from `center` of offset 1.0.
+    pub radius: Px,
+    stops: GradientStops,
+}
+
+impl RadialGradient {
+    /// Returns a gradient centered on `center` with no color stops.
+    pub fn new(center: Point<Px>, radius: Px) -> Self {
+        Self {
+            center,
+            radius,
+            stops: GradientStops::default(),
+        }
+    }
+
+    /// Adds a color stop at `offset` and returns self.
+    pub fn with_stop(mut self, offset: impl Into<ZeroToOne>, color: Color) -> Self {
+        self.stops.insert(offset.into(), color);
+        self
+    }
+
+    /// Returns the color stops of this gradient.
+    #[must_use]
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops.0
+    }
+}
+
+/// Describes how to color the area being painted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    /// A single color.
+    Solid(Color),
+    /// A [`LinearGradient`].
+    LinearGradient(LinearGradient),
+    /// A [`RadialGradient`].
+    RadialGradient(RadialGradient),
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Paint {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::LinearGradient(gradient)
+    }
+}
+
+impl From<RadialGradient> for Paint {
+    fn from(gradient: RadialGradient) -> Self {
+        Self::RadialGradient(gradient)
+    }
+}
+
+/// A repeating pattern of dashes and gaps used when stroking a path.
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct DashPattern {
+    lengths: Vec<Px>,
+    offset: Px,
+}
+
+impl DashPattern {
+    /// Returns a pattern that alternates between drawing and skipping each
+    /// length in `lengths`, starting with a dash.
+    ///
+    /// If an odd number of lengths is provided, the lengths are repeated to
+    /// produce an even number of entries, matching the behavior of SVG's
+    /// `stroke-dasharray`.
+    pub fn new(lengths: impl IntoIterator<Item = Px>) -> Self {
+        let mut lengths = lengths
+            .into_iter()
+            .map(|length| length.max(Px::ZERO))
+            .collect::<Vec<_>>();
+        if lengths.len() % 2 == 1 {
+            lengths.extend_from_within(..);
+        }
+        Self {
+            lengths,
+            offset: Px::ZERO,
+        }
+    }
+
+    /// Sets the distance into the pattern to begin drawing at, and returns
+    /// self.
+    ///
+    /// Animating the offset produces a "marching ants" effect.
+    pub fn offset(mut self, offset: Px) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn total_length(&self) -> f32 {
+        self.lengths.iter().map(|length| length.into_float()).sum()
+    }
+
+    /// Splits `polyline` into the dashes this pattern produces.
+    pub(crate) fn apply(&self, polyline: &[Point<f32>], closed: bool) -> Vec<Vec<Point<f32>>> {
+        let total = self.total_length();
+        if total <= f32::EPSILON {
+            return vec![polyline.to_vec()];
+        }
+
+        let mut dashes = Vec::new();
+        let mut index = 0;
+        let mut remaining = self.offset.into_float().rem_euclid(total);
+        while remaining >= self.lengths[index].into_float() {
+            remaining -= self.lengths[index].into_float();
+            index = (index + 1) % self.lengths.len();
+        }
+        let mut remaining_in_dash = self.lengths[index].into_float() - remaining;
+        let mut current = if index % 2 == 0 {
+            vec![polyline[0]]
+        } else {
+            Vec::new()
+        };
+
+        let closing = closed.then(|| [polyline[polyline.len() - 1], polyline[0]]);
+        let segments = polyline
+            .windows(2)
+            .map(|pair| [pair[0], pair[1]])
+            .chain(closing);
+        for [mut from, to] in segments {
+            let mut segment_length = distance(from, to);
+            while segment_length > remaining_in_dash {
+                let t = remaining_in_dash / segment_length;
+                let split = Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+                if index % 2 == 0 {
+                    current.push(split);
+                    dashes.push(std::mem::take(&mut current));
+                } else {
+                    current.push(split);
+                }
+                segment_length -= remaining_in_dash;
+                from = split;
+                index = (index + 1) % self.lengths.len();
+                remaining_in_dash = self.lengths[index].into_float();
+            }
+            remaining_in_dash -= segment_length;
+            if index % 2 == 0 {
+                current.push(to);
+            }
+        }
+
+        if index % 2 == 0 && current.len() > 1 {
+            dashes.push(current);
+        }
+
+        dashes
+    }
+}
+
+/// Describes how to draw the outline of a [`Path`].
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct Stroke {
+    /// The paint to apply to the outline.
+    pub paint: Paint,
+    /// The width of the outline.
+    pub width: Px,
+    /// The dash pattern, if the outline should be dashed.
+    pub dashes: Option<DashPattern>,
+}
+
+impl Stroke {
+    /// Returns a solid stroke `width` pixels wide.
+    pub fn new(paint: impl Into<Paint>, width: Px) -> Self {
+        Self {
+            paint: paint.into(),
+            width,
+            dashes: None,
+        }
+    }
+
+    /// Dashes this stroke using `pattern` and returns self.
+    pub fn dashed(mut self, pattern: DashPattern) -> Self {
+        self.dashes = Some(pattern);
+        self
+    }
+
+    /// Returns the polylines that should be drawn for `path`, after applying
+    /// the dash pattern.
+    pub(crate) fn polylines(&self, path: &Path) -> Vec<(Vec<Point<f32>>, bool)> {
+        let mut polylines = Vec::new();
+        for (polyline, closed) in path.polylines() {
+            if let Some(dashes) = &self.dashes {
+                polylines.extend(
+                    dashes
+                        .apply(polyline, closed)
+                        .into_iter()
+                        .map(|dash| (dash, false)),
+                );
+            } else {
+                polylines.push((polyline.to_vec(), closed));
+            }
+        }
+        polylines
+    }
+
+    /// Returns convex polygons that together cover the area of `polyline`
+    /// stroked with this stroke's width.
+    ///
+    /// This is used when the stroke can't be delegated to Kludgine's stroke
+    /// tesselator, such as when painting with a gradient or when clipping to
+    /// a path.
+    pub(crate) fn outline_polygons(
+        &self,
+        polyline: &[Point<f32>],
+        closed: bool,
+    ) -> Vec<Vec<Point<f32>>> {
+        let half_width = self.width.into_float() / 2.;
+        let mut polygons = Vec::new();
+        let closing = closed.then(|| [polyline[polyline.len() - 1], polyline[0]]);
+        for [from, to] in polyline
+            .windows(2)
+            .map(|pair| [pair[0], pair[1]])
+            .chain(closing)
+        {
+            let segment_length = distance(from, to);
+            if segment_length <= f32::EPSILON {
+                continue;
+            }
+            let normal = Point::new(
+                -(to.y - from.y) / segment_length * half_width,
+                (to.x - from.x) / segment_length * half_width,
+            );
+            polygons.push(vec![
+                Point::new(from.x + normal.x, from.y + normal.y),
+                Point::new(to.x + normal.x, to.y + normal.y),
+                Point::new(to.x - normal.x, to.y - normal.y),
+                Point::new(from.x - normal.x, from.y - normal.y),
+            ]);
+        }
+
+        // Round joins between segments.
+        let joins = if closed {
+            polyline
+        } else {
+            &polyline[1..polyline.len() - 1]
+        };
+        let join_segments = segment_count(PI * half_width.sqrt()).max(8);
+        for center in joins {
+            polygons.push(
+                (0..join_segments)
+                    .map(|step| {
+                        let angle = TAU * step.cast::<f32>() / join_segments.cast::<f32>();
+                        Point::new(
+                            center.x + half_width * angle.cos(),
+                            center.y + half_width * angle.sin(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        polygons
+    }
+
+    pub(crate) fn options(&self, color: Color) -> StrokeOptions<Px> {
+        StrokeOptions::px_wide(self.width).colored(color)
+    }
+}
+
+/// Splits `polygon` into pieces that each can be painted with per-vertex
+/// colors, invoking `draw` for each piece.
+///
+/// Gradients are decomposed into regions where the gradient is linear. Each
+/// region is convex, which allows clipping `polygon` to it using
+/// [`clip_polygon`].
+pub(crate) fn paint_polygon(
+    polygon: &[Point<f32>],
+    paint: &Paint,
+    mut draw: impl FnMut(&[Point<f32>], &dyn Fn(Point<f32>) -> Color),
+) {
+    let Some((min, max)) = bounds_of(polygon.iter()) else {
+        return;
+    };
+    match paint {
+        Paint::Solid(color) => draw(polygon, &|_| *color),
+        Paint::LinearGradient(gradient) => {
+            let start = to_float(gradient.start);
+            let end = to_float(gradient.end);
+            let axis = Point::new(end.x - start.x, end.y - start.y);
+            let axis_length_squared = axis.x * axis.x + axis.y * axis.y;
+            if axis_length_squared <= f32::EPSILON {
+                let color = gradient.stops.color_at(1.);
+                draw(polygon, &|_| color);
+                return;
+            }
+            let offset_of = |point: Point<f32>| {
+                ((point.x - start.x) * axis.x + (point.y - start.y) * axis.y) / axis_length_squared
+            };
+            let (mut min_offset, mut max_offset) = (f32::MAX, f32::MIN);
+            for corner in [min, max, Point::new(min.x, max.y), Point::new(max.x, min.y)] {
+                let offset = offset_of(corner);
+                min_offset = min_offset.min(offset);
+                max_offset = max_offset.max(offset);
+            }
+
+            let mut boundaries = vec![min_offset];
+            boundaries.extend(
+                gradient
+                    .stops
+                    .boundaries()
+                    .filter(|offset| *offset > min_offset && *offset < max_offset),
+            );
+            boundaries.push(max_offset);
+
+            let color_at = |point: Point<f32>| gradient.stops.color_at(offset_of(point));
+            for band in boundaries.windows(2) {
+                let (low, high) = (band[0], band[1]);
+                if high - low <= f32::EPSILON {
+                    continue;
+                }
+                // Each band is bounded by two lines perpendicular to the
+                // gradient's axis.
+                let low_point = Point::new(start.x + axis.x * low, start.y + axis.y * low);
+                let high_point = Point::new(start.x + axis.x * high, start.y + axis.y * high);
+                let perpendicular = Point::new(-axis.y, axis.x);
+                let clipped = clip_to_half_plane(
+                    &clip_to_half_plane(
+                        polygon,
+                        Point::new(low_point.x + perpendicular.x, low_point.y + perpendicular.y),
+                        low_point,
+                    ),
+                    Point::new(
+                        high_point.x - perpendicular.x,
+                        high_point.y - perpendicular.y,
+                    ),
+                    high_point,
+                );
+                if clipped.len() > 2 {
+                    draw(&clipped, &color_at);
+                }
+            }
+        }
+        Paint::RadialGradient(gradient) => {
+            let center = to_float(gradient.center);
+            let radius = gradient.radius.into_float().max(f32::EPSILON);
+            let color_at =
+                |point: Point<f32>| gradient.stops.color_at(distance(center, point) / radius);
+
+            let far = [min, max, Point::new(min.x, max.y), Point::new(max.x, min.y)]
+                .into_iter()
+                .map(|corner| distance(center, corner))
+                .fold(0., f32::max)
+                / radius;
+            let mut rings = vec![0.];
+            rings.extend(
+                gradient
+                    .stops
+                    .boundaries()
+                    .filter(|offset| *offset > 0. && *offset < far),
+            );
+            rings.push(far);
+            // Subdivide rings so that the straight edges of each wedge stay
+            // close to the circle they approximate.
+            let ring_step = (DEFAULT_TOLERANCE * 16. / radius).max(0.05);
+            let mut subdivided = Vec::with_capacity(rings.len());
+            for pair in rings.windows(2) {
+                let steps = segment_count((pair[1] - pair[0]) / ring_step);
+                for step in 0..steps {
+                    subdivided.push(
+                        pair[0] + (pair[1] - pair[0]) * step.cast::<f32>() / steps.cast::<f32>(),
+                    );
+                }
+            }
+            subdivided.push(far);
+            subdivided.push(far + 1.);
+
+            let far_radius = (far + 1.) * radius;
+            let wedges =
+                segment_count(TAU / (2. * (1. - DEFAULT_TOLERANCE / far_radius.max(1.)).acos()))
+                    .clamp(16, 256);
+            let wedge_angle = TAU / wedges.cast::<f32>();
+            for wedge in 0..wedges {
+                let start_angle = wedge.cast::<f32>() * wedge_angle;
+                let end_angle = start_angle + wedge_angle;
+                let (start_sin, start_cos) = start_angle.sin_cos();
+                let (end_sin, end_cos) = end_angle.sin_cos();
+                for ring in subdivided.windows(2) {
+                    let inner = ring[0] * radius;
+                    let outer = ring[1] * radius;
+                    let piece = if inner <= f32::EPSILON {
+                        vec![
+                            center,
+                            Point::new(center.x + outer * start_cos, center.y + outer * start_sin),
+                            Point::new(center.x + outer * end_cos, center.y + outer * end_sin),
+                        ]
+                    } else {
+                        vec![
+                            Point::new(center.x + inner * start_cos, center.y + inner * start_sin),
+                            Point::new(center.x + outer * start_cos, center.y + outer * start_sin),
+                            Point::new(center.x + outer * end_cos, center.y + outer * end_sin),
+                            Point::new(center.x + inner * end_cos, center.y + inner * end_sin),
+                        ]
+                    };
+                    let Some((piece_min, piece_max)) = bounds_of(piece.iter()) else {
+                        continue;
+                    };
+                    if piece_max.x < min.x
+                        || piece_max.y < min.y
+                        || piece_min.x > max.x
+                        || piece_min.y > max.y
+                    {
+                        continue;
+                    }
+                    let clipped = clip_polygon(polygon, &piece);
+                    if clipped.len() > 2 {
+                        draw(&clipped, &color_at);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Clips `subject` to the inside of the convex polygon `clip` using the
+/// Sutherland-Hodgman algorithm.
+///
+/// `subject` may be concave. The winding of `clip` may be either direction.
+pub(crate) fn clip_polygon(subject: &[Point<f32>], clip: &[Point<f32>]) -> Vec<Point<f32>> {
+    if clip.len() < 3 {
+        return Vec::new();
+    }
+    let clockwise = signed_area(clip) > 0.;
+    let mut output = subject.to_vec();
+    for index in 0..clip.len() {
+        if output.is_empty() {
+            break;
+        }
+        let a = clip[index];
+        let b = clip[(index + 1) % clip.len()];
+        output = if clockwise {
+            clip_to_half_plane(&output, a, b)
+        } else {
+            clip_to_half_plane(&output, b, a)
+        };
+    }
+    output
+}
+
+/// Returns the portion of `subject` that lies on the right side of the
+/// directed line from `a` to `b`, in a coordinate system where y points down.
+fn clip_to_half_plane(subject: &[Point<f32>], a: Point<f32>, b: Point<f32>) -> Vec<Point<f32>> {
+    let mut output = Vec::with_capacity(subject.len() + 2);
+    let Some(mut previous) = subject.last().copied() else {
+        return output;
+    };
+    let mut previous_side = cross(a, b, previous);
+    for &point in subject {
+        let side = cross(a, b, point);
+        if side >= 0. {
+            if previous_side < 0. {
+                output.push(intersect(previous, point, previous_side, side));
+            }
+            output.push(point);
+        } else if previous_side >= 0. {
+            output.push(intersect(previous, point, previous_side, side));
+        }
+        previous = point;
+        previous_side = side;
+    }
+    output
+}
+
+fn intersect(from: Point<f32>, to: Point<f32>, from_side: f32, to_side: f32) -> Point<f32> {
+    let t = from_side / (from_side - to_side);
+    Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t)
+}
+
+fn cross(a: Point<f32>, b: Point<f32>, point: Point<f32>) -> f32 {
+    (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x)
+}
+
+fn signed_area(polygon: &[Point<f32>]) -> f32 {
+    let mut area = 0.;
+    for (index, a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+        area += a.x * b.y - b.x * a.y;
+    }
+    area / 2.
+}
+
+fn bounds_of<'a>(
+    mut points: impl Iterator<Item = &'a Point<f32>>,
+) -> Option<(Point<f32>, Point<f32>)> {
+    let first = *points.next()?;
+    Some(points.fold((first, first), |(min, max), point| {
+        (
+            Point::new(min.x.min(point.x), min.y.min(point.y)),
+            Point::new(max.x.max(point.x), max.y.max(point.y)),
+        )
+    }))
+}
+
+fn length(vector: Point<f32>) -> f32 {
+    vector.x.hypot(vector.y)
+}
+
+fn distance(a: Point<f32>, b: Point<f32>) -> f32 {
+    length(Point::new(b.x - a.x, b.y - a.y))
+}
+
+fn segment_count(estimate: f32) -> usize {
+    if estimate.is_finite() {
+        estimate.ceil().cast::<usize>().clamp(1, MAX_CURVE_SEGMENTS)
+    } else {
+        1
+    }
+}
+
+pub(crate) fn to_float(point: Point<Px>) -> Point<f32> {
+    Point::new(point.x.into_float(), point.y.into_float())
+}
+
+pub(crate) fn to_px(point: Point<f32>) -> Point<Px> {
+    Point::new(Px::from(point.x), Px::from(point.y))
+}
+
+/// Returns a Kludgine path builder containing `polygon`.
+pub(crate) fn shape_builder(
+    polygon: &[Point<f32>],
+    color_at: Option<&dyn Fn(Point<f32>) -> Color>,
+) -> ShapeBuilder<Px, false> {
+    let mut points = polygon.iter().map(|point| match color_at {
+        Some(color_at) => (to_px(*point), color_at(*point)),
+        None => (to_px(*point), Color::WHITE),
+    });
+    let mut builder = ShapeBuilder::new(points.next().expect("polygon is not empty"));
+    for point in points {
+        builder = builder.line_to(point);
+    }
+    builder
+}
+
+#[test]
+fn dashes() {
+    let line = [Point::new(0., 0.), Point::new(10., 0.)];
+    let dashes = DashPattern::new([Px::new(2), Px::new(3)]).apply(&line, false);
+    assert_eq!(
+        dashes,
+        vec![
+            vec![Point::new(0., 0.), Point::new(2., 0.)],
+            vec![Point::new(5., 0.), Point::new(7., 0.)],
+        ]
+    );
+
+    let dashes = DashPattern::new([Px::new(2), Px::new(3)])
+        .offset(Px::new(1))
+        .apply(&line, false);
+    assert_eq!(
+        dashes,
+        vec![
+            vec![Point::new(0., 0.), Point::new(1., 0.)],
+            vec![Point::new(4., 0.), Point::new(6., 0.)],
+            vec![Point::new(9., 0.), Point::new(10., 0.)],
+        ]
+    );
+}
+
+#[test]
+fn clipping() {
+    let square = [
+        Point::new(0., 0.),
+        Point::new(10., 0.),
+        Point::new(10., 10.),
+        Point::new(0., 10.),
+    ];
+    let offset = square.map(|point| Point::new(point.x + 5., point.y + 5.));
+    let clipped = clip_polygon(&square, &offset);
+    assert!((signed_area(&clipped).abs() - 25.).abs() < 0.001);
+
+    // The clip winding should not matter.
+    let mut reversed = offset;
+    reversed.reverse();
+    let clipped = clip_polygon(&square, &reversed);
+    assert!((signed_area(&clipped).abs() - 25.).abs() < 0.001);
+}
+
+#[test]
+fn fill_rules() {
+    fn square(origin: i32, size: i32) -> PathBuilder {
+        let (origin, size) = (Px::new(origin), Px::new(size));
+        PathBuilder::new(Point::new(origin, origin))
+            .line_to(Point::new(origin + size, origin))
+            .line_to(Point::new(origin + size, origin + size))
+            .line_to(Point::new(origin, origin + size))
+            .close()
+    }
+    fn filled_area(path: &Path) -> f32 {
+        path.trapezoids()
+            .iter()
+            .map(|trapezoid| signed_area(trapezoid))
+            .sum()
+    }
+
+    // An inner square wound in the same direction as the outer square.
+    let inner = square(3, 4).build();
+    let mut nested = square(0, 10).build();
+    nested.subpaths.extend(inner.subpaths.iter().cloned());
+    assert!((filled_area(&nested) - 100.).abs() < 0.001);
+    let nested = nested.with_fill_rule(FillRule::EvenOdd);
+    assert!((filled_area(&nested) - 84.).abs() < 0.001);
+
+    // Reversing the inner square's winding creates a hole for both rules.
+    let mut reversed = inner;
+    reversed.subpaths[0].points.reverse();
+    let mut holed = square(0, 10).build();
+    holed.subpaths.extend(reversed.subpaths);
+    assert!((filled_area(&holed) - 84.).abs() < 0.001);
+
+    // A self-intersecting bowtie.
+    let bowtie = PathBuilder::new(Point::new(Px::ZERO, Px::ZERO))
+        .line_to(Point::new(Px::new(10), Px::new(10)))
+        .line_to(Point::new(Px::new(10), Px::ZERO))
+        .line_to(Point::new(Px::ZERO, Px::new(10)))
+        .close()
+        .build();
+    assert!((filled_area(&bowtie) - 50.).abs() < 0.001);
+}
+
+#[test]
+fn flattening() {
+    let path = PathBuilder::new(Point::new(Px::ZERO, Px::ZERO))
+        .quadratic_curve_to(
+            Point::new(Px::new(50), Px::new(100)),
+            Point::new(Px::new(100), Px::ZERO),
+        )
+        .build();
+    let (points, closed) = path.polylines().next().expect("one subpath");
+    assert!(!closed);
+    assert!(points.len() > 2);
+    assert_eq!(points.first(), Some(&Point::new(0., 0.)));
+    assert_eq!(points.last(), Some(&Point::new(100., 0.)));
+
+    let rect = Rect::new(
+        Point::new(Px::new(5), Px::new(5)),
+        Size::new(Px::new(10), Px::new(10)),
+    );
+    assert_eq!(Path::rect(rect).bounds(), Some(rect));
+}
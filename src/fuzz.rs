@@ -0,0 +1,677 @@
+//! Randomized input testing for widgets.
+//!
+//! A [`Fuzzer`] drives a [`VirtualWindow`] with randomized, but seeded,
+//! sequences of mouse movements, clicks, drags, mouse wheel scrolls, and key
+//! presses. After each input event, the window is prepared and checked for:
+//!
+//! - Panics, including those caused by a
+//!   [`DeadlockError`](crate::value::DeadlockError).
+//! - Widgets whose layouts have negative sizes. Layouts are measured in
+//!   integer pixels, which prevents NaN sizes.
+//! - Visible widgets whose layouts extend beyond their visible parent's
+//!   layout. Widgets such as [`Scroll`] intentionally position their children
+//!   outside of their bounds, and can be excluded using
+//!   [`Fuzzer::allow_overflow`].
+//!
+//! When a failure is found, the sequence of events is minimized by repeatedly
+//! removing events that are not needed to reproduce the failure.
+//!
+//! This module is only available when the `fuzz` feature is enabled.
+//!
+//! ```rust,no_run
+//! use cushy::fuzz::Fuzzer;
+//! use cushy::value::Dynamic;
+//! use cushy::widgets::input::InputValue;
+//!
+//! Fuzzer::new(|| Dynamic::<String>::default().into_input())
+//!     .runs(10)
+//!     .run()
+//!     .unwrap();
+//! ```
+//!
+//! Because the window is rendered using wgpu, a compatible adapter must be
+//! available.
+//!
+//! Deadlocks are only detected when Cushy is able to detect them. A fuzzed
+//! widget that deadlocks in a way Cushy cannot detect will cause
+//! [`Fuzzer::run`] to never return.
+
+use std::any::type_name;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+use figures::units::{Px, UPx};
+use figures::{IntoSigned, Point, Rect, Size, Zero};
+use kludgine::app::winit::event::{
+    ElementState, Modifiers, MouseButton, MouseScrollDelta, TouchPhase,
+};
+use kludgine::app::winit::keyboard::{
+    Key, KeyLocation, ModifiersState, NamedKey, NativeKeyCode, PhysicalKey, SmolStr,
+};
+
+use crate::debug::WidgetDump;
+use crate::widget::{MakeWidget, Widget, WidgetId};
+use crate::widgets::Scroll;
+use crate::window::{request_device, DeviceId, KeyEvent, VirtualRecorderError, VirtualWindow};
+
+/// A randomized input tester for widgets.
+pub struct Fuzzer<MakeContents> {
+    make_contents: MakeContents,
+    seed: u64,
+    runs: u64,
+    steps: usize,
+    size: Size<UPx>,
+    minimize: bool,
+    allow_overflow: Vec<&'static str>,
+}
+
+impl<MakeContents, Contents> Fuzzer<MakeContents>
+where
+    MakeContents: Fn() -> Contents,
+    Contents: MakeWidget,
+{
+    /// Returns a new fuzzer that tests the widgets returned from
+    /// `make_contents`.
+    ///
+    /// `make_contents` is invoked once for each run and each attempt to
+    /// minimize a failure, and should return a widget in its initial state
+    /// each time it is invoked.
+    #[must_use]
+    pub fn new(make_contents: MakeContents) -> Self {
+        Self {
+            make_contents,
+            seed: 0,
+            runs: 1,
+            steps: 100,
+            size: Size::upx(800, 600),
+            minimize: true,
+            allow_overflow: vec![type_name::<Scroll>()],
+        }
+    }
+
+    /// Sets the seed of the first run. Each additional run uses the next seed.
+    ///
+    /// The default seed is 0.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of runs to perform. Each run uses a different seed.
+    ///
+    /// One run is performed by default.
+    #[must_use]
+    pub const fn runs(mut self, runs: u64) -> Self {
+        self.runs = runs;
+        self
+    }
+
+    /// Sets the number of input actions to perform in each run.
+    ///
+    /// A single action may produce multiple [`FuzzStep`]s. For example, a drag
+    /// is made up of a button press, several cursor movements, and a button
+    /// release. 100 actions are performed by default.
+    #[must_use]
+    pub const fn steps(mut self, steps: usize) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Sets the size of the virtual window.
+    ///
+    /// The default size is 800x600.
+    #[must_use]
+    pub fn size<Unit>(mut self, size: Size<Unit>) -> Self
+    where
+        Unit: Into<UPx>,
+    {
+        self.size = size.map(Into::into);
+        self
+    }
+
+    /// Sets whether failing sequences are minimized before being returned.
+    ///
+    /// Failures are minimized by default.
+    #[must_use]
+    pub const fn minimize(mut self, minimize: bool) -> Self {
+        self.minimize = minimize;
+        self
+    }
+
+    /// Allows the children of widgets of type `W` to be laid out outside of
+    /// the widget's bounds.
+    ///
+    /// Overflow is allowed for [`Scroll`] by default.
+    #[must_use]
+    pub fn allow_overflow<W>(mut self) -> Self
+    where
+        W: Widget,
+    {
+        self.allow_overflow.push(type_name::<W>());
+        self
+    }
+
+    /// Performs the configured runs, returning the first failure found.
+    pub fn run(self) -> Result<(), FuzzError> {
+        let (device, queue) = request_device()?;
+
+        for seed in self.seed..self.seed.saturating_add(self.runs) {
+            let steps = generate(seed, self.steps, self.size);
+            if let Err((failed_at, error)) = self.execute(&steps, &device, &queue) {
+                let mut steps = steps;
+                steps.truncate(failed_at + 1);
+                let (steps, error) = if self.minimize {
+                    self.minimized(steps, error, &device, &queue)
+                } else {
+                    (steps, error)
+                };
+                return Err(FuzzError::Failed(Box::new(FuzzFailure {
+                    seed,
+                    steps,
+                    error,
+                })));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replays `steps`, returning the first failure found.
+    ///
+    /// This can be used to reproduce a [`FuzzFailure`].
+    pub fn replay(&self, steps: &[FuzzStep]) -> Result<(), FuzzError> {
+        let (device, queue) = request_device()?;
+        self.execute(steps, &device, &queue)
+            .map_err(|(failed_at, error)| {
+                FuzzError::Failed(Box::new(FuzzFailure {
+                    seed: self.seed,
+                    steps: steps[..=failed_at].to_vec(),
+                    error,
+                }))
+            })
+    }
+
+    /// Executes `steps` in a new window, returning the index of the step that
+    /// failed along with the problem found.
+    fn execute(
+        &self,
+        steps: &[FuzzStep],
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<(), (usize, Problem)> {
+        let mut window = catch_panic(|| {
+            let mut window = (self.make_contents)()
+                .build_standalone_window()
+                .size(self.size)
+                .finish_virtual(device, queue);
+            window.prepare(device, queue);
+            window
+        })
+        .map_err(|problem| (0, problem))?;
+        self.check(&window).map_err(|problem| (0, problem))?;
+
+        for (index, step) in steps.iter().enumerate() {
+            catch_panic(|| {
+                step.apply(&mut window);
+                window.prepare(device, queue);
+            })
+            .map_err(|problem| (index, problem))?;
+            self.check(&window).map_err(|problem| (index, problem))?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, window: &VirtualWindow) -> Result<(), Problem> {
+        for root in &window.dump_tree().roots {
+            self.check_widget(root, None)?;
+        }
+        Ok(())
+    }
+
+    fn check_widget(
+        &self,
+        widget: &WidgetDump,
+        parent: Option<&WidgetDump>,
+    ) -> Result<(), Problem> {
+        if let Some(layout) = widget.layout {
+            if layout.size.width < 0 || layout.size.height < 0 {
+                return Err(Problem::NegativeSize {
+                    widget: widget.id,
+                    type_name: widget.type_name,
+                    layout,
+                });
+            }
+
+            if let Some((parent, parent_layout)) = parent
+                .filter(|parent| {
+                    parent.visible
+                        && widget.visible
+                        && !self.allow_overflow.contains(&parent.type_name)
+                })
+                .and_then(|parent| Some((parent, parent.layout?)))
+            {
+                if !contains(parent_layout, layout) {
+                    return Err(Problem::Overflow {
+                        widget: widget.id,
+                        type_name: widget.type_name,
+                        layout,
+                        parent: parent.id,
+                        parent_layout,
+                    });
+                }
+            }
+        }
+
+        for child in &widget.children {
+            self.check_widget(child, Some(widget))?;
+        }
+        Ok(())
+    }
+
+    /// Removes steps from `steps` while the failure continues to reproduce.
+    fn minimized(
+        &self,
+        mut steps: Vec<FuzzStep>,
+        mut error: Problem,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> (Vec<FuzzStep>, Problem) {
+        let mut chunk = (steps.len() / 2).max(1);
+        loop {
+            let mut start = 0;
+            let mut removed_any = false;
+            while start < steps.len() {
+                let end = (start + chunk).min(steps.len());
+                let candidate = steps[..start]
+                    .iter()
+                    .chain(&steps[end..])
+                    .cloned()
+                    .collect::<Vec<_>>();
+                match self.execute(&candidate, device, queue) {
+                    Err((failed_at, candidate_error)) if candidate_error.same_kind(&error) => {
+                        steps = candidate;
+                        steps.truncate(failed_at + 1);
+                        error = candidate_error;
+                        removed_any = true;
+                    }
+                    _ => start = end,
+                }
+            }
+
+            if !removed_any {
+                if chunk == 1 {
+                    return (steps, error);
+                }
+                chunk /= 2;
+            }
+        }
+    }
+}
+
+fn contains(outer: Rect<Px>, inner: Rect<Px>) -> bool {
+    let (outer_start, outer_end) = outer.extents();
+    let (inner_start, inner_end) = inner.extents();
+    inner_start.x >= outer_start.x
+        && inner_start.y >= outer_start.y
+        && inner_end.x <= outer_end.x
+        && inner_end.y <= outer_end.y
+}
+
+fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, Problem> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&'static str>()
+            .map(|message| String::from(*message))
+            .or_else(|| payload.downcast::<String>().ok().map(|message| *message))
+            .unwrap_or_else(|| String::from("<unknown panic payload>"));
+        if message.contains("deadlock") || message.contains("DeadlockError") {
+            Problem::Deadlock(message)
+        } else {
+            Problem::Panic(message)
+        }
+    })
+}
+
+/// A single input event performed by a [`Fuzzer`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuzzStep {
+    /// The cursor moved to a location.
+    CursorMoved(Point<Px>),
+    /// The cursor left the window.
+    CursorLeft,
+    /// A mouse button was pressed or released.
+    MouseInput {
+        /// Whether the button was pressed or released.
+        state: ElementState,
+        /// The mouse button.
+        button: MouseButton,
+    },
+    /// The mouse wheel was scrolled.
+    MouseWheel(MouseScrollDelta),
+    /// A key was pressed or released.
+    Key {
+        /// The key.
+        key: Key,
+        /// Whether the key was pressed or released.
+        state: ElementState,
+        /// The modifiers held while the key was pressed or released.
+        modifiers: ModifiersState,
+    },
+}
+
+impl FuzzStep {
+    fn apply(&self, window: &mut VirtualWindow) {
+        let device = DeviceId::Virtual(0);
+        match self {
+            FuzzStep::CursorMoved(location) => window.cursor_moved(device, *location),
+            FuzzStep::CursorLeft => window.cursor_left(),
+            FuzzStep::MouseInput { state, button } => {
+                let _handled = window.mouse_input(device, *state, *button);
+            }
+            FuzzStep::MouseWheel(delta) => {
+                let _handled = window.mouse_wheel(device, *delta, TouchPhase::Moved);
+            }
+            FuzzStep::Key {
+                key,
+                state,
+                modifiers,
+            } => {
+                let text = match (state, key) {
+                    (ElementState::Pressed, Key::Character(text)) => Some(text.clone()),
+                    (ElementState::Pressed, Key::Named(NamedKey::Space)) => {
+                        Some(SmolStr::new_inline(" "))
+                    }
+                    (ElementState::Pressed, Key::Named(NamedKey::Enter)) => {
+                        Some(SmolStr::new_inline("\r"))
+                    }
+                    _ => None,
+                };
+                let _handled = window.keyboard_input(
+                    device,
+                    KeyEvent {
+                        physical_key: PhysicalKey::Unidentified(NativeKeyCode::Xkb(0)),
+                        logical_key: key.clone(),
+                        text,
+                        location: KeyLocation::Standard,
+                        state: *state,
+                        repeat: false,
+                        modifiers: Modifiers::from(*modifiers),
+                    },
+                    false,
+                );
+            }
+        }
+    }
+}
+
+/// The keys randomly pressed by a [`Fuzzer`].
+const KEYS: [NamedKey; 15] = [
+    NamedKey::Tab,
+    NamedKey::Enter,
+    NamedKey::Space,
+    NamedKey::Escape,
+    NamedKey::Backspace,
+    NamedKey::Delete,
+    NamedKey::ArrowLeft,
+    NamedKey::ArrowRight,
+    NamedKey::ArrowUp,
+    NamedKey::ArrowDown,
+    NamedKey::Home,
+    NamedKey::End,
+    NamedKey::PageUp,
+    NamedKey::PageDown,
+    NamedKey::Insert,
+];
+
+/// The characters randomly typed by a [`Fuzzer`].
+const CHARACTERS: [&str; 6] = ["a", "Z", "1", "-", "é", "👍"];
+
+fn generate(seed: u64, actions: usize, size: Size<UPx>) -> Vec<FuzzStep> {
+    let mut rng = SplitMix64(seed);
+    let mut steps = Vec::with_capacity(actions * 2);
+    for _ in 0..actions {
+        match rng.below(12) {
+            0..=3 => steps.push(FuzzStep::CursorMoved(rng.location(size))),
+            4..=5 => {
+                let button = rng.button();
+                steps.push(FuzzStep::CursorMoved(rng.location(size)));
+                steps.push(FuzzStep::MouseInput {
+                    state: ElementState::Pressed,
+                    button,
+                });
+                steps.push(FuzzStep::MouseInput {
+                    state: ElementState::Released,
+                    button,
+                });
+            }
+            6 => {
+                let button = rng.button();
+                steps.push(FuzzStep::CursorMoved(rng.location(size)));
+                steps.push(FuzzStep::MouseInput {
+                    state: ElementState::Pressed,
+                    button,
+                });
+                for _ in 0..=rng.below(4) {
+                    steps.push(FuzzStep::CursorMoved(rng.location(size)));
+                }
+                steps.push(FuzzStep::MouseInput {
+                    state: ElementState::Released,
+                    button,
+                });
+            }
+            7 => {
+                let delta = if rng.below(2) == 0 {
+                    MouseScrollDelta::LineDelta(rng.delta(3), rng.delta(3))
+                } else {
+                    MouseScrollDelta::PixelDelta(
+                        (f64::from(rng.delta(100)), f64::from(rng.delta(100))).into(),
+                    )
+                };
+                steps.push(FuzzStep::MouseWheel(delta));
+            }
+            8..=10 => {
+                let key = if rng.below(3) == 0 {
+                    Key::Character(SmolStr::new(*rng.choose(&CHARACTERS)))
+                } else {
+                    Key::Named(*rng.choose(&KEYS))
+                };
+                let modifiers = match rng.below(8) {
+                    0 => ModifiersState::SHIFT,
+                    1 => ModifiersState::CONTROL,
+                    _ => ModifiersState::empty(),
+                };
+                for state in [ElementState::Pressed, ElementState::Released] {
+                    steps.push(FuzzStep::Key {
+                        key: key.clone(),
+                        state,
+                        modifiers,
+                    });
+                }
+            }
+            _ => steps.push(FuzzStep::CursorLeft),
+        }
+    }
+    steps
+}
+
+/// A small, seedable pseudorandom number generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, max: u32) -> u32 {
+        u32::try_from(self.next() % u64::from(max)).expect("less than max")
+    }
+
+    fn delta(&mut self, max: u16) -> f32 {
+        let offset = u16::try_from(self.below(u32::from(max) * 2 + 1)).expect("less than max");
+        f32::from(offset) - f32::from(max)
+    }
+
+    fn choose<'a, T>(&mut self, options: &'a [T]) -> &'a T {
+        let index = self.next() % u64::try_from(options.len()).expect("small slice");
+        &options[usize::try_from(index).expect("small slice")]
+    }
+
+    fn button(&mut self) -> MouseButton {
+        match self.below(6) {
+            0 => MouseButton::Right,
+            1 => MouseButton::Middle,
+            _ => MouseButton::Left,
+        }
+    }
+
+    /// Returns a location within `size`, occasionally slightly outside of it.
+    fn location(&mut self, size: Size<UPx>) -> Point<Px> {
+        let size = size.into_signed();
+        Point::new(self.coordinate(size.width), self.coordinate(size.height))
+    }
+
+    fn coordinate(&mut self, max: Px) -> Px {
+        let max = max.max(Px::ZERO).get();
+        let range = u32::try_from(max).unwrap_or_default() + 21;
+        Px::new(i32::try_from(self.below(range)).unwrap_or_default() - 10)
+    }
+}
+
+/// An error returned from [`Fuzzer::run`] or [`Fuzzer::replay`].
+#[derive(Debug)]
+pub enum FuzzError {
+    /// The virtual window could not be created.
+    Window(VirtualRecorderError),
+    /// A failure was found.
+    Failed(Box<FuzzFailure>),
+}
+
+impl From<VirtualRecorderError> for FuzzError {
+    fn from(value: VirtualRecorderError) -> Self {
+        Self::Window(value)
+    }
+}
+
+impl std::error::Error for FuzzError {}
+
+impl Display for FuzzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzError::Window(err) => Display::fmt(err, f),
+            FuzzError::Failed(failure) => Display::fmt(failure, f),
+        }
+    }
+}
+
+/// A sequence of input that caused a [`Fuzzer`] to find a problem.
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    /// The seed of the run that failed.
+    pub seed: u64,
+    /// The steps that reproduce the problem. The problem was found after the
+    /// final step was performed.
+    pub steps: Vec<FuzzStep>,
+    /// The problem that was found.
+    pub error: Problem,
+}
+
+impl Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} after {} steps with seed {}:",
+            self.error,
+            self.steps.len(),
+            self.seed
+        )?;
+        for step in &self.steps {
+            writeln!(f, "  {step:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem found by a [`Fuzzer`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Problem {
+    /// A panic occurred.
+    Panic(String),
+    /// A panic occurred due to a detected deadlock.
+    Deadlock(String),
+    /// A widget's layout has a negative size.
+    NegativeSize {
+        /// The widget's id.
+        widget: WidgetId,
+        /// The widget's type name.
+        type_name: &'static str,
+        /// The widget's layout.
+        layout: Rect<Px>,
+    },
+    /// A widget's layout extends beyond its parent's layout.
+    Overflow {
+        /// The widget's id.
+        widget: WidgetId,
+        /// The widget's type name.
+        type_name: &'static str,
+        /// The widget's layout.
+        layout: Rect<Px>,
+        /// The parent widget's id.
+        parent: WidgetId,
+        /// The parent widget's layout.
+        parent_layout: Rect<Px>,
+    },
+}
+
+impl Problem {
+    fn same_kind(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Panic(a), Self::Panic(b)) | (Self::Deadlock(a), Self::Deadlock(b)) => a == b,
+            (Self::NegativeSize { type_name: a, .. }, Self::NegativeSize { type_name: b, .. })
+            | (Self::Overflow { type_name: a, .. }, Self::Overflow { type_name: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Panic(message) => write!(f, "panic: {message}"),
+            Problem::Deadlock(message) => write!(f, "deadlock: {message}"),
+            Problem::NegativeSize {
+                widget,
+                type_name,
+                layout,
+            } => write!(f, "{type_name} ({widget:?}) has a negative size: {layout:?}"),
+            Problem::Overflow {
+                widget,
+                type_name,
+                layout,
+                parent,
+                parent_layout,
+            } => write!(
+                f,
+                "{type_name} ({widget:?}) at {layout:?} is outside of its parent ({parent:?}) at {parent_layout:?}"
+            ),
+        }
+    }
+}
+
+#[test]
+fn generation_is_deterministic() {
+    let size = Size::upx(100, 100);
+    let first = generate(42, 50, size);
+    assert_eq!(first, generate(42, 50, size));
+    assert_ne!(first, generate(43, 50, size));
+    assert!(first.iter().all(|step| match step {
+        FuzzStep::CursorMoved(location) => {
+            location.x >= -10 && location.x <= 110 && location.y >= -10 && location.y <= 110
+        }
+        _ => true,
+    }));
+}
@@ -1,28 +1,47 @@
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::task::Poll;
 
 use figures::units::{Px, UPx};
 use figures::{
-    self, Fraction, IntoSigned, IntoUnsigned, Point, Rect, ScreenScale, ScreenUnit, Size, Zero,
+    self, FloatConversion, Fraction, IntoSigned, IntoUnsigned, Point, Rect, ScreenScale,
+    ScreenUnit, Size, Zero,
 };
+use image::RgbaImage;
+use intentional::{Assert, Cast};
 use kempt::{map, Map};
 use kludgine::cosmic_text::{fontdb, FamilyOwned, FontSystem};
-use kludgine::drawing::Renderer;
-use kludgine::shapes::Shape;
+use kludgine::drawing::{Drawing, Renderer};
+use kludgine::shapes::{CornerRadii, Shape};
 use kludgine::text::{MeasuredText, Text, TextOrigin};
 use kludgine::{
-    cosmic_text, ClipGuard, Color, Drawable, Kludgine, ShaderScalable, ShapeSource, TextureSource,
+    cosmic_text, wgpu, ClipGuard, Color, Drawable, Kludgine, ShaderScalable, ShapeSource, Texture,
+    TextureSource,
 };
+use parking_lot::Mutex;
 
 use crate::animation::ZeroToOne;
+use crate::drawing::{self, Paint, Path, Stroke};
 use crate::fonts::{FontCollection, LoadedFontFace, LoadedFontId};
 use crate::styles::FontFamilyList;
 use crate::value::{DynamicRead, Generation, Source};
+use crate::widgets::image::NineSlice;
+use crate::window::copy_buffer_aligned_bytes_per_row;
 
 /// A 2d graphics context
 pub struct Graphics<'clip, 'gfx, 'pass> {
     renderer: RenderContext<'clip, 'gfx, 'pass>,
     region: Rect<Px>,
     pub(crate) opacity: ZeroToOne,
+    /// The areas, in window coordinates, that path drawing is clipped to.
+    ///
+    /// Each clip is a list of non-overlapping trapezoids produced by
+    /// [`Path::trapezoids`].
+    path_clips: Vec<Vec<[Point<f32>; 4]>>,
+    /// The location of the renderer's origin in window coordinates.
+    ///
+    /// This is only non-zero when rendering to an [`OffscreenTarget`].
+    offset: Point<Px>,
 }
 
 enum RenderContext<'clip, 'gfx, 'pass> {
@@ -38,6 +57,8 @@ impl<'clip, 'gfx, 'pass> Graphics<'clip, 'gfx, 'pass> {
             region: renderer.clip_rect().into_signed(),
             renderer: RenderContext::Renderer(renderer),
             opacity: ZeroToOne::ONE,
+            path_clips: Vec::new(),
+            offset: Point::ZERO,
         }
     }
 
@@ -51,7 +72,7 @@ impl<'clip, 'gfx, 'pass> Graphics<'clip, 'gfx, 'pass> {
     /// needed when using [`inner_graphics()`](Self::inner_graphics).
     #[must_use]
     pub fn translation(&self) -> Point<Px> {
-        let clip_origin = self.renderer.clip_rect().origin.into_signed();
+        let clip_origin = self.renderer.clip_rect().origin.into_signed() + self.offset;
         -Point::new(
             if clip_origin.x <= self.region.origin.x {
                 Px::ZERO
@@ -91,13 +112,13 @@ impl<'clip, 'gfx, 'pass> Graphics<'clip, 'gfx, 'pass> {
 
         // If the current region has a negative component, we need to adjust the
         // clipped rect before we perform an intersection in unsigned space.
-        let mut effective_region = region;
-        if region.origin.x < 0 {
-            effective_region.size.width += region.origin.x;
+        let mut effective_region = region - self.offset;
+        if effective_region.origin.x < 0 {
+            effective_region.size.width += effective_region.origin.x;
             effective_region.origin.x = Px::ZERO;
         }
-        if region.origin.y < 0 {
-            effective_region.size.height += region.origin.y;
+        if effective_region.origin.y < 0 {
+            effective_region.size.height += effective_region.origin.y;
             effective_region.origin.y = Px::ZERO;
         }
         let new_clip = self
@@ -111,16 +132,54 @@ impl<'clip, 'gfx, 'pass> Graphics<'clip, 'gfx, 'pass> {
             renderer: RenderContext::Clipped(self.renderer.clipped_to(new_clip)),
             region,
             opacity: self.opacity,
+            path_clips: self.path_clips.clone(),
+            offset: self.offset,
         }
     }
 
+    /// Returns a context whose path drawing is clipped to the inside of
+    /// `path`.
+    ///
+    /// Unlike [`clipped_to()`](Self::clipped_to), the returned context uses the
+    /// same coordinate system as `self`.
+    ///
+    /// Clipping to a path is performed geometrically rather than by the GPU:
+    ///
+    /// - Only [`fill_path()`](Self::fill_path) and
+    ///   [`stroke_path()`](Self::stroke_path) are clipped to the path. All
+    ///   other drawing operations are clipped to the path's bounding box.
+    /// - The inside of `path` is determined using its
+    ///   [`FillRule`](crate::drawing::FillRule), which allows clipping to
+    ///   concave paths and paths with holes.
+    /// - Clipped geometry is converted to whole pixels when drawn.
+    pub fn clipped_to_path(&mut self, path: &Path) -> Graphics<'_, 'gfx, 'pass> {
+        let region = self.region;
+        let origin = Point::new(region.origin.x.into_float(), region.origin.y.into_float());
+        let bounds = path.bounds().unwrap_or_default();
+        let mut clipped = self.clipped_to(bounds);
+        clipped.region = region;
+        clipped.path_clips.push(
+            path.trapezoids()
+                .into_iter()
+                .map(|trapezoid| {
+                    trapezoid.map(|point| Point::new(point.x + origin.x, point.y + origin.y))
+                })
+                .collect(),
+        );
+        clipped
+    }
+
     /// Returns the current clipping rectangle.
     ///
     /// The clipping rectangle is represented in unsigned pixels in the window's
     /// coordinate system.
     #[must_use]
     pub fn clip_rect(&self) -> Rect<UPx> {
-        self.renderer.clip_rect()
+        if self.offset == Point::ZERO {
+            self.renderer.clip_rect()
+        } else {
+            (self.renderer.clip_rect().into_signed() + self.offset).into_unsigned()
+        }
     }
 
     /// Returns the current region being rendered to.
@@ -219,6 +278,280 @@ impl<'clip, 'gfx, 'pass> Graphics<'clip, 'gfx, 'pass> {
         self.renderer.draw_textured_shape(shape, texture);
     }
 
+    /// Draws `image` to fill `destination`.
+    ///
+    /// See [`NineSlice`] for more information about how the image is scaled.
+    pub fn draw_nine_slice(
+        &mut self,
+        image: &NineSlice,
+        destination: Rect<Px>,
+        opacity: ZeroToOne,
+    ) {
+        let scale = self.scale().into_f32() * image.scale;
+        for (destination, source) in image.slices(image.texture.size(), destination, scale) {
+            self.draw_textured_shape(
+                &Shape::textured_round_rect(destination, CornerRadii::ZERO, source, Color::WHITE),
+                &*image.texture,
+                opacity,
+            );
+        }
+    }
+
+    /// Fills the inside of `path` using `paint`.
+    ///
+    /// Each subpath of `path` is treated as closed, and the path's
+    /// [`FillRule`](crate::drawing::FillRule) determines which areas are
+    /// filled.
+    pub fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>) {
+        let paint = paint.into();
+        for trapezoid in path.trapezoids() {
+            self.fill_polygon(&trapezoid, &paint);
+        }
+    }
+
+    /// Draws the outline of `path` using `stroke`.
+    ///
+    /// When `stroke` is painted with a solid color and no path clipping is
+    /// active, the outline is tesselated by Kludgine. Otherwise, the outline
+    /// is built from individual line segments joined with round joins. When
+    /// using a partially transparent paint in this mode, areas where segments
+    /// overlap will be blended more than once.
+    pub fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        for (polyline, closed) in stroke.polylines(path) {
+            match &stroke.paint {
+                Paint::Solid(color) if self.path_clips.is_empty() => {
+                    let builder = drawing::shape_builder(&polyline, None);
+                    let shape = if closed {
+                        builder.close().stroke(stroke.options(*color))
+                    } else {
+                        builder.build().stroke(stroke.options(*color))
+                    };
+                    self.draw_shape(&shape);
+                }
+                paint => {
+                    for polygon in stroke.outline_polygons(&polyline, closed) {
+                        self.fill_polygon(&polygon, paint);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fills the convex `polygon` using `paint`, clipped to the active path
+    /// clips.
+    fn fill_polygon(&mut self, polygon: &[Point<f32>], paint: &Paint) {
+        let mut pieces = vec![polygon.to_vec()];
+        if !self.path_clips.is_empty() {
+            let origin = Point::new(
+                self.region.origin.x.into_float(),
+                self.region.origin.y.into_float(),
+            );
+            // Each clip is made of non-overlapping trapezoids, so clipping a
+            // piece to each trapezoid produces non-overlapping pieces.
+            for clip in &self.path_clips {
+                pieces = pieces
+                    .iter()
+                    .flat_map(|piece| {
+                        clip.iter().map(move |trapezoid| {
+                            let local = trapezoid
+                                .map(|point| Point::new(point.x - origin.x, point.y - origin.y));
+                            drawing::clip_polygon(piece, &local)
+                        })
+                    })
+                    .filter(|piece| piece.len() > 2)
+                    .collect();
+            }
+        }
+
+        for piece in pieces.iter().filter(|piece| piece.len() > 2) {
+            if let Paint::Solid(color) = paint {
+                if color.alpha() > 0 {
+                    self.draw_shape(&drawing::shape_builder(piece, None).close().fill(*color));
+                }
+            } else {
+                drawing::paint_polygon(piece, paint, |piece, color_at| {
+                    self.draw_shape(
+                        &drawing::shape_builder(piece, Some(color_at))
+                            .close()
+                            .filled(),
+                    );
+                });
+            }
+        }
+    }
+
+    /// Renders into `target` using a new graphics context covering `region`.
+    ///
+    /// `region` is interpreted relative to this context's region, and the
+    /// context passed to `render` uses the same window coordinate system as
+    /// `self`. The rendered texture can be retrieved using
+    /// [`OffscreenTarget::texture`] and drawn using this context.
+    ///
+    /// Returns false if nothing was rendered, either because `region` is empty
+    /// or because it is too large to fit in a texture.
+    pub(crate) fn render_offscreen(
+        &mut self,
+        target: &mut OffscreenTarget,
+        region: Rect<Px>,
+        render: impl FnOnce(Graphics<'_, '_, '_>),
+    ) -> bool {
+        let region = region + self.region.origin;
+        let size = region.size.into_unsigned();
+        if size.width == 0 || size.height == 0 {
+            return false;
+        }
+
+        // Fonts loaded into the window are copied into the offscreen
+        // renderer's font system whenever the set of faces changes.
+        let face_count = self.font_system().db().len();
+        let fonts = (target.font_faces != face_count).then(|| self.font_system().db().clone());
+        let dpi_scale = self.renderer.dpi_scale();
+        let zoom = self.renderer.zoom();
+
+        let main: &kludgine::Graphics<'gfx> = &self.renderer;
+        let device = main.device();
+        let queue = main.queue();
+        let max_dimension = device.limits().max_texture_dimension_2d;
+        if size.width.get() > max_dimension || size.height.get() > max_dimension {
+            return false;
+        }
+
+        let offscreen_kludgine = target.kludgine.get_or_insert_with(|| {
+            Kludgine::new(
+                device,
+                queue,
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                wgpu::MultisampleState::default(),
+                size,
+                dpi_scale.into_f32(),
+            )
+        });
+        offscreen_kludgine.resize(size, dpi_scale, zoom, queue);
+        if target
+            .texture
+            .as_ref()
+            .map_or(true, |texture| texture.size() != size)
+        {
+            // The texture is drawn by the window, so it must be created using
+            // the window's Kludgine instance.
+            target.texture = Some(Texture::new(
+                main,
+                size,
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
+                wgpu::FilterMode::Linear,
+            ));
+        }
+
+        let mut offscreen = kludgine::Graphics::new(offscreen_kludgine, device, queue);
+        if let Some(fonts) = fonts {
+            *offscreen.font_system().db_mut() = fonts;
+            offscreen.rebuild_font_system();
+            target.font_faces = face_count;
+        }
+        let renderer = target.drawing.new_frame(&mut offscreen);
+        render(Graphics {
+            renderer: RenderContext::Renderer(renderer),
+            region,
+            opacity: ZeroToOne::ONE,
+            path_clips: Vec::new(),
+            offset: region.origin,
+        });
+        drop(offscreen);
+
+        let texture = target.texture.as_ref().assert("always initialized above");
+        let mut frame = offscreen_kludgine.next_frame();
+        let mut gfx = frame.render_into(
+            texture,
+            wgpu::LoadOp::Clear(Color::CLEAR_BLACK),
+            device,
+            queue,
+        );
+        target.drawing.render(1., &mut gfx);
+        drop(gfx);
+        frame.submit(queue);
+        true
+    }
+
+    /// Begins copying the texture last rendered into `target` into an image.
+    ///
+    /// The copy happens asynchronously. Use
+    /// [`finish_offscreen_read`](Self::finish_offscreen_read) on a later frame
+    /// to retrieve the image. Returns `None` if `target` has not been rendered
+    /// into.
+    pub(crate) fn read_offscreen(&self, target: &OffscreenTarget) -> Option<OffscreenRead> {
+        let texture = target.texture.as_ref()?;
+        let size = texture.size();
+        let packed_bytes_per_row = size.width.get() * 4;
+        let bytes_per_row = copy_buffer_aligned_bytes_per_row(packed_bytes_per_row);
+        let bytes = u64::from(bytes_per_row) * u64::from(size.height.get());
+
+        let main: &kludgine::Graphics<'gfx> = &self.renderer;
+        let device = main.device();
+        let queue = main.queue();
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: bytes,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        texture.copy_to_buffer(
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            &mut encoder,
+        );
+        queue.submit([encoder.finish()]);
+
+        let map_result = Arc::new(Mutex::new(None));
+        buffer.slice(..).map_async(wgpu::MapMode::Read, {
+            let map_result = map_result.clone();
+            move |result| {
+                *map_result.lock() = Some(result);
+            }
+        });
+
+        Some(OffscreenRead {
+            buffer,
+            size: Size::new(size.width.get(), size.height.get()),
+            bytes_per_row,
+            map_result,
+        })
+    }
+
+    /// Returns the image copied by `read`, if the GPU has finished copying it.
+    ///
+    /// This does not block. Returns `Poll::Ready(None)` if the copy failed.
+    pub(crate) fn finish_offscreen_read(&self, read: &OffscreenRead) -> Poll<Option<RgbaImage>> {
+        let main: &kludgine::Graphics<'gfx> = &self.renderer;
+        main.device().poll(wgpu::Maintain::Poll);
+        let Some(result) = read.map_result.lock().take() else {
+            return Poll::Pending;
+        };
+        if result.is_err() {
+            return Poll::Ready(None);
+        }
+
+        let packed_bytes_per_row = read.size.width * 4;
+        let mapped = read.buffer.slice(..).get_mapped_range();
+        let mut data = Vec::with_capacity((packed_bytes_per_row * read.size.height).cast());
+        for row in mapped.chunks(read.bytes_per_row.cast()) {
+            data.extend_from_slice(&row[..packed_bytes_per_row.cast()]);
+        }
+        drop(mapped);
+        read.buffer.unmap();
+
+        Poll::Ready(RgbaImage::from_vec(read.size.width, read.size.height, data))
+    }
+
     /// Measures `text` using the current text settings.
     ///
     /// `default_color` does not affect the
@@ -358,6 +691,43 @@ impl<'gfx, 'pass> DerefMut for RenderContext<'_, 'gfx, 'pass> {
     }
 }
 
+/// A texture that widgets can be rendered into before being composited into a
+/// window.
+///
+/// Offscreen targets are tied to the window they were first rendered in, and
+/// are typically stored in a [`WindowLocal`](crate::window::WindowLocal).
+#[derive(Default)]
+pub(crate) struct OffscreenTarget {
+    kludgine: Option<Kludgine>,
+    texture: Option<Texture>,
+    drawing: Drawing,
+    font_faces: usize,
+}
+
+impl OffscreenTarget {
+    /// Returns the texture that was last rendered into.
+    pub fn texture(&self) -> Option<&Texture> {
+        self.texture.as_ref()
+    }
+}
+
+impl std::fmt::Debug for OffscreenTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OffscreenTarget")
+            .field("size", &self.texture.as_ref().map(Texture::size))
+            .finish_non_exhaustive()
+    }
+}
+
+/// An in-progress copy of an [`OffscreenTarget`]'s texture into an image.
+#[derive(Debug)]
+pub(crate) struct OffscreenRead {
+    buffer: wgpu::Buffer,
+    size: Size<u32>,
+    bytes_per_row: u32,
+    map_result: Arc<Mutex<Option<Result<(), wgpu::BufferAsyncError>>>>,
+}
+
 pub(crate) struct LoadedFontIds {
     generation: usize,
     pub(crate) faces: Vec<LoadedFontFace>,
@@ -13,14 +13,18 @@ extern crate self as cushy;
 mod utils;
 
 pub mod animation;
+pub mod clock;
 pub mod context;
+pub mod drawing;
 mod graphics;
 mod names;
 #[macro_use]
 pub mod styles;
 mod app;
+pub mod cursor;
 pub mod debug;
 pub mod fonts;
+pub mod profiler;
 mod tick;
 mod tree;
 pub mod value;
@@ -31,12 +35,19 @@ pub mod window;
 pub mod dialog;
 #[doc(hidden)]
 pub mod example;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "settings")]
+pub mod settings;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 #[cfg(feature = "tokio")]
 pub use app::TokioRuntime;
 pub use app::{
-    App, AppRuntime, Application, Cushy, DefaultRuntime, Open, PendingApp, Run, ShutdownGuard,
+    App, AppRuntime, Application, BoxedFuture, Cushy, DefaultRuntime, Open, PendingApp, Run,
+    ShutdownGuard,
 };
 /// A macro to create a `main()` function with less boilerplate.
 ///
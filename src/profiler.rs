@@ -0,0 +1,503 @@
+//! Measuring how much work windows perform each frame.
+//!
+//! A [`Profiler`] can be attached to a window using
+//! [`Window::profiler`](crate::window::Window::profiler). Once attached, the
+//! profiler records:
+//!
+//! - How long each frame spent performing layout, redrawing, and rendering.
+//! - How many times [`Widget::layout`](crate::widget::Widget::layout) and
+//!   [`Widget::redraw`](crate::widget::Widget::redraw) were invoked, both per
+//!   frame and per widget.
+//! - Which [`Dynamic`] values caused the window to be invalidated by way of
+//!   [`invalidate_when_changed`](crate::context::WidgetContext::invalidate_when_changed)
+//!   or
+//!   [`redraw_when_changed`](crate::context::WidgetContext::redraw_when_changed).
+//!
+//! A single profiler can be attached to multiple windows. Each window's frames
+//! are measured independently, and completed frames from all windows are
+//! recorded in the same history in the order they finish.
+//!
+//! By default, a graph of the most recent frames is drawn over the window's
+//! contents. The overlay can be hidden using [`Profiler::overlay`].
+//!
+//! When each frame finishes, its profile is also emitted as a `DEBUG` level
+//! [`tracing`] event with the target `cushy::profiler`. Each invalidation is
+//! emitted as a `TRACE` level event with the same target, allowing the data to
+//! be exported using any `tracing` subscriber.
+//!
+//! ```rust,no_run
+//! use cushy::profiler::Profiler;
+//! use cushy::widget::MakeWidget;
+//! use cushy::Run;
+//!
+//! let profiler = Profiler::new();
+//! "Hello"
+//!     .into_button()
+//!     .into_window()
+//!     .profiler(&profiler)
+//!     .run()
+//!     .unwrap();
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+use figures::units::{Lp, Px};
+use figures::{FloatConversion, Point, Rect, Round, ScreenScale, Size};
+use kludgine::shapes::Shape;
+use kludgine::text::{Text, TextOrigin};
+use kludgine::DrawableExt;
+use parking_lot::Mutex;
+use tracing::Level;
+
+use crate::context::GraphicsContext;
+use crate::value::{Dynamic, Source};
+use crate::widget::{MountedWidget, WidgetId};
+
+static ATTACHED: AtomicBool = AtomicBool::new(false);
+static NEXT_WINDOW: AtomicUsize = AtomicUsize::new(1);
+
+/// The number of frames shown in the overlay graph.
+const GRAPH_FRAMES: i32 = 120;
+/// The frame duration represented by the full height of the overlay graph.
+const GRAPH_SECONDS: f32 = 1. / 30.;
+
+/// Returns true if a profiler has ever been attached to a window.
+pub(crate) fn attached() -> bool {
+    ATTACHED.load(Ordering::Relaxed)
+}
+
+/// Records performance information about the windows it is attached to.
+///
+/// This type is cheap to clone. All clones share the same data.
+#[derive(Clone, Debug)]
+pub struct Profiler {
+    data: Arc<ProfilerData>,
+    /// Identifies the window this clone is measuring, allowing frames from
+    /// multiple windows to be in progress at the same time.
+    window: usize,
+}
+
+#[derive(Debug)]
+struct ProfilerData {
+    history: usize,
+    overlay: Dynamic<bool>,
+    state: Mutex<ProfilerState>,
+}
+
+#[derive(Debug, Default)]
+struct ProfilerState {
+    frames: VecDeque<FrameProfile>,
+    current: AHashMap<usize, FrameProfile>,
+    pending_invalidations: AHashMap<usize, Vec<Invalidation>>,
+    widgets: AHashMap<WidgetId, WidgetProfile>,
+}
+
+impl Profiler {
+    /// Returns a new profiler that keeps the 120 most recent frames.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_history(120)
+    }
+
+    /// Returns a new profiler that keeps the `frames` most recent frames.
+    #[must_use]
+    pub fn with_history(frames: usize) -> Self {
+        Self {
+            data: Arc::new(ProfilerData {
+                history: frames.max(1),
+                overlay: Dynamic::new(true),
+                state: Mutex::default(),
+            }),
+            window: 0,
+        }
+    }
+
+    /// Returns the dynamic controlling whether the overlay graph is drawn.
+    ///
+    /// The overlay is shown by default.
+    #[must_use]
+    pub fn overlay(&self) -> &Dynamic<bool> {
+        &self.data.overlay
+    }
+
+    /// Returns the most recently completed frames, oldest first.
+    #[must_use]
+    pub fn frames(&self) -> Vec<FrameProfile> {
+        self.data.state.lock().frames.iter().cloned().collect()
+    }
+
+    /// Returns the most recently completed frame, if any.
+    #[must_use]
+    pub fn last_frame(&self) -> Option<FrameProfile> {
+        self.data.state.lock().frames.back().cloned()
+    }
+
+    /// Returns the number of times each widget has been laid out and redrawn
+    /// since this profiler was created or last reset.
+    ///
+    /// The widgets are sorted with the most frequently updated widgets first.
+    #[must_use]
+    pub fn widgets(&self) -> Vec<(WidgetId, WidgetProfile)> {
+        let mut widgets = self
+            .0
+            .state
+            .lock()
+            .widgets
+            .iter()
+            .map(|(id, profile)| (*id, *profile))
+            .collect::<Vec<_>>();
+        widgets.sort_by(|(a_id, a), (b_id, b)| {
+            (b.layouts + b.redraws)
+                .cmp(&(a.layouts + a.redraws))
+                .then_with(|| a_id.cmp(b_id))
+        });
+        widgets
+    }
+
+    /// Clears all recorded data.
+    pub fn reset(&self) {
+        let mut state = self.data.state.lock();
+        state.frames.clear();
+        state.pending_invalidations.clear();
+        state.widgets.clear();
+    }
+
+    pub(crate) fn note_attached(&self) {
+        ATTACHED.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns a profiler sharing this profiler's data that measures a
+    /// separate window.
+    pub(crate) fn for_window(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            window: NEXT_WINDOW.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub(crate) fn begin_frame(&self) {
+        let mut state = self.data.state.lock();
+        if let Some(unrendered) = state.current.remove(&self.window) {
+            self.finish_frame(&mut state, unrendered);
+        }
+        let invalidations = state
+            .pending_invalidations
+            .remove(&self.window)
+            .unwrap_or_default();
+        state.current.insert(
+            self.window,
+            FrameProfile {
+                invalidations,
+                ..FrameProfile::default()
+            },
+        );
+    }
+
+    pub(crate) fn layout_finished(&self, elapsed: Duration) {
+        if let Some(frame) = self.data.state.lock().current.get_mut(&self.window) {
+            frame.layout += elapsed;
+        }
+    }
+
+    pub(crate) fn redraw_finished(&self, elapsed: Duration) {
+        if let Some(frame) = self.data.state.lock().current.get_mut(&self.window) {
+            frame.redraw += elapsed;
+        }
+    }
+
+    pub(crate) fn render_finished(&self, elapsed: Duration) {
+        let mut state = self.data.state.lock();
+        if let Some(mut frame) = state.current.remove(&self.window) {
+            frame.render = elapsed;
+            self.finish_frame(&mut state, frame);
+        }
+    }
+
+    fn finish_frame(&self, state: &mut ProfilerState, frame: FrameProfile) {
+        tracing::event!(
+            target: "cushy::profiler",
+            Level::DEBUG,
+            layout_us = frame.layout.as_micros(),
+            redraw_us = frame.redraw.as_micros(),
+            render_us = frame.render.as_micros(),
+            layouts = frame.layouts,
+            redraws = frame.redraws,
+            invalidations = frame.invalidations.len(),
+            "frame"
+        );
+        for invalidation in &frame.invalidations {
+            tracing::event!(
+                target: "cushy::profiler",
+                Level::TRACE,
+                value_type = invalidation.value_type,
+                dynamic = invalidation.dynamic,
+                widget = ?invalidation.widget,
+                "invalidated"
+            );
+        }
+
+        if state.frames.len() == self.data.history {
+            state.frames.pop_front();
+        }
+        state.frames.push_back(frame);
+    }
+
+    pub(crate) fn note_layout(&self, widget: &MountedWidget) {
+        self.note_widget(widget, |frame, profile| {
+            frame.layouts += 1;
+            profile.layouts += 1;
+        });
+    }
+
+    pub(crate) fn note_redraw(&self, widget: &MountedWidget) {
+        self.note_widget(widget, |frame, profile| {
+            frame.redraws += 1;
+            profile.redraws += 1;
+        });
+    }
+
+    fn note_widget(
+        &self,
+        widget: &MountedWidget,
+        update: impl FnOnce(&mut FrameProfile, &mut WidgetProfile),
+    ) {
+        let id = widget.id();
+        let mut state = self.data.state.lock();
+        if !state.widgets.contains_key(&id) {
+            // Avoid holding the state lock while locking the widget.
+            drop(state);
+            let type_name = widget.lock().as_widget().type_name();
+            state = self.data.state.lock();
+            state.widgets.entry(id).or_insert(WidgetProfile {
+                type_name,
+                layouts: 0,
+                redraws: 0,
+            });
+        }
+        let state = &mut *state;
+        let profile = state.widgets.get_mut(&id).expect("inserted above");
+        let mut unused = FrameProfile::default();
+        update(
+            state.current.get_mut(&self.window).unwrap_or(&mut unused),
+            profile,
+        );
+    }
+
+    pub(crate) fn note_invalidation(&self, invalidation: Invalidation) {
+        self.data
+            .state
+            .lock()
+            .pending_invalidations
+            .entry(self.window)
+            .or_default()
+            .push(invalidation);
+    }
+
+    /// Draws the overlay graph in the top-right corner of `context`.
+    pub(crate) fn draw_overlay(&self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.redraw_when_changed(&self.data.overlay);
+        if !self.data.overlay.get() {
+            return;
+        }
+
+        let theme = context.theme();
+        let phase_colors = [
+            theme.primary.color,
+            theme.secondary.color,
+            theme.tertiary.color,
+        ];
+        let background = theme.surface.lowest_container.with_alpha(220);
+        let text_color = theme.surface.on_color;
+
+        let scale = context.gfx.scale();
+        let padding = Lp::points(4).into_px(scale).round();
+        let bar_width = Lp::points(1).into_px(scale).round().max(Px::new(1));
+        let graph_height = Lp::points(48).into_px(scale).round();
+
+        let state = self.data.state.lock();
+        let summary = state
+            .frames
+            .back()
+            .map_or_else(|| String::from("Waiting for frames"), FrameProfile::summary);
+        context.apply_current_font_settings();
+        let summary = context
+            .gfx
+            .measure_text(Text::<Px>::new(&summary, text_color));
+
+        let graph_width = bar_width * GRAPH_FRAMES;
+        let panel_size = Size::new(
+            graph_width.max(summary.size.width) + padding * 2,
+            summary.size.height + graph_height + padding * 3,
+        );
+        let panel_origin = Point::new(
+            context.gfx.region().size.width - panel_size.width - padding,
+            padding,
+        );
+        context.gfx.draw_shape(&Shape::filled_rect(
+            Rect::new(panel_origin, panel_size),
+            background,
+        ));
+        context.gfx.draw_measured_text(
+            (&summary).translate_by(panel_origin + Point::squared(padding)),
+            TextOrigin::TopLeft,
+        );
+
+        let graph_bottom = panel_origin.y + panel_size.height - padding;
+        let graph_right = panel_origin.x + panel_size.width - padding;
+        // Mark the height of a frame that takes 1/60th of a second.
+        let budget = graph_height / 2;
+        context.gfx.draw_shape(&Shape::filled_rect(
+            Rect::new(
+                Point::new(graph_right - graph_width, graph_bottom - budget),
+                Size::new(graph_width, Px::new(1)),
+            ),
+            text_color.with_alpha(128),
+        ));
+        for (index, frame) in (1..=GRAPH_FRAMES).zip(state.frames.iter().rev()) {
+            let x = graph_right - bar_width * index;
+            let mut y = graph_bottom;
+            for (duration, color) in [frame.layout, frame.redraw, frame.render]
+                .into_iter()
+                .zip(phase_colors)
+            {
+                let height =
+                    Px::from(graph_height.into_float() * duration.as_secs_f32() / GRAPH_SECONDS)
+                        .min(y - (graph_bottom - graph_height));
+                if height > Px::new(0) {
+                    y -= height;
+                    context.gfx.draw_shape(&Shape::filled_rect(
+                        Rect::new(Point::new(x, y), Size::new(bar_width, height)),
+                        color,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The work performed while preparing and rendering a single frame.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameProfile {
+    /// The time spent performing layout.
+    pub layout: Duration,
+    /// The time spent redrawing widgets.
+    pub redraw: Duration,
+    /// The time spent rendering the redrawn contents to the GPU, including
+    /// submitting the rendered commands when the window controls submission.
+    pub render: Duration,
+    /// The number of times [`Widget::layout`](crate::widget::Widget::layout)
+    /// was invoked.
+    pub layouts: usize,
+    /// The number of times [`Widget::redraw`](crate::widget::Widget::redraw)
+    /// was invoked.
+    pub redraws: usize,
+    /// The changes to [`Dynamic`]s that caused this frame to be prepared.
+    pub invalidations: Vec<Invalidation>,
+}
+
+impl FrameProfile {
+    /// Returns the total time spent on this frame.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.layout + self.redraw + self.render
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "layout {:.1?} redraw {:.1?} render {:.1?}\n{} layouts, {} redraws, {} invalidations",
+            self.layout,
+            self.redraw,
+            self.render,
+            self.layouts,
+            self.redraws,
+            self.invalidations.len()
+        )
+    }
+}
+
+/// The number of times a widget has been laid out and redrawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WidgetProfile {
+    /// The Rust type name of the widget.
+    pub type_name: &'static str,
+    /// The number of times [`Widget::layout`](crate::widget::Widget::layout)
+    /// was invoked.
+    pub layouts: usize,
+    /// The number of times [`Widget::redraw`](crate::widget::Widget::redraw)
+    /// was invoked.
+    pub redraws: usize,
+}
+
+/// A change to a [`Dynamic`] that invalidated a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Invalidation {
+    /// The Rust type name of the dynamic's value.
+    pub value_type: &'static str,
+    /// A number that uniquely identifies the dynamic while it is allocated.
+    ///
+    /// This can be used to determine whether multiple invalidations were
+    /// caused by the same dynamic.
+    pub dynamic: usize,
+    /// The widget that was invalidated, if the dynamic was registered using
+    /// [`invalidate_when_changed`](crate::context::WidgetContext::invalidate_when_changed).
+    /// If the dynamic was registered using
+    /// [`redraw_when_changed`](crate::context::WidgetContext::redraw_when_changed),
+    /// this is `None`.
+    pub widget: Option<WidgetId>,
+}
+
+#[test]
+fn frame_history() {
+    let profiler = Profiler::with_history(2);
+    for _ in 0..3 {
+        profiler.note_invalidation(Invalidation {
+            value_type: "u32",
+            dynamic: 1,
+            widget: None,
+        });
+        profiler.begin_frame();
+        profiler.layout_finished(Duration::from_millis(1));
+        profiler.render_finished(Duration::from_millis(2));
+    }
+    let frames = profiler.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].total(), Duration::from_millis(3));
+    assert_eq!(frames[1].invalidations.len(), 1);
+}
+
+#[test]
+fn independent_windows() {
+    let profiler = Profiler::new();
+    let first = profiler.for_window();
+    let second = profiler.for_window();
+    first.note_invalidation(Invalidation {
+        value_type: "u32",
+        dynamic: 1,
+        widget: None,
+    });
+    first.begin_frame();
+    // Beginning another window's frame must not finish the first window's.
+    second.begin_frame();
+    first.layout_finished(Duration::from_millis(1));
+    second.layout_finished(Duration::from_millis(10));
+    assert!(profiler.frames().is_empty());
+
+    first.render_finished(Duration::from_millis(2));
+    second.render_finished(Duration::from_millis(20));
+    let frames = profiler.frames();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].total(), Duration::from_millis(3));
+    assert_eq!(frames[0].invalidations.len(), 1);
+    assert_eq!(frames[1].total(), Duration::from_millis(30));
+    assert!(frames[1].invalidations.is_empty());
+}
@@ -0,0 +1,219 @@
+//! Recording and replaying window input.
+//!
+//! An [`InputRecorder`] captures the keyboard, mouse, input method, resize,
+//! and focus events delivered to a window. Recorders can be attached to a
+//! [`Window`](crate::window::Window) using
+//! [`Window::record_input`](crate::window::Window::record_input) or to a
+//! standalone window using
+//! [`StandaloneWindowBuilder::record_input`](crate::window::StandaloneWindowBuilder::record_input).
+//!
+//! The captured [`InputRecording`] can be saved to a file and later replayed
+//! into a [`VirtualRecorder`](crate::window::VirtualRecorder) using
+//! [`AnimationRecorder::replay`](crate::window::AnimationRecorder::replay),
+//! optionally capturing an animated PNG of the replay. This allows
+//! reproducing user-reported bugs.
+//!
+//! ```rust,no_run
+//! use cushy::replay::{InputRecorder, InputRecording};
+//! use cushy::widget::MakeWidget;
+//! use cushy::Run;
+//!
+//! let recorder = InputRecorder::new();
+//! "Hello"
+//!     .into_button()
+//!     .into_window()
+//!     .record_input(&recorder)
+//!     .on_close({
+//!         let recorder = recorder.clone();
+//!         move || recorder.recording().save("input.json").unwrap()
+//!     })
+//!     .run()
+//!     .unwrap();
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use figures::units::{Px, UPx};
+use figures::{Point, Size};
+use kludgine::app::winit::event::{ElementState, Ime, MouseButton, MouseScrollDelta, TouchPhase};
+use kludgine::app::winit::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey, SmolStr};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+use crate::window::KeyEvent;
+
+/// Records input events delivered to one or more windows.
+///
+/// This type is cheap to clone. All clones share the same recording.
+#[derive(Clone, Debug)]
+pub struct InputRecorder(Arc<Mutex<RecorderState>>);
+
+#[derive(Debug)]
+struct RecorderState {
+    started_at: Instant,
+    events: Vec<RecordedEvent>,
+}
+
+impl InputRecorder {
+    /// Returns a new recorder whose timestamps are relative to the current
+    /// time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(RecorderState {
+            started_at: clock::now(),
+            events: Vec::new(),
+        })))
+    }
+
+    /// Records `input` as occurring at the current time.
+    pub fn record(&self, input: RecordedInput) {
+        let mut state = self.0.lock();
+        let at = clock::now().saturating_duration_since(state.started_at);
+        state.events.push(RecordedEvent { at, input });
+    }
+
+    /// Returns a copy of the events recorded so far.
+    #[must_use]
+    pub fn recording(&self) -> InputRecording {
+        InputRecording {
+            events: self.0.lock().events.clone(),
+        }
+    }
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A sequence of recorded input events.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+    /// The recorded events, in the order they occurred.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    /// Writes this recording as JSON to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(file, self)?;
+        Ok(())
+    }
+
+    /// Reads a recording previously written using [`InputRecording::save`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Returns the time the final event occurred at.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.events.last().map(|event| event.at).unwrap_or_default()
+    }
+}
+
+/// An input event and when it occurred.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// The amount of time since recording began.
+    pub at: Duration,
+    /// The input that occurred.
+    pub input: RecordedInput,
+}
+
+/// A recorded input event.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecordedInput {
+    /// A keyboard event.
+    Keyboard {
+        /// The logical key of the event.
+        logical_key: Key,
+        /// The physical key of the event.
+        physical_key: PhysicalKey,
+        /// The text input by the event.
+        text: Option<SmolStr>,
+        /// The location of the key.
+        location: KeyLocation,
+        /// Whether the key was pressed or released.
+        state: ElementState,
+        /// If true, the event was caused by a key being repeated.
+        repeat: bool,
+        /// The modifiers active during this event.
+        modifiers: ModifiersState,
+        /// If true, this event was synthesized rather than being caused by
+        /// the user.
+        is_synthetic: bool,
+    },
+    /// A mouse wheel event.
+    MouseWheel {
+        /// The amount scrolled.
+        delta: MouseScrollDelta,
+        /// The phase of the scroll gesture.
+        phase: TouchPhase,
+    },
+    /// An input method event.
+    Ime(Ime),
+    /// The cursor moved to a new location within the window.
+    CursorMoved(Point<Px>),
+    /// The cursor left the window.
+    CursorLeft,
+    /// A mouse button was pressed or released.
+    MouseInput {
+        /// Whether the button was pressed or released.
+        state: ElementState,
+        /// The button.
+        button: MouseButton,
+    },
+    /// The window was resized.
+    Resized(Size<UPx>),
+    /// The window gained or lost focus.
+    Focused(bool),
+}
+
+impl RecordedInput {
+    pub(crate) fn keyboard(input: &KeyEvent, is_synthetic: bool) -> Self {
+        Self::Keyboard {
+            logical_key: input.logical_key.clone(),
+            physical_key: input.physical_key,
+            text: input.text.clone(),
+            location: input.location,
+            state: input.state,
+            repeat: input.repeat,
+            modifiers: input.modifiers.state(),
+            is_synthetic,
+        }
+    }
+}
+
+#[test]
+fn recording_round_trip() {
+    let recorder = InputRecorder::new();
+    recorder.record(RecordedInput::CursorMoved(Point::px(10, 20)));
+    recorder.record(RecordedInput::MouseInput {
+        state: ElementState::Pressed,
+        button: MouseButton::Left,
+    });
+    recorder.record(RecordedInput::Keyboard {
+        logical_key: Key::Character(SmolStr::new("a")),
+        physical_key: PhysicalKey::Code(kludgine::app::winit::keyboard::KeyCode::KeyA),
+        text: Some(SmolStr::new("a")),
+        location: KeyLocation::Standard,
+        state: ElementState::Pressed,
+        repeat: false,
+        modifiers: ModifiersState::SHIFT,
+        is_synthetic: false,
+    });
+    let recording = recorder.recording();
+
+    let json = serde_json::to_string(&recording).unwrap();
+    let loaded: InputRecording = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, recording);
+}
@@ -0,0 +1,540 @@
+//! Persistent application settings stored in a file.
+//!
+//! [`Settings`] loads a file containing a set of key-value pairs and provides
+//! a [`Dynamic`] for each key. When any of these dynamics are updated, the
+//! settings file is saved after a short delay.
+//!
+//! The file format is chosen using a [`SettingsFormat`]. Each format is
+//! enabled by its own feature:
+//!
+//! | Format   | Feature         |
+//! |----------|-----------------|
+//! | [`Json`] | `settings-json` |
+//! | [`Toml`] | `settings-toml` |
+//! | [`Ron`]  | `settings-ron`  |
+//!
+//! Settings do not depend on a running application, so they can be loaded
+//! before any windows are opened:
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "settings-json")]
+//! # fn example() -> Result<(), cushy::settings::SettingsError> {
+//! use cushy::figures::units::UPx;
+//! use cushy::figures::Size;
+//! use cushy::settings::{Json, Settings};
+//! use cushy::widget::MakeWidget;
+//! use cushy::window::ThemeMode;
+//! use cushy::Run;
+//!
+//! let settings = Settings::<Json>::for_app("my-app")?;
+//! "Hello, World"
+//!     .into_window()
+//!     .inner_size(settings.get("window-size", Size::new(UPx::new(800), UPx::new(600))))
+//!     .themed_mode(settings.get_or_default::<ThemeMode>("theme-mode"))
+//!     .run()
+//!     .expect("error running app");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Keys that are present in the file but never requested are preserved when
+//! the file is saved. Keys that are requested but missing from the file, or
+//! that contain a value that cannot be deserialized, use the default value
+//! provided. This allows the set of settings to change between versions of an
+//! application.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use std::{fs, io};
+
+use ahash::AHashMap;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::value::{CallbackHandle, Destination, Dynamic, Source, WeakDynamic};
+
+/// The delay after the most recent change before settings are saved.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// A persistent store of settings.
+///
+/// This type is cheap to clone. See the [module documentation](self) for more
+/// information.
+pub struct Settings<Format>(Arc<SettingsData<Format>>)
+where
+    Format: SettingsFormat;
+
+struct SettingsData<Format>
+where
+    Format: SettingsFormat,
+{
+    path: PathBuf,
+    values: Mutex<BTreeMap<String, Format::Value>>,
+    entries: Mutex<AHashMap<String, Box<dyn Any + Send + Sync>>>,
+    dirty: AtomicBool,
+    changes: Dynamic<usize>,
+    _saved_changes: Dynamic<usize>,
+    _save: CallbackHandle,
+}
+
+impl<Format> Settings<Format>
+where
+    Format: SettingsFormat,
+{
+    /// Opens the settings for the application named `app_name`, located in the
+    /// current user's configuration directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be determined
+    /// or the settings file cannot be read.
+    pub fn for_app(app_name: &str) -> Result<Self, SettingsError> {
+        let directory = dirs::config_dir().ok_or(SettingsError::NoConfigDirectory)?;
+        Self::open(
+            directory
+                .join(app_name)
+                .join(format!("settings.{}", Format::EXTENSION)),
+        )
+    }
+
+    /// Opens the settings stored at `path`.
+    ///
+    /// If `path` does not exist, the settings will be empty and the file will
+    /// be created when the settings are first saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SettingsError> {
+        let path = path.into();
+        let values = match fs::read_to_string(&path) {
+            Ok(contents) => Format::parse(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(SettingsError::Io(err)),
+        };
+
+        let changes = Dynamic::new(0_usize);
+        let saved_changes = changes.debounced_with_delay(SAVE_DELAY);
+        Ok(Self(Arc::new_cyclic(
+            |data: &Weak<SettingsData<Format>>| {
+                let data = data.clone();
+                let save = saved_changes.for_each_subsequent(move |_| {
+                    let Some(data) = data.upgrade() else { return };
+                    if let Err(err) = data.save() {
+                        tracing::error!("error saving settings: {err}");
+                    }
+                });
+                SettingsData {
+                    path,
+                    values: Mutex::new(values),
+                    entries: Mutex::default(),
+                    dirty: AtomicBool::new(false),
+                    changes,
+                    _saved_changes: saved_changes,
+                    _save: save,
+                }
+            },
+        )))
+    }
+
+    /// Returns the path of the settings file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.0.path
+    }
+
+    /// Returns a dynamic containing the setting stored for `key`, or `default`
+    /// if no valid value is stored.
+    ///
+    /// Updating the returned dynamic updates the stored setting. Each call
+    /// with the same key returns the same dynamic while it is in use. The
+    /// returned dynamic keeps the settings open, so changes made after every
+    /// clone of these settings has been dropped are still saved.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `key` has already been requested with a
+    /// different type.
+    pub fn get<T>(&self, key: &str, default: T) -> Dynamic<T>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+    {
+        self.get_with(key, || default)
+    }
+
+    /// Returns a dynamic containing the setting stored for `key`, or
+    /// `T::default()` if no valid value is stored.
+    ///
+    /// Updating the returned dynamic updates the stored setting. Each call
+    /// with the same key returns the same dynamic while it is in use. The
+    /// returned dynamic keeps the settings open, so changes made after every
+    /// clone of these settings has been dropped are still saved.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `key` has already been requested with a
+    /// different type.
+    pub fn get_or_default<T>(&self, key: &str) -> Dynamic<T>
+    where
+        T: Serialize + DeserializeOwned + Default + Send + 'static,
+    {
+        self.get_with(key, T::default)
+    }
+
+    fn get_with<T>(&self, key: &str, default: impl FnOnce() -> T) -> Dynamic<T>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+    {
+        let mut entries = self.0.entries.lock();
+        if let Some(existing) = entries.get(key) {
+            if let Some(existing) = existing
+                .downcast_ref::<WeakDynamic<T>>()
+                .unwrap_or_else(|| panic!("setting `{key}` was requested using another type"))
+                .upgrade()
+            {
+                return existing;
+            }
+        }
+
+        let stored = self.0.values.lock().get(key).cloned();
+        let initial = match stored.map(Format::deserialize) {
+            Some(Ok(value)) => value,
+            Some(Err(err)) => {
+                tracing::warn!("ignoring invalid value for setting `{key}`: {err}");
+                default()
+            }
+            None => default(),
+        };
+
+        let value = Dynamic::new(initial);
+        // The callback keeps the settings alive for as long as the dynamic
+        // exists, while the settings only hold a weak reference to the dynamic
+        // to avoid a cycle.
+        let data = self.0.clone();
+        let owned_key = key.to_string();
+        value
+            .for_each_subsequent(move |value| data.store(&owned_key, value))
+            .persist();
+        entries.insert(key.to_string(), Box::new(value.downgrade()));
+        value
+    }
+
+    /// Saves the settings immediately.
+    ///
+    /// Settings are saved automatically shortly after any changes are made and
+    /// when the last clone of the settings is dropped. This function is useful
+    /// to ensure the settings are saved at a specific time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings cannot be serialized or written.
+    pub fn save(&self) -> Result<(), SettingsError> {
+        self.0.save()
+    }
+}
+
+impl<Format> SettingsData<Format>
+where
+    Format: SettingsFormat,
+{
+    fn store<T>(&self, key: &str, value: &T)
+    where
+        T: Serialize,
+    {
+        match Format::serialize(value) {
+            Ok(value) => {
+                self.values.lock().insert(key.to_string(), value);
+                self.dirty.store(true, Ordering::Release);
+                self.changes
+                    .map_mut(|mut changes| *changes = changes.wrapping_add(1));
+            }
+            Err(err) => tracing::error!("error serializing setting `{key}`: {err}"),
+        }
+    }
+
+    fn save(&self) -> Result<(), SettingsError> {
+        self.dirty.store(false, Ordering::Release);
+        let contents = Format::to_string(&self.values.lock())?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Writing to a temporary file and renaming it prevents a partially
+        // written file if the application exits while saving.
+        let temporary = self.path.with_extension("tmp");
+        fs::write(&temporary, contents)?;
+        fs::rename(&temporary, &self.path)?;
+        Ok(())
+    }
+}
+
+impl<Format> Drop for SettingsData<Format>
+where
+    Format: SettingsFormat,
+{
+    fn drop(&mut self) {
+        if self.dirty.load(Ordering::Acquire) {
+            if let Err(err) = self.save() {
+                tracing::error!("error saving settings: {err}");
+            }
+        }
+    }
+}
+
+impl<Format> Clone for Settings<Format>
+where
+    Format: SettingsFormat,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<Format> Debug for Settings<Format>
+where
+    Format: SettingsFormat,
+    Format::Value: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Settings")
+            .field("path", &self.0.path)
+            .field("values", &*self.0.values.lock())
+            .finish()
+    }
+}
+
+/// A file format that [`Settings`] can be stored in.
+pub trait SettingsFormat: Send + Sync + 'static {
+    /// The file extension used for files in this format.
+    const EXTENSION: &'static str;
+
+    /// The representation of a single setting's value in this format.
+    type Value: Clone + Send + Sync + 'static;
+
+    /// Parses the settings stored in `contents`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `contents` is not valid.
+    fn parse(contents: &str) -> Result<BTreeMap<String, Self::Value>, SettingsError>;
+
+    /// Returns the file contents that store `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `settings` cannot be represented in this format.
+    fn to_string(settings: &BTreeMap<String, Self::Value>) -> Result<String, SettingsError>;
+
+    /// Returns `value` in this format's representation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` cannot be represented in this format.
+    fn serialize<T>(value: &T) -> Result<Self::Value, SettingsError>
+    where
+        T: Serialize;
+
+    /// Returns the `T` stored in `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` does not contain a valid `T`.
+    fn deserialize<T>(value: Self::Value) -> Result<T, SettingsError>
+    where
+        T: DeserializeOwned;
+}
+
+/// Settings stored as JSON.
+#[cfg(feature = "settings-json")]
+#[derive(Debug, Clone, Copy)]
+pub struct Json;
+
+#[cfg(feature = "settings-json")]
+impl SettingsFormat for Json {
+    type Value = serde_json::Value;
+
+    const EXTENSION: &'static str = "json";
+
+    fn parse(contents: &str) -> Result<BTreeMap<String, Self::Value>, SettingsError> {
+        serde_json::from_str(contents).map_err(SettingsError::format)
+    }
+
+    fn to_string(settings: &BTreeMap<String, Self::Value>) -> Result<String, SettingsError> {
+        serde_json::to_string_pretty(settings).map_err(SettingsError::format)
+    }
+
+    fn serialize<T>(value: &T) -> Result<Self::Value, SettingsError>
+    where
+        T: Serialize,
+    {
+        serde_json::to_value(value).map_err(SettingsError::format)
+    }
+
+    fn deserialize<T>(value: Self::Value) -> Result<T, SettingsError>
+    where
+        T: DeserializeOwned,
+    {
+        serde_json::from_value(value).map_err(SettingsError::format)
+    }
+}
+
+/// Settings stored as TOML.
+///
+/// TOML is unable to represent `None` or unit values.
+#[cfg(feature = "settings-toml")]
+#[derive(Debug, Clone, Copy)]
+pub struct Toml;
+
+#[cfg(feature = "settings-toml")]
+impl SettingsFormat for Toml {
+    type Value = toml::Value;
+
+    const EXTENSION: &'static str = "toml";
+
+    fn parse(contents: &str) -> Result<BTreeMap<String, Self::Value>, SettingsError> {
+        toml::from_str(contents).map_err(SettingsError::format)
+    }
+
+    fn to_string(settings: &BTreeMap<String, Self::Value>) -> Result<String, SettingsError> {
+        toml::to_string_pretty(settings).map_err(SettingsError::format)
+    }
+
+    fn serialize<T>(value: &T) -> Result<Self::Value, SettingsError>
+    where
+        T: Serialize,
+    {
+        toml::Value::try_from(value).map_err(SettingsError::format)
+    }
+
+    fn deserialize<T>(value: Self::Value) -> Result<T, SettingsError>
+    where
+        T: DeserializeOwned,
+    {
+        value.try_into().map_err(SettingsError::format)
+    }
+}
+
+/// Settings stored as [RON](https://github.com/ron-rs/ron).
+#[cfg(feature = "settings-ron")]
+#[derive(Debug, Clone, Copy)]
+pub struct Ron;
+
+#[cfg(feature = "settings-ron")]
+impl SettingsFormat for Ron {
+    type Value = ron::Value;
+
+    const EXTENSION: &'static str = "ron";
+
+    fn parse(contents: &str) -> Result<BTreeMap<String, Self::Value>, SettingsError> {
+        ron::from_str(contents).map_err(SettingsError::format)
+    }
+
+    fn to_string(settings: &BTreeMap<String, Self::Value>) -> Result<String, SettingsError> {
+        ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())
+            .map_err(SettingsError::format)
+    }
+
+    fn serialize<T>(value: &T) -> Result<Self::Value, SettingsError>
+    where
+        T: Serialize,
+    {
+        let serialized = ron::to_string(value).map_err(SettingsError::format)?;
+        ron::from_str(&serialized).map_err(SettingsError::format)
+    }
+
+    fn deserialize<T>(value: Self::Value) -> Result<T, SettingsError>
+    where
+        T: DeserializeOwned,
+    {
+        value.into_rust().map_err(SettingsError::format)
+    }
+}
+
+/// An error from a [`Settings`] store.
+#[derive(Debug)]
+pub enum SettingsError {
+    /// An error occurred reading or writing the settings file.
+    Io(io::Error),
+    /// The settings could not be parsed or serialized.
+    Format(Box<dyn std::error::Error + Send + Sync>),
+    /// The current user's configuration directory could not be determined.
+    NoConfigDirectory,
+}
+
+impl SettingsError {
+    fn format(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Format(Box::new(error))
+    }
+}
+
+impl From<io::Error> for SettingsError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io(err) => Display::fmt(err, f),
+            SettingsError::Format(err) => Display::fmt(err, f),
+            SettingsError::NoConfigDirectory => {
+                f.write_str("the configuration directory could not be determined")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+#[test]
+#[cfg(feature = "settings-json")]
+fn settings_round_trip() {
+    let path = std::env::temp_dir().join(format!("cushy-settings-{}.json", std::process::id()));
+    fs::write(&path, r#"{"unknown": true, "count": "invalid"}"#).unwrap();
+
+    let settings = Settings::<Json>::open(&path).unwrap();
+    let count = settings.get("count", 1_u32);
+    // The stored value is not a valid u32, so the default is used.
+    assert_eq!(count.get(), 1);
+    count.set(2);
+    assert_eq!(settings.get("count", 0_u32), count);
+    drop(count);
+    drop(settings);
+
+    // Dropping the settings saved them, and the unknown key was preserved.
+    let settings = Settings::<Json>::open(&path).unwrap();
+    assert_eq!(settings.get("count", 0_u32).get(), 2);
+    assert!(settings.get("unknown", false).get());
+    drop(settings);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[cfg(feature = "settings-json")]
+fn settings_outlived_by_dynamic() {
+    let path = std::env::temp_dir().join(format!(
+        "cushy-settings-outlived-{}.json",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+
+    let settings = Settings::<Json>::open(&path).unwrap();
+    let count = settings.get("count", 1_u32);
+    drop(settings);
+
+    // The dynamic keeps the settings alive, so this change is saved when the
+    // dynamic is dropped.
+    count.set(3);
+    drop(count);
+
+    let settings = Settings::<Json>::open(&path).unwrap();
+    assert_eq!(settings.get("count", 0_u32).get(), 3);
+    drop(settings);
+    fs::remove_file(&path).unwrap();
+}
@@ -10,6 +10,7 @@ use kludgine::app::winit::event::{ElementState, MouseButton};
 use kludgine::app::winit::keyboard::Key;
 use parking_lot::{Condvar, Mutex, MutexGuard};
 
+use crate::clock::{self, Clock};
 use crate::context::WidgetContext;
 use crate::value::{Destination, Dynamic};
 use crate::widget::{EventHandling, HANDLED, IGNORED};
@@ -93,7 +94,9 @@ impl Tick {
     where
         F: FnMut(Duration, &InputState) + Send + 'static,
     {
-        let now = Instant::now();
+        // The tick uses the clock of the thread that created it.
+        let clock = Clock::current();
+        let now = clock.now();
         let data = Arc::new(TickData {
             state: Mutex::new(TickState {
                 last_time: now,
@@ -110,7 +113,10 @@ impl Tick {
 
         std::thread::spawn({
             let data = data.clone();
-            move || tick_loop(&data, tick)
+            move || {
+                clock.enter();
+                tick_loop(&data, tick);
+            }
         });
 
         Self {
@@ -189,21 +195,21 @@ where
 {
     let mut state = data.state();
     while state.keep_running {
-        let mut now = Instant::now();
-        match state.next_target.checked_duration_since(now) {
-            Some(remaining) if remaining > Duration::ZERO => {
-                drop(state);
-                std::thread::sleep(remaining);
-                state = data.state();
-
-                now = Instant::now();
-            }
-            _ => {}
+        let mut now = clock::now();
+        // When a manual clock is installed, advancing it waits for this guard
+        // to be dropped, ensuring the tick has been processed.
+        let mut awake = None;
+        if state.next_target > now {
+            let next_target = state.next_target;
+            drop(state);
+            awake = Some(clock::sleep_until(next_target));
+            state = data.state();
+
+            now = clock::now();
         }
 
-        let elapsed = now
-            .checked_duration_since(state.last_time)
-            .expect("instant never decreases");
+        // Removing a manual clock can cause time to move backwards.
+        let elapsed = now.saturating_duration_since(state.last_time);
         state.frame += 1;
 
         tick(elapsed, &state.input);
@@ -213,6 +219,7 @@ where
         // Signal that we have a new frame, which will cause the widget to
         // redraw.
         data.tick_number.map_mut(|mut tick| *tick += 1);
+        drop(awake);
 
         // Wait for a frame to be rendered.
         while state.keep_running {
@@ -1,12 +1,14 @@
 use std::mem;
 use std::sync::{Arc, Weak};
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use alot::{LotId, Lots};
 use figures::units::{Px, UPx};
 use figures::{Point, Rect, Size};
 use parking_lot::Mutex;
 
+use crate::debug::{WidgetDump, WidgetTreeDump};
+use crate::profiler::{self, Profiler};
 use crate::styles::{Styles, ThemePair, VisualOrder};
 use crate::value::Value;
 use crate::widget::{MountedWidget, WidgetId, WidgetInstance};
@@ -25,6 +27,7 @@ impl Tree {
         parent: Option<&MountedWidget>,
     ) -> MountedWidget {
         let mut data = self.data.lock();
+        data.structure_generation += 1;
         let id = widget.id();
         let (effective_styles, parent_id) = if let Some(parent) = parent {
             (
@@ -39,6 +42,8 @@ impl Tree {
             children: Vec::new(),
             parent: parent_id,
             last_layout_query: None,
+            invalidations: 0,
+            caches_contents: false,
             layout: None,
             associated_styles: None,
             effective_styles,
@@ -68,9 +73,16 @@ impl Tree {
 
     pub fn remove_child(&self, child: &MountedWidget, children_to_unmount: &mut Vec<WidgetId>) {
         let mut data = self.data.lock();
+        data.structure_generation += 1;
         data.remove_child(child.node_id, children_to_unmount);
     }
 
+    /// Returns a counter that is incremented each time a widget is added to
+    /// or removed from this tree.
+    pub(crate) fn structure_generation(&self) -> usize {
+        self.data.lock().structure_generation
+    }
+
     pub(crate) fn set_layout(&self, widget: LotId, rect: Rect<Px>) {
         let mut data = self.data.lock();
 
@@ -106,6 +118,19 @@ impl Tree {
             };
 
             data.invalidate(id, true);
+            data.note_invalidated(id);
+        }
+    }
+
+    pub(crate) fn set_profiler(&self, profiler: Profiler) {
+        self.data.lock().profiler = Some(profiler);
+    }
+
+    pub(crate) fn profiler(&self) -> Option<Profiler> {
+        if profiler::attached() {
+            self.data.lock().profiler.clone()
+        } else {
+            None
         }
     }
 
@@ -117,6 +142,31 @@ impl Tree {
         data.render_info.push(widget, layout);
     }
 
+    pub(crate) fn rendered_widget_count(&self) -> usize {
+        self.data.lock().render_info.order.len()
+    }
+
+    pub(crate) fn rendered_widgets_since(&self, index: usize) -> Vec<LotId> {
+        let data = self.data.lock();
+        data.render_info
+            .order
+            .get(index..)
+            .unwrap_or_default()
+            .iter()
+            .map(|area| area.node)
+            .collect()
+    }
+
+    pub(crate) fn note_widgets_rendered(&self, widgets: &[LotId]) {
+        let mut data = self.data.lock();
+        for &widget in widgets {
+            let Some(layout) = data.nodes.get(widget).and_then(|node| node.layout) else {
+                continue;
+            };
+            data.render_info.push(widget, layout);
+        }
+    }
+
     pub(crate) fn begin_layout(
         &self,
         parent: LotId,
@@ -273,6 +323,63 @@ impl Tree {
         data.widget_from_id(id, self)
     }
 
+    pub(crate) fn children(&self, id: LotId) -> Vec<MountedWidget> {
+        let data = self.data.lock();
+        let Some(node) = data.nodes.get(id) else {
+            return Vec::new();
+        };
+        node.children
+            .iter()
+            .filter_map(|child| data.widget_from_node(*child, self))
+            .collect()
+    }
+
+    /// Returns all mounted widgets, with parents before their children.
+    pub(crate) fn widgets(&self) -> Vec<MountedWidget> {
+        let data = self.data.lock();
+        let mut to_visit = data
+            .nodes
+            .entries()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        to_visit.reverse();
+        let mut widgets = Vec::with_capacity(data.nodes.len());
+        while let Some(id) = to_visit.pop() {
+            let node = &data.nodes[id];
+            widgets.push(MountedWidget {
+                node_id: id,
+                widget: node.widget.clone(),
+                tree: WeakTree(Arc::downgrade(&self.data)),
+            });
+            to_visit.extend(node.children.iter().rev().copied());
+        }
+        widgets
+    }
+
+    /// Returns a snapshot of the mounted widget hierarchy.
+    pub(crate) fn dump(&self) -> WidgetTreeDump {
+        let data = self.data.lock();
+        let rendered = data
+            .render_info
+            .order
+            .iter()
+            .map(|area| area.node)
+            .collect::<AHashSet<_>>();
+        let roots = data
+            .nodes
+            .entries()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| data.snapshot(id, &rendered))
+            .collect::<Vec<_>>();
+        drop(data);
+
+        // Widgets are only locked after the tree has been unlocked.
+        WidgetTreeDump {
+            roots: roots.into_iter().map(NodeSnapshot::into_dump).collect(),
+        }
+    }
+
     pub(crate) fn widget_is_valid(&self, id: LotId) -> bool {
         let data = self.data.lock();
         data.nodes.get(id).is_some()
@@ -400,7 +507,35 @@ impl Tree {
     }
 
     pub fn invalidate(&self, id: LotId, include_hierarchy: bool) {
-        self.data.lock().invalidate(id, include_hierarchy);
+        let mut data = self.data.lock();
+        data.invalidate(id, include_hierarchy);
+        data.note_invalidated(id);
+    }
+
+    pub(crate) fn invalidation_generation(&self, id: LotId) -> usize {
+        let data = self.data.lock();
+        data.nodes.get(id).map_or(0, |node| node.invalidations)
+    }
+
+    pub(crate) fn set_caches_contents(&self, id: LotId) {
+        let mut data = self.data.lock();
+        if let Some(node) = data.nodes.get_mut(id) {
+            node.caches_contents = true;
+        }
+    }
+
+    /// Returns true if an ancestor of `id` caches the rendered contents of its
+    /// descendants.
+    pub(crate) fn is_cached(&self, id: LotId) -> bool {
+        let data = self.data.lock();
+        let mut parent = data.nodes.get(id).and_then(|node| node.parent);
+        while let Some(node) = parent.and_then(|id| data.nodes.get(id)) {
+            if node.caches_contents {
+                return true;
+            }
+            parent = node.parent;
+        }
+        false
     }
 }
 
@@ -434,9 +569,26 @@ struct TreeData {
     escapes: Vec<LotId>,
     render_info: RenderInfo,
     previous_focuses: AHashMap<WidgetId, WidgetId>,
+    profiler: Option<Profiler>,
+    structure_generation: usize,
 }
 
 impl TreeData {
+    fn snapshot(&self, id: LotId, rendered: &AHashSet<LotId>) -> NodeSnapshot {
+        let node = &self.nodes[id];
+        NodeSnapshot {
+            widget: node.widget.clone(),
+            layout: node.layout,
+            visible: rendered.contains(&id),
+            focused: self.focus == Some(id),
+            children: node
+                .children
+                .iter()
+                .map(|child| self.snapshot(*child, rendered))
+                .collect(),
+        }
+    }
+
     fn widget_from_id(&self, id: WidgetId, tree: &Tree) -> Option<MountedWidget> {
         let node_id = *self.nodes_by_id.get(&id)?;
         Some(MountedWidget {
@@ -552,6 +704,16 @@ impl TreeData {
         }
     }
 
+    fn note_invalidated(&mut self, mut id: LotId) {
+        while let Some(node) = self.nodes.get_mut(id) {
+            node.invalidations = node.invalidations.wrapping_add(1);
+            let Some(parent) = node.parent else {
+                break;
+            };
+            id = parent;
+        }
+    }
+
     fn invalidate(&mut self, id: LotId, include_hierarchy: bool) {
         let Some(mut node) = self.nodes.get_mut(id) else {
             return;
@@ -627,12 +789,40 @@ impl RenderArea {
     }
 }
 
+struct NodeSnapshot {
+    widget: WidgetInstance,
+    layout: Option<Rect<Px>>,
+    visible: bool,
+    focused: bool,
+    children: Vec<NodeSnapshot>,
+}
+
+impl NodeSnapshot {
+    fn into_dump(self) -> WidgetDump {
+        let (type_name, summary) = self
+            .widget
+            .try_describe()
+            .unwrap_or(("<locked>", String::new()));
+        WidgetDump {
+            type_name,
+            id: self.widget.id(),
+            layout: self.layout,
+            visible: self.visible,
+            focused: self.focused,
+            summary,
+            children: self.children.into_iter().map(Self::into_dump).collect(),
+        }
+    }
+}
+
 struct Node {
     widget: WidgetInstance,
     children: Vec<LotId>,
     parent: Option<LotId>,
     layout: Option<Rect<Px>>,
     last_layout_query: Option<CachedLayoutQuery>,
+    invalidations: usize,
+    caches_contents: bool,
     associated_styles: Option<Value<Styles>>,
     effective_styles: Styles,
     theme: Option<Value<ThemePair>>,
@@ -1,32 +1,67 @@
 //! Types for storing and interacting with values in Widgets.
 
 use std::cell::{Ref, RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Debug, Display};
 use std::future::Future;
 use std::hash::{BuildHasher, Hash};
 use std::ops::{Add, AddAssign, Deref, DerefMut, Not};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Weak};
-use std::task::{Poll, Waker};
+use std::task::{ready, Poll, Waker};
 use std::thread::{self, ThreadId};
 use std::time::{Duration, Instant};
 
 use ahash::{AHashMap, AHashSet};
 use alot::{LotId, Lots};
+use futures_core::Stream;
 use intentional::Assert;
 use kempt::{Map, Sort};
-use parking_lot::{Condvar, Mutex, MutexGuard};
+use parking_lot::{Condvar, Mutex, MutexGuard, ReentrantMutex};
 
 use crate::animation::{AnimationHandle, DynamicTransition, IntoAnimate, LinearInterpolate, Spawn};
+use crate::app::Application;
+use crate::clock;
 use crate::context::{self, Trackable, WidgetContext};
+use crate::profiler::{self, Invalidation};
 use crate::utils::WithClone;
 use crate::widget::{
-    MakeWidget, MakeWidgetWithTag, OnceCallback, WidgetId, WidgetInstance, WidgetList,
+    ChildrenSyncChange, MakeWidget, MakeWidgetWithTag, OnceCallback, WidgetId, WidgetInstance,
+    WidgetList,
 };
 use crate::widgets::{Label, Radio, Select, Space, Switcher};
 use crate::window::WindowHandle;
 
+/// Derives projections for each field of a struct.
+///
+/// This macro generates a trait named `<Struct>Lenses` with the same
+/// visibility as the struct. The trait contains one function per field, named
+/// after the field, that returns [`Dynamic::project`] for that field. Fields of
+/// tuple structs are named `field_0`, `field_1`, and so on. The trait is
+/// implemented for `Dynamic<Struct>`.
+///
+/// Fields named after a method of [`Dynamic`], such as `get` or `set`, are
+/// rejected because the method would shadow or conflict with the projection
+/// function. These fields can be projected using [`Dynamic::project`].
+///
+/// ```rust
+/// use cushy::value::{Destination, Dynamic, Lenses, Source};
+///
+/// #[derive(Lenses, Default, PartialEq)]
+/// struct Settings {
+///     name: String,
+///     volume: u8,
+/// }
+///
+/// let settings = Dynamic::new(Settings::default());
+/// let volume = settings.volume();
+/// volume.set(11);
+/// assert_eq!(settings.map_ref(|settings| settings.volume), 11);
+/// ```
+#[rustfmt::skip]
+pub use cushy_macros::Lenses;
+
 /// A source of one or more `T` values.
 pub trait Source<T> {
     /// Maps the contents with read-only access, providing access to the value's
@@ -308,6 +343,132 @@ pub trait Source<T> {
         debounced
     }
 
+    /// Returns a new dynamic that contains the updated contents of this dynamic,
+    /// updating at most once per `period`.
+    ///
+    /// `edges` controls whether the first update in each period is stored
+    /// immediately, whether the last update in each period is stored when the
+    /// period ends, or both.
+    #[must_use]
+    fn throttled(&self, period: Duration, edges: ThrottleEdges) -> Dynamic<T>
+    where
+        T: PartialEq + Clone + Send + 'static,
+    {
+        let throttled = Dynamic::new(self.get());
+        let throttle = Throttle::new(&throttled, period, edges);
+        throttled.set_source(self.for_each_subsequent(move |value| {
+            throttle.update(value.clone());
+        }));
+        throttled
+    }
+
+    /// Returns a new dynamic that contains the most recent value of this
+    /// dynamic for which `predicate` returns true.
+    ///
+    /// The returned dynamic initially contains the current value, even if
+    /// `predicate` would not accept it.
+    #[must_use]
+    fn filter<F>(&self, mut predicate: F) -> Dynamic<T>
+    where
+        T: PartialEq + Clone + Send + 'static,
+        F: for<'a> FnMut(&'a T) -> bool + Send + 'static,
+    {
+        let filtered = Dynamic::new(self.get());
+        let filtered_weak = filtered.downgrade();
+        filtered.set_source(self.for_each_subsequent_try(move |value| {
+            let filtered = filtered_weak.upgrade().ok_or(CallbackDisconnected)?;
+            if predicate(value) {
+                filtered.set(value.clone());
+            }
+            Ok(())
+        }));
+        filtered
+    }
+
+    /// Returns a new dynamic that is only updated when the key returned by
+    /// `key` for the new value differs from the key of the current value.
+    ///
+    /// Unlike the other functions that create new dynamics, `T` is not
+    /// required to implement `PartialEq`.
+    #[must_use]
+    fn distinct_by<K, F>(&self, mut key: F) -> Dynamic<T>
+    where
+        T: Clone + Send + 'static,
+        K: PartialEq + Send + 'static,
+        F: for<'a> FnMut(&'a T) -> K + Send + 'static,
+    {
+        let (initial, mut current_key) = self.map_ref(|value| (value.clone(), key(value)));
+        let distinct = Dynamic::new(initial);
+        let distinct_weak = distinct.downgrade();
+        distinct.set_source(self.for_each_subsequent_try(move |value| {
+            let distinct = distinct_weak.upgrade().ok_or(CallbackDisconnected)?;
+            let new_key = key(value);
+            if new_key != current_key {
+                current_key = new_key;
+                *distinct.lock() = value.clone();
+            }
+            Ok(())
+        }));
+        distinct
+    }
+
+    /// Returns a new dynamic that accumulates each value of this dynamic using
+    /// `fold`.
+    ///
+    /// The returned dynamic begins with `initial`, and `fold` is immediately
+    /// invoked with the current value. Afterwards, `fold` is invoked each time
+    /// this dynamic is updated.
+    ///
+    /// ```rust
+    /// use cushy::value::{Destination, Dynamic, Source};
+    ///
+    /// let value = Dynamic::new(1);
+    /// let history = value.scan(Vec::new(), |history, value| history.push(*value));
+    /// value.set(2);
+    /// assert_eq!(history.get(), vec![1, 2]);
+    /// ```
+    #[must_use]
+    fn scan<R, F>(&self, mut initial: R, mut fold: F) -> Dynamic<R>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+        F: for<'a> FnMut(&'a mut R, &'a T) + Send + 'static,
+    {
+        self.map_ref(|value| fold(&mut initial, value));
+        let accumulated = Dynamic::new(initial);
+        let accumulated_weak = accumulated.downgrade();
+        accumulated.set_source(self.for_each_subsequent_try(move |value| {
+            let accumulated = accumulated_weak.upgrade().ok_or(CallbackDisconnected)?;
+            accumulated.map_mut(|mut accumulated| fold(&mut accumulated, value));
+            Ok(())
+        }));
+        accumulated
+    }
+
+    /// Returns a new dynamic that contains the previous and current values of
+    /// this dynamic.
+    ///
+    /// The returned dynamic initially contains `(None, current)`. Each time
+    /// this dynamic is updated, the returned dynamic is updated to contain
+    /// `(Some(previous), new)`.
+    #[must_use]
+    fn previous(&self) -> Dynamic<(Option<T>, T)>
+    where
+        T: Clone + Send + 'static,
+    {
+        let pair = Dynamic::new((None, self.get()));
+        let pair_weak = pair.downgrade();
+        pair.set_source(self.for_each_subsequent_try(move |value| {
+            let pair = pair_weak.upgrade().ok_or(CallbackDisconnected)?;
+            pair.map_mut(|mut pair| {
+                let previous = std::mem::replace(&mut pair.1, value.clone());
+                pair.0 = Some(previous);
+            });
+            Ok(())
+        }));
+        pair
+    }
+
     /// Creates a new dynamic value that contains the result of invoking `map`
     /// each time this value is changed.
     fn map_each_generational<R, F>(&self, mut map: F) -> Dynamic<R>
@@ -400,6 +561,35 @@ pub trait Source<T> {
     {
         self.map_each(|value| U::from(value))
     }
+
+    /// Returns a [`Stream`] of this source's values.
+    ///
+    /// The first item produced by the stream is the current value. Afterwards,
+    /// an item is produced each time this source is updated. The stream only
+    /// stores the most recent value, which means if multiple updates happen
+    /// before the stream is polled, only the last value is produced.
+    ///
+    /// The stream does not keep `self` alive. Once all instances of this source
+    /// have been dropped, the stream ends after producing any remaining value.
+    fn to_stream(&self) -> ValueStream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(ValueStreamState {
+            value: None,
+            waker: None,
+            disconnected: false,
+        }));
+        let sender = ValueStreamSender(shared.clone());
+        let handle = self.for_each_cloned(move |value| sender.send(value)).weak();
+        // Reading after installing the callback ensures no updates can be
+        // missed.
+        shared.lock().value = Some(self.get());
+        ValueStream {
+            shared,
+            _handle: handle,
+        }
+    }
 }
 
 /// A destination for values of type `T`.
@@ -610,6 +800,7 @@ impl<T> Source<T> for Arc<DynamicData<T>> {
         map: impl FnOnce(DynamicGuard<'_, T, true>) -> R,
     ) -> Result<R, DeadlockError> {
         let state = self.state()?;
+        track_computed_read(&state.callbacks);
         Ok(map(DynamicGuard {
             guard: DynamicOrOwnedGuard::Dynamic(state),
             accessed_mut: false,
@@ -711,6 +902,13 @@ impl<T> Destination<T> for Dynamic<T> {
     fn try_map_mut<R>(&self, map: impl FnOnce(Mutable<'_, T>) -> R) -> Result<R, DeadlockError> {
         self.0.map_mut(map)
     }
+
+    fn set(&self, new_value: T)
+    where
+        T: PartialEq,
+    {
+        let _result = self.0.set(new_value);
+    }
 }
 
 /// A `mut` reference to `T` that tracks whether the contents have been accessed
@@ -1141,6 +1339,79 @@ impl<T> Dynamic<T> {
         self.linked(ToString::to_string, |s: &String| s.parse().ok())
     }
 
+    /// Returns a new dynamic that is kept in sync with the portion of `self`
+    /// returned by `project`.
+    ///
+    /// When `self` is updated, the projected value is cloned into the returned
+    /// dynamic. When the returned dynamic is updated, its value is stored in
+    /// the projected location within `self`. Updates are only propagated when
+    /// the values differ, which prevents the two dynamics from updating each
+    /// other in a loop.
+    ///
+    /// ```rust
+    /// use cushy::value::{Destination, Dynamic, Source};
+    ///
+    /// #[derive(Debug, Default, PartialEq)]
+    /// struct Settings {
+    ///     volume: u8,
+    /// }
+    ///
+    /// let settings = Dynamic::new(Settings::default());
+    /// let volume = settings.project(|settings| &mut settings.volume);
+    /// volume.set(11);
+    /// assert_eq!(settings.map_ref(|settings| settings.volume), 11);
+    /// settings.map_mut(|mut settings| settings.volume = 5);
+    /// assert_eq!(volume.get(), 5);
+    /// ```
+    ///
+    /// [`Lenses`](crate::value::Lenses) can be derived to generate a projection
+    /// for each field of a struct.
+    pub fn project<R, Project>(&self, project: Project) -> Dynamic<R>
+    where
+        T: Send + 'static,
+        R: Clone + PartialEq + Send + 'static,
+        Project: for<'a> Fn(&'a mut T) -> &'a mut R + Send + Sync + 'static,
+    {
+        let project = Arc::new(project);
+        let r = Dynamic::new(self.read_projection(&*project));
+        let r_weak = r.downgrade();
+        let t_weak = self.downgrade();
+        r.set_source(dynamic_for_each(&self.0, {
+            let project = project.clone();
+            move || {
+                let t = t_weak.upgrade().ok_or(CallbackDisconnected)?;
+                let r = r_weak.upgrade().ok_or(CallbackDisconnected)?;
+                r.set(t.read_projection(&*project));
+                Ok(())
+            }
+        }));
+
+        // The projected dynamic holds a reference to the original, since it's
+        // being created from the original.
+        let t = self.clone();
+        self.set_source(r.for_each_subsequent(move |r| {
+            t.map_mut(|mut t| {
+                let projected = project(&mut *t.value);
+                if *projected != *r {
+                    projected.clone_from(r);
+                    t.mutated.set(true);
+                }
+            });
+        }));
+
+        r
+    }
+
+    /// Returns a clone of the projected value without notifying observers of
+    /// `self`.
+    fn read_projection<R>(&self, project: impl FnOnce(&mut T) -> &mut R) -> R
+    where
+        R: Clone,
+    {
+        let mut state = self.state().expect("deadlocked");
+        project(&mut state.wrapped.value).clone()
+    }
+
     /// Sets the current `source` for this dynamic with `source`.
     ///
     /// A dynamic can have multiple source callbacks.
@@ -1151,6 +1422,150 @@ impl<T> Dynamic<T> {
         self.state().assert("deadlocked").source_callback += source;
     }
 
+    /// Returns a new dynamic that contains the result of `compute`.
+    ///
+    /// Every [`Source`] read while `compute` is executing is recorded as a
+    /// dependency. When any dependency is changed, `compute` is invoked again
+    /// and the returned dynamic is updated with the result. Dependencies are
+    /// recorded again each time `compute` is invoked, which allows `compute` to
+    /// read different sources depending on the values it observes:
+    ///
+    /// ```rust
+    /// use cushy::value::{Destination, Dynamic, Source};
+    ///
+    /// let use_metric = Dynamic::new(true);
+    /// let meters = Dynamic::new(1.0_f32);
+    /// let feet = Dynamic::new(3.0_f32);
+    /// let distance = Dynamic::computed({
+    ///     let (use_metric, meters, feet) = (use_metric.clone(), meters.clone(), feet.clone());
+    ///     move || {
+    ///         if use_metric.get() {
+    ///             format!("{}m", meters.get())
+    ///         } else {
+    ///             format!("{}ft", feet.get())
+    ///         }
+    ///     }
+    /// });
+    /// assert_eq!(distance.get(), "1m");
+    /// // `feet` was not read, so changing it does not cause `compute` to run.
+    /// feet.set(6.0);
+    /// use_metric.set(false);
+    /// assert_eq!(distance.get(), "6ft");
+    /// ```
+    ///
+    /// Only values read through [`Source`] are tracked. Reads performed by
+    /// change callbacks that are invoked while `compute` is executing are not
+    /// recorded. If `compute` modifies one of its own dependencies, it will not
+    /// be invoked recursively.
+    pub fn computed<F>(mut compute: F) -> Self
+    where
+        F: FnMut() -> T + Send + 'static,
+        T: PartialEq + Send + 'static,
+    {
+        let (initial, reads) = track_computed_reads(&mut compute);
+        let computed = Self::new(initial);
+        let state = Arc::new(ReentrantMutex::new(RefCell::new(ComputedState {
+            compute,
+            computed: computed.downgrade(),
+            dependencies: Vec::new(),
+        })));
+        state
+            .lock()
+            .borrow_mut()
+            .track(&Arc::downgrade(&state), reads);
+        // The computed dynamic keeps the state alive, and the state keeps the
+        // dependency callbacks installed.
+        computed.keep_alive(state);
+        computed
+    }
+
+    /// Returns a new dynamic that initially contains `initial` and is updated
+    /// with each item produced by `stream`.
+    ///
+    /// `stream` is polled using `app`'s [`AppRuntime`](crate::AppRuntime).
+    /// Once all instances of the returned dynamic, including any
+    /// [`DynamicReader`]s, have been dropped, `stream` is dropped without being
+    /// polled again.
+    pub fn from_stream<S>(app: &impl Application, initial: T, stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+        T: PartialEq + Send + 'static,
+    {
+        let mut stream = Box::pin(stream);
+        Self::driven_by(app, initial, move |cx| stream.as_mut().poll_next(cx))
+    }
+
+    /// Returns a new dynamic that initially contains `initial` and is updated
+    /// with the output of `future` once it completes.
+    ///
+    /// `future` is executed using `app`'s [`AppRuntime`](crate::AppRuntime).
+    /// Once all instances of the returned dynamic, including any
+    /// [`DynamicReader`]s, have been dropped, `future` is dropped without being
+    /// polled again.
+    pub fn from_future<F>(app: &impl Application, initial: T, future: F) -> Self
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: PartialEq + Send + 'static,
+    {
+        let mut future = Some(Box::pin(future));
+        Self::driven_by(app, initial, move |cx| {
+            let Some(pending) = &mut future else {
+                return Poll::Ready(None);
+            };
+            let value = ready!(pending.as_mut().poll(cx));
+            future = None;
+            Poll::Ready(Some(value))
+        })
+    }
+
+    fn driven_by<Next>(app: &impl Application, initial: T, mut poll_next: Next) -> Self
+    where
+        Next: FnMut(&mut std::task::Context<'_>) -> Poll<Option<T>> + Send + 'static,
+        T: PartialEq + Send + 'static,
+    {
+        let dynamic = Self::new(initial);
+        let cancellation = Arc::new(Cancellation::default());
+        let weak = dynamic.downgrade();
+        app.cushy().spawn({
+            let cancellation = cancellation.clone();
+            std::future::poll_fn(move |cx| {
+                if cancellation.register(cx.waker()) {
+                    return Poll::Ready(());
+                }
+
+                loop {
+                    match poll_next(cx) {
+                        Poll::Ready(Some(value)) => {
+                            let Some(dynamic) = weak.upgrade() else {
+                                return Poll::Ready(());
+                            };
+                            dynamic.set(value);
+                        }
+                        Poll::Ready(None) => return Poll::Ready(()),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            })
+        });
+        // Dropping the last instance of the dynamic drops the cancellation
+        // guard, waking the task so that it can exit.
+        dynamic.keep_alive(Arc::new(CancelOnDrop(cancellation)));
+        dynamic
+    }
+
+    /// Keeps `owner` alive until all instances of this dynamic have been
+    /// dropped.
+    fn keep_alive(&self, owner: Arc<dyn ReferencedDynamic>) {
+        let callbacks = self.state().assert("deadlocked").callbacks.clone();
+        self.set_source(CallbackHandle(CallbackHandleInner::Single(
+            CallbackHandleData {
+                id: None,
+                owner: Some(owner),
+                callbacks,
+            },
+        )));
+    }
+
     /// Attaches `for_each` to this value so that it is invoked each time the
     /// value's contents are updated. This function returns `self`.
     #[must_use]
@@ -1583,7 +1998,7 @@ impl<T> DynamicData<T> {
         let mut during_sync = self.during_callback_state.lock();
 
         let current_thread_id = std::thread::current().id();
-        let guard = loop {
+        let mut guard = loop {
             match self.state.try_lock() {
                 Some(g) => break g,
                 None => loop {
@@ -1602,6 +2017,7 @@ impl<T> DynamicData<T> {
         *during_sync = Some(LockState {
             locked_thread: current_thread_id,
         });
+        guard.apply_restorations();
         Ok(DynamicMutexGuard {
             dynamic: self,
             guard,
@@ -1640,6 +2056,35 @@ impl<T> DynamicData<T> {
 
         Ok(old)
     }
+
+    /// Stores `new_value` if it differs from the current value. The previous
+    /// value is kept if a transaction using [`Transaction::restoring_all`] is
+    /// active on the current thread.
+    fn set(&self, new_value: T) -> Result<(), DeadlockError>
+    where
+        T: PartialEq,
+    {
+        let mut state = self.state()?;
+        let (old, callbacks) = {
+            let state = &mut *state;
+            if state.wrapped.value == new_value {
+                (None, None)
+            } else {
+                let old = std::mem::replace(&mut state.wrapped.value, new_value);
+                // The original must be recorded before noting the change to
+                // avoid warning that it cannot be restored.
+                let old = state.record_original(old);
+                (old, Some(state.note_changed()))
+            }
+        };
+        drop(state);
+        drop(callbacks);
+        drop(old);
+
+        self.sync.notify_all();
+
+        Ok(())
+    }
 }
 
 fn dynamic_for_each<T, F>(this: &Arc<DynamicData<T>>, map: F) -> CallbackHandle
@@ -1656,6 +2101,106 @@ where
     }))
 }
 
+thread_local! {
+    static COMPUTED_READS: RefCell<Option<Vec<Arc<ChangeCallbacksData>>>> =
+        const { RefCell::new(None) };
+}
+
+/// Restores the previous set of tracked reads when dropped, even if the
+/// tracked function panics.
+struct RestoreComputedReads(Option<Vec<Arc<ChangeCallbacksData>>>);
+
+impl Drop for RestoreComputedReads {
+    fn drop(&mut self) {
+        COMPUTED_READS.set(self.0.take());
+    }
+}
+
+/// Invokes `compute`, returning its result and the callback collections of
+/// every dynamic read during its execution.
+fn track_computed_reads<R>(compute: impl FnOnce() -> R) -> (R, Vec<Arc<ChangeCallbacksData>>) {
+    let restore = RestoreComputedReads(COMPUTED_READS.replace(Some(Vec::new())));
+    let result = compute();
+    let reads = COMPUTED_READS.take().unwrap_or_default();
+    drop(restore);
+    (result, reads)
+}
+
+/// Invokes `f` without recording any reads it performs as dependencies.
+fn untracked<R>(f: impl FnOnce() -> R) -> R {
+    let _restore = RestoreComputedReads(COMPUTED_READS.take());
+    f()
+}
+
+fn track_computed_read(callbacks: &Arc<ChangeCallbacksData>) {
+    COMPUTED_READS.with_borrow_mut(|reads| {
+        if let Some(reads) = reads {
+            if !reads.iter().any(|read| Arc::ptr_eq(read, callbacks)) {
+                reads.push(callbacks.clone());
+            }
+        }
+    });
+}
+
+type SharedComputedState<T, F> = Arc<ReentrantMutex<RefCell<ComputedState<T, F>>>>;
+
+struct ComputedState<T, F> {
+    compute: F,
+    computed: WeakDynamic<T>,
+    dependencies: Vec<(Arc<ChangeCallbacksData>, CallbackHandle)>,
+}
+
+impl<T, F> ComputedState<T, F>
+where
+    F: FnMut() -> T + Send + 'static,
+    T: PartialEq + Send + 'static,
+{
+    fn recompute(this: &SharedComputedState<T, F>) -> Result<(), CallbackDisconnected> {
+        let guard = this.lock();
+        let Ok(mut state) = guard.try_borrow_mut() else {
+            // The computation modified one of its own dependencies.
+            return Ok(());
+        };
+        let computed = state.computed.upgrade().ok_or(CallbackDisconnected)?;
+        let (value, reads) = track_computed_reads(&mut state.compute);
+        state.track(&Arc::downgrade(this), reads);
+        computed.set(value);
+        Ok(())
+    }
+
+    /// Updates the installed callbacks to match `reads`, keeping the callbacks
+    /// for dependencies that are still being read.
+    fn track(
+        &mut self,
+        this: &Weak<ReentrantMutex<RefCell<Self>>>,
+        reads: Vec<Arc<ChangeCallbacksData>>,
+    ) {
+        self.dependencies
+            .retain(|(dependency, _)| reads.iter().any(|read| Arc::ptr_eq(read, dependency)));
+        for read in reads {
+            if self
+                .dependencies
+                .iter()
+                .any(|(dependency, _)| Arc::ptr_eq(dependency, &read))
+            {
+                continue;
+            }
+
+            let this = this.clone();
+            let id = read.callbacks.lock().callbacks.push(Box::new(move || {
+                let this = this.upgrade().ok_or(CallbackDisconnected)?;
+                Self::recompute(&this)
+            }));
+            let handle = CallbackHandle(CallbackHandleInner::Single(CallbackHandleData {
+                id: Some(id),
+                owner: None,
+                callbacks: read.clone(),
+            }));
+            self.dependencies.push((read, handle));
+        }
+    }
+}
+
 /// A callback function is no longer connected to its source.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct CallbackDisconnected;
@@ -1903,6 +2448,23 @@ impl InvalidationState {
         }
     }
 
+    fn profile(&self, value_type: &'static str, dynamic: usize) {
+        for (window, widget) in &self.widgets {
+            window.redraw_status.note_invalidation(Invalidation {
+                value_type,
+                dynamic,
+                widget: Some(*widget),
+            });
+        }
+        for (window, _) in self.windows.iter().filter(|(_, redraw)| **redraw) {
+            window.redraw_status.note_invalidation(Invalidation {
+                value_type,
+                dynamic,
+                widget: None,
+            });
+        }
+    }
+
     fn extend(&mut self, other: &mut InvalidationState) {
         self.widgets.extend(other.widgets.drain());
         self.windows.extend(other.windows.drain());
@@ -1926,6 +2488,7 @@ struct State<T> {
     invalidation: InvalidationState,
     on_disconnect: Option<Vec<OnceCallback>>,
     readers: usize,
+    originals: Vec<(usize, T)>,
 }
 
 impl<T> State<T> {
@@ -1944,12 +2507,20 @@ impl<T> State<T> {
             readers: 0,
             on_disconnect: Some(Vec::new()),
             source_callback: CallbackHandle::default(),
+            originals: Vec::new(),
         }
     }
 
     fn note_changed(&mut self) -> ChangeCallbacks {
         self.wrapped.generation = self.wrapped.generation.next();
+        self.note_unrestorable_change();
 
+        if profiler::attached() {
+            self.invalidation.profile(
+                std::any::type_name::<T>(),
+                std::ptr::from_ref(self) as usize,
+            );
+        }
         if !InvalidationBatch::take_invalidations(&mut self.invalidation) {
             self.invalidation.invoke();
         }
@@ -1960,6 +2531,60 @@ impl<T> State<T> {
         }
     }
 
+    /// Keeps `original` so that it can be restored if the current restoring
+    /// transaction fails. Returns `original` if it does not need to be kept.
+    fn record_original(&mut self, original: T) -> Option<T> {
+        let Some(frame) = RestoringFrame::current() else {
+            return Some(original);
+        };
+        if self.originals.iter().any(|(id, _)| *id == frame) {
+            return Some(original);
+        }
+
+        RestoringFrame::touch(&self.callbacks);
+        self.originals.push((frame, original));
+        None
+    }
+
+    fn note_unrestorable_change(&self) {
+        if let Some(frame) = RestoringFrame::current() {
+            if !self.originals.iter().any(|(id, _)| *id == frame) {
+                tracing::warn!(
+                    "{} was modified in place during a restoring transaction and will not be restored",
+                    std::any::type_name::<T>()
+                );
+            }
+        }
+    }
+
+    /// Applies the outcomes of the restoring transactions that have finished
+    /// since this value was last accessed.
+    fn apply_restorations(&mut self) {
+        for restoration in self.callbacks.take_restorations() {
+            match restoration {
+                Restoration::Restore(frame) => {
+                    if let Some(index) = self.originals.iter().position(|(id, _)| *id == frame) {
+                        let (_, original) = self.originals.remove(index);
+                        self.wrapped.value = original;
+                        self.wrapped.generation = self.wrapped.generation.next();
+                    }
+                }
+                Restoration::Keep { frame, parent } => {
+                    if let Some(index) = self.originals.iter().position(|(id, _)| *id == frame) {
+                        let (_, original) = self.originals.remove(index);
+                        // The enclosing restoring transaction keeps the
+                        // original unless it already recorded one.
+                        if let Some(parent) = parent
+                            .filter(|parent| !self.originals.iter().any(|(id, _)| id == parent))
+                        {
+                            self.originals.insert(index, (parent, original));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn debug(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result
     where
         T: Debug,
@@ -1984,6 +2609,10 @@ impl<T> Drop for State<T> {
         // Ensure any disconnections that didn't fire due to deadlocking still
         // are invoked.
         drop(self.cleanup());
+        // The value can no longer change, so the callbacks will never be
+        // invoked again. Releasing them allows observers, such as
+        // `ValueStream`, to detect that the value is gone.
+        drop(self.callbacks.take_callbacks());
     }
 }
 
@@ -2027,6 +2656,38 @@ struct ChangeCallbacksData {
     callbacks: Mutex<CallbacksList>,
     currently_executing: Mutex<ChangeCallbacksExecutor>,
     sync: Condvar,
+    restorations: Mutex<Vec<Restoration>>,
+    restorations_pending: AtomicBool,
+}
+
+impl ChangeCallbacksData {
+    fn push_restoration(&self, restoration: Restoration) {
+        self.restorations.lock().push(restoration);
+        self.restorations_pending.store(true, Ordering::Release);
+    }
+
+    fn take_restorations(&self) -> Vec<Restoration> {
+        if self.restorations_pending.load(Ordering::Relaxed)
+            && self.restorations_pending.swap(false, Ordering::Acquire)
+        {
+            std::mem::take(&mut *self.restorations.lock())
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn take_callbacks(&self) -> Option<Lots<Box<dyn ValueCallback>>> {
+        if self.currently_executing.lock().thread == Some(thread::current().id()) {
+            // The callbacks are executing on this thread, and releasing them
+            // would deadlock.
+            return None;
+        }
+
+        Some(std::mem::replace(
+            &mut self.callbacks.lock().callbacks,
+            Lots::new(),
+        ))
+    }
 }
 
 impl CallbackCollection for ChangeCallbacksData {
@@ -2063,6 +2724,10 @@ struct ChangeCallbacks {
 
 impl Drop for ChangeCallbacks {
     fn drop(&mut self) {
+        if Transaction::defer(self) {
+            return;
+        }
+
         let mut currently_executing = self.data.currently_executing.lock();
         let current_thread = thread::current().id();
         loop {
@@ -2083,9 +2748,11 @@ impl Drop for ChangeCallbacks {
                         state.invoked_at = Instant::now();
                         // Invoke all callbacks, removing those that report an
                         // error.
-                        state
-                            .callbacks
-                            .drain_filter(|callback| callback.changed().is_err());
+                        untracked(|| {
+                            state
+                                .callbacks
+                                .drain_filter(|callback| callback.changed().is_err());
+                        });
                     }
 
                     // Remove ourselves as the current executor, notifying any
@@ -2512,34 +3179,137 @@ impl<T> Drop for DynamicReader<T> {
     }
 }
 
-/// Suspends the current async task until the contained value has been
-/// updated or there are no remaining writers for the value.
+/// A [`Stream`] of values from a [`Source`].
 ///
-/// Yeilds true if a newly updated value was discovered.
-#[derive(Debug)]
-#[must_use = "futures must be .await'ed to be executed"]
-pub struct BlockUntilUpdatedFuture<'a, T>(&'a DynamicReader<T>);
-
-impl<'a, T> Future for BlockUntilUpdatedFuture<'a, T> {
-    type Output = bool;
-
-    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let mut state = self.0.source.state().expect("deadlocked");
-        if state.wrapped.generation != self.0.read_generation() {
-            return Poll::Ready(true);
-        } else if state.readers == Arc::strong_count(&self.0.source)
-            || state.on_disconnect.is_none()
-        {
-            return Poll::Ready(false);
+/// This type is returned from [`Source::to_stream`].
+#[must_use = "streams do nothing unless polled"]
+pub struct ValueStream<T> {
+    shared: Arc<Mutex<ValueStreamState<T>>>,
+    _handle: CallbackHandle,
+}
+
+struct ValueStreamState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    disconnected: bool,
+}
+
+impl<T> Stream for ValueStream<T> {
+    type Item = T;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut state = self.shared.lock();
+        if let Some(value) = state.value.take() {
+            Poll::Ready(Some(value))
+        } else if state.disconnected {
+            Poll::Ready(None)
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
         }
-
-        state.invalidation.wakers.push(cx.waker().clone());
-        Poll::Pending
     }
 }
 
-#[test]
-fn disconnecting_reader_from_dynamic() {
+impl<T> Debug for ValueStream<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueStream")
+            .field("pending", &self.shared.lock().value)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Delivers values to a [`ValueStream`], ending the stream when dropped.
+struct ValueStreamSender<T>(Arc<Mutex<ValueStreamState<T>>>);
+
+impl<T> ValueStreamSender<T> {
+    fn send(&self, value: T) {
+        let mut state = self.0.lock();
+        state.value = Some(value);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for ValueStreamSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.0.lock();
+        state.disconnected = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Tracks whether a background task driving a [`Dynamic`] should stop.
+#[derive(Default)]
+struct Cancellation {
+    cancelled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Cancellation {
+    /// Stores `waker` to be woken upon cancellation, returning true if
+    /// already cancelled.
+    fn register(&self, waker: &Waker) -> bool {
+        let mut stored = self.waker.lock();
+        if self.cancelled.load(Ordering::Acquire) {
+            return true;
+        }
+        *stored = Some(waker.clone());
+        false
+    }
+}
+
+struct CancelOnDrop(Arc<Cancellation>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        let waker = {
+            let mut waker = self.0.waker.lock();
+            self.0.cancelled.store(true, Ordering::Release);
+            waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Suspends the current async task until the contained value has been
+/// updated or there are no remaining writers for the value.
+///
+/// Yeilds true if a newly updated value was discovered.
+#[derive(Debug)]
+#[must_use = "futures must be .await'ed to be executed"]
+pub struct BlockUntilUpdatedFuture<'a, T>(&'a DynamicReader<T>);
+
+impl<'a, T> Future for BlockUntilUpdatedFuture<'a, T> {
+    type Output = bool;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.0.source.state().expect("deadlocked");
+        if state.wrapped.generation != self.0.read_generation() {
+            return Poll::Ready(true);
+        } else if state.readers == Arc::strong_count(&self.0.source)
+            || state.on_disconnect.is_none()
+        {
+            return Poll::Ready(false);
+        }
+
+        state.invalidation.wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[test]
+fn disconnecting_reader_from_dynamic() {
     let value = Dynamic::new(1);
     let ref_reader = value.create_reader();
     drop(value);
@@ -3239,8 +4009,22 @@ macro_rules! impl_tuple_for_each {
         // The list of tuple fields excluding the one being invoked.
         [$($rtype:ident $rfield:tt $rvar:ident),+]
     ) => {
+        let source = $var.clone();
         $handles += $var.for_each_subsequent((&$for_each, $(&$rvar,)+).with_clone(|(for_each, $($rvar,)+)| {
             move |$var: &$type| {
+                let deferred = Transaction::defer_grouped(&for_each, || {
+                    let for_each = for_each.clone();
+                    let $var = source.clone();
+                    $(let $rvar = $rvar.clone();)+
+                    Box::new(move || {
+                        $(let $avar = $avar.read();)+
+                        let mut for_each = for_each.lock();
+                        (for_each)(($(&$avar,)+));
+                    })
+                });
+                if deferred {
+                    return;
+                }
                 $(let $rvar = $rvar.read();)+
                 let mut for_each =
                     for_each.lock();
@@ -3412,8 +4196,23 @@ macro_rules! impl_tuple_for_each_cloned {
         // The list of tuple fields excluding the one being invoked.
         [$($rtype:ident $rfield:tt $rvar:ident),+]
     ) => {
+        let source = $var.clone();
         $handles += $var.for_each_cloned((&$for_each, $(&$rvar,)+).with_clone(|(for_each, $($rvar,)+)| {
             move |$var: $type| {
+                let deferred = Transaction::defer_grouped(&for_each, || {
+                    let for_each = for_each.clone();
+                    let $var = source.clone();
+                    $(let $rvar = $rvar.clone();)+
+                    Box::new(move || {
+                        $(let $avar = $avar.get();)+
+                        if let Some(mut for_each) = for_each.try_lock() {
+                            (for_each)(($($avar,)+));
+                        }
+                    })
+                });
+                if deferred {
+                    return;
+                }
                 $(let $rvar = $rvar.get();)+
                 if let Some(mut for_each) =
                     for_each.try_lock() {
@@ -3484,6 +4283,8 @@ pub enum Validation {
     Valid,
     /// The data is invalid. The string contains a human-readable message.
     Invalid(String),
+    /// The data is being validated asynchronously.
+    Pending,
 }
 
 impl Validation {
@@ -3494,7 +4295,7 @@ impl Validation {
     #[must_use]
     pub fn message<'a>(&'a self, hint: &'a str) -> &'a str {
         match self {
-            Validation::None | Validation::Valid => hint,
+            Validation::None | Validation::Valid | Validation::Pending => hint,
             Validation::Invalid(err) => err,
         }
     }
@@ -3505,6 +4306,12 @@ impl Validation {
         matches!(self, Self::Invalid(_))
     }
 
+    /// Returns true if the data is being validated asynchronously.
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending)
+    }
+
     /// Returns the result of merging both validations.
     #[must_use]
     pub fn and(&self, other: &Self) -> Self {
@@ -3513,6 +4320,7 @@ impl Validation {
             (Validation::Invalid(error), _) | (_, Validation::Invalid(error)) => {
                 Validation::Invalid(error.clone())
             }
+            (Validation::Pending, _) | (_, Validation::Pending) => Validation::Pending,
             (Validation::None, _) | (_, Validation::None) => Validation::None,
         }
     }
@@ -3536,6 +4344,7 @@ where
 pub struct Validations {
     state: Dynamic<ValidationsState>,
     invalid: Dynamic<usize>,
+    pending: Dynamic<usize>,
 }
 
 #[derive(Default, Debug, Eq, PartialEq, Clone)]
@@ -3547,6 +4356,17 @@ enum ValidationsState {
     Disabled,
 }
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum CheckResult {
+    Passed,
+    Failed(String),
+    Pending,
+}
+
+/// The delay [`Validations::validate_async`] waits for a value to stop changing
+/// before checking it.
+const ASYNC_VALIDATION_DELAY: Duration = Duration::from_millis(250);
+
 impl Validations {
     /// Validates `dynamic`'s contents using `check`, returning a dynamic
     /// containing the validation status.
@@ -3564,11 +4384,124 @@ impl Validations {
         Valid: for<'a> FnMut(&'a T) -> Result<(), E> + Send + 'static,
         E: Display,
     {
-        let validation = Dynamic::new(Validation::None);
         let mut message_mapping = Self::map_to_message(move |value| check(value));
         let error_message = dynamic.map_each_generational(move |gen| message_mapping(&gen.guard));
 
-        validation.set_source((&self.state, &error_message).for_each_cloned({
+        self.validation_for(dynamic, &error_message)
+    }
+
+    /// Validates `dynamic`'s contents using the future returned from `check`,
+    /// returning a dynamic containing the validation status.
+    ///
+    /// `check` is invoked once `dynamic` has stopped changing for a short
+    /// delay, and the returned future is executed using `app`'s runtime. While
+    /// the check is waiting or executing, the validation status is
+    /// [`Validation::Pending`]. Results from checks of values that have since
+    /// been changed are ignored.
+    ///
+    /// The validation is linked with `self` such that checking `self`'s
+    /// validation status will include this validation. A pending validation is
+    /// not considered valid.
+    #[must_use]
+    pub fn validate_async<T, E, Valid, Check>(
+        &self,
+        app: &impl Application,
+        dynamic: &Dynamic<T>,
+        check: Valid,
+    ) -> Dynamic<Validation>
+    where
+        T: Send + 'static,
+        Valid: for<'a> FnMut(&'a T) -> Check + Send + 'static,
+        Check: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.validate_async_with_delay(app, dynamic, ASYNC_VALIDATION_DELAY, check)
+    }
+
+    /// Validates `dynamic`'s contents using the future returned from `check`
+    /// once `dynamic` has not changed for `delay`, returning a dynamic
+    /// containing the validation status.
+    ///
+    /// See [`validate_async`](Self::validate_async) for more information.
+    #[must_use]
+    pub fn validate_async_with_delay<T, E, Valid, Check>(
+        &self,
+        app: &impl Application,
+        dynamic: &Dynamic<T>,
+        delay: Duration,
+        check: Valid,
+    ) -> Dynamic<Validation>
+    where
+        T: Send + 'static,
+        Valid: for<'a> FnMut(&'a T) -> Check + Send + 'static,
+        Check: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let error_message = Dynamic::new(GenerationalValue {
+            value: CheckResult::Pending,
+            generation: dynamic.generation(),
+        });
+        let check = Arc::new(Mutex::new(check));
+        let start_check = {
+            let cushy = app.cushy().clone();
+            let dynamic = dynamic.downgrade();
+            let error_message = error_message.clone();
+            move |generation: Generation| {
+                let check = check.clone();
+                let dynamic = dynamic.clone();
+                let error_message = error_message.clone();
+                cushy.spawn(async move {
+                    let Some(dynamic) = dynamic.upgrade() else {
+                        return;
+                    };
+                    let Some(pending) = dynamic.map_generational(|value| {
+                        (value.generation() == generation).then(|| check.lock()(&value))
+                    }) else {
+                        return;
+                    };
+                    let result = pending.await;
+                    if dynamic.generation() == generation {
+                        error_message.set(GenerationalValue {
+                            value: match result {
+                                Ok(()) => CheckResult::Passed,
+                                Err(err) => CheckResult::Failed(err.to_string()),
+                            },
+                            generation,
+                        });
+                    }
+                });
+            }
+        };
+
+        start_check(dynamic.generation());
+        let schedule_checks = dynamic.for_each_subsequent_generational({
+            let error_message = error_message.clone();
+            move |value| {
+                let generation = value.generation();
+                error_message.set(GenerationalValue {
+                    value: CheckResult::Pending,
+                    generation,
+                });
+                let start_check = start_check.clone();
+                delay.on_complete(move || start_check(generation)).launch();
+            }
+        });
+
+        let validation = self.validation_for(dynamic, &error_message);
+        validation.set_source(schedule_checks);
+        validation
+    }
+
+    fn validation_for<T>(
+        &self,
+        dynamic: &Dynamic<T>,
+        error_message: &Dynamic<GenerationalValue<CheckResult>>,
+    ) -> Dynamic<Validation>
+    where
+        T: Send + 'static,
+    {
+        let validation = Dynamic::new(Validation::None);
+        validation.set_source((&self.state, error_message).for_each_cloned({
             let mut f = self.generate_validation(dynamic);
             let validation = validation.clone();
 
@@ -3608,7 +4541,7 @@ impl Validations {
 
     fn map_to_message<T, E, Valid>(
         mut check: Valid,
-    ) -> impl for<'a> FnMut(&'a GenerationalValue<T>) -> GenerationalValue<Option<String>> + Send + 'static
+    ) -> impl for<'a> FnMut(&'a GenerationalValue<T>) -> GenerationalValue<CheckResult> + Send + 'static
     where
         T: Send + 'static,
         Valid: for<'a> FnMut(&'a T) -> Result<(), E> + Send + 'static,
@@ -3616,8 +4549,8 @@ impl Validations {
     {
         move |value| {
             value.map_ref(|value| match check(value) {
-                Ok(()) => None,
-                Err(err) => Some(err.to_string()),
+                Ok(()) => CheckResult::Passed,
+                Err(err) => CheckResult::Failed(err.to_string()),
             })
         }
     }
@@ -3625,21 +4558,23 @@ impl Validations {
     fn generate_validation<T>(
         &self,
         dynamic: &Dynamic<T>,
-    ) -> impl FnMut(ValidationsState, GenerationalValue<Option<String>>) -> Validation
+    ) -> impl FnMut(ValidationsState, GenerationalValue<CheckResult>) -> Validation
     where
         T: Send + 'static,
     {
         self.invalid.map_mut(|mut invalid| *invalid += 1);
 
         let invalid_count = self.invalid.clone();
+        let pending_count = self.pending.clone();
         let dynamic = dynamic.clone();
         let mut initial_generation = dynamic.generation();
         let mut invalid = true;
+        let mut pending = false;
 
         move |current_state, generational| {
             let new_invalid = match (&current_state, &generational.value) {
-                (ValidationsState::Disabled, _) | (_, None) => false,
-                (_, Some(_)) => true,
+                (ValidationsState::Disabled, _) | (_, CheckResult::Passed) => false,
+                (_, CheckResult::Failed(_) | CheckResult::Pending) => true,
             };
             if invalid != new_invalid {
                 if new_invalid {
@@ -3649,10 +4584,20 @@ impl Validations {
                 }
                 invalid = new_invalid;
             }
-            let new_status = if let Some(err) = generational.value {
-                Validation::Invalid(err.to_string())
-            } else {
-                Validation::Valid
+            let new_pending = current_state != ValidationsState::Disabled
+                && generational.value == CheckResult::Pending;
+            if pending != new_pending {
+                if new_pending {
+                    pending_count.map_mut(|mut pending| *pending += 1);
+                } else {
+                    pending_count.map_mut(|mut pending| *pending -= 1);
+                }
+                pending = new_pending;
+            }
+            let new_status = match generational.value {
+                CheckResult::Passed => Validation::Valid,
+                CheckResult::Failed(err) => Validation::Invalid(err),
+                CheckResult::Pending => Validation::Pending,
             };
             match current_state {
                 ValidationsState::Resetting => {
@@ -3688,11 +4633,39 @@ impl Validations {
     }
 
     /// Returns true if this set of validations are all valid.
+    ///
+    /// Validations that are still [pending](Validation::Pending) are not
+    /// considered valid. To wait for pending validations to complete, use
+    /// [`wait_for_pending`](Self::wait_for_pending).
     #[must_use]
     pub fn is_valid(&self) -> bool {
         self.invoke_callback((), &mut |()| true)
     }
 
+    /// Returns true if any validations are still [pending](Validation::Pending).
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.pending.get() > 0
+    }
+
+    /// Returns a future that waits for all [pending](Validation::Pending)
+    /// validations to complete before returning the result of
+    /// [`is_valid`](Self::is_valid).
+    pub fn wait_for_pending(&self) -> impl Future<Output = bool> + Send + 'static {
+        let validations = self.clone();
+        let mut pending = self.pending.to_stream();
+        async move {
+            while let Some(count) =
+                std::future::poll_fn(|cx| std::pin::Pin::new(&mut pending).poll_next(cx)).await
+            {
+                if count == 0 {
+                    break;
+                }
+            }
+            validations.is_valid()
+        }
+    }
+
     fn invoke_callback<T, R, F>(&self, t: T, handler: &mut F) -> R
     where
         F: FnMut(T) -> R + Send + 'static,
@@ -3714,7 +4687,8 @@ impl Validations {
     /// The returned function can be use in a
     /// [`Callback`](crate::widget::Callback).
     ///
-    /// When the contents are invalid, `R::default()` is returned.
+    /// When the contents are invalid or any validations are still
+    /// [pending](Validation::Pending), `R::default()` is returned.
     pub fn when_valid<T, R, F>(self, mut handler: F) -> impl FnMut(T) -> R + Send + 'static
     where
         F: FnMut(T) -> R + Send + 'static,
@@ -3866,6 +4840,160 @@ where
     }
 }
 
+/// The edges of a [throttled](Source::throttled) period that store updated
+/// values.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum ThrottleEdges {
+    /// The first update in each period is stored immediately. All other
+    /// updates during the period are ignored.
+    Leading,
+    /// The last update in each period is stored when the period ends.
+    Trailing,
+    /// The first update in each period is stored immediately, and the last
+    /// update during the period is stored when the period ends.
+    #[default]
+    Both,
+}
+
+impl ThrottleEdges {
+    const fn leading(self) -> bool {
+        matches!(self, Self::Leading | Self::Both)
+    }
+
+    const fn trailing(self) -> bool {
+        matches!(self, Self::Trailing | Self::Both)
+    }
+}
+
+struct Throttle<T>(Arc<Mutex<ThrottleState<T>>>);
+
+struct ThrottleState<T> {
+    destination: WeakDynamic<T>,
+    period: Duration,
+    edges: ThrottleEdges,
+    period_start: Option<Instant>,
+    pending: Option<T>,
+    scheduled: bool,
+}
+
+impl<T> Throttle<T>
+where
+    T: Clone + PartialEq + Send + 'static,
+{
+    fn new(destination: &Dynamic<T>, period: Duration, edges: ThrottleEdges) -> Self {
+        Self(Arc::new(Mutex::new(ThrottleState {
+            destination: destination.downgrade(),
+            period,
+            edges,
+            period_start: None,
+            pending: None,
+            scheduled: false,
+        })))
+    }
+
+    fn update(&self, value: T) {
+        let mut store = None;
+        let mut schedule_in = None;
+        let mut state = self.0.lock();
+        let now = clock::now();
+        let elapsed = state
+            .period_start
+            .map(|start| now.saturating_duration_since(start));
+        match elapsed {
+            Some(elapsed) if elapsed < state.period => {
+                if state.edges.trailing() {
+                    state.pending = Some(value);
+                    if !state.scheduled {
+                        state.scheduled = true;
+                        schedule_in = Some(state.period - elapsed);
+                    }
+                }
+            }
+            _ => {
+                state.period_start = Some(now);
+                if state.edges.leading() {
+                    state.pending = None;
+                    store = Some(value);
+                } else {
+                    state.pending = Some(value);
+                    state.scheduled = true;
+                    schedule_in = Some(state.period);
+                }
+            }
+        }
+        let destination = state.destination.clone();
+        // The timer must be launched without holding the lock, as the
+        // completion callback acquires it while the animation system is locked.
+        drop(state);
+
+        if let (Some(value), Some(destination)) = (store, destination.upgrade()) {
+            destination.set(value);
+        }
+        if let Some(delay) = schedule_in {
+            let state = self.0.clone();
+            delay
+                .on_complete(move || {
+                    let mut state = state.lock();
+                    state.scheduled = false;
+                    if let Some(value) = state.pending.take() {
+                        // Storing the trailing value begins a new period.
+                        state.period_start = Some(clock::now());
+                        let destination = state.destination.clone();
+                        drop(state);
+                        if let Some(destination) = destination.upgrade() {
+                            destination.set(value);
+                        }
+                    }
+                })
+                .launch();
+        }
+    }
+}
+
+/// Returns a dynamic that contains the current value of each source in
+/// `sources`.
+///
+/// Each time any source is updated, the value at the source's index in the
+/// returned dynamic is updated. Unlike the tuple implementations of
+/// [`MapEach`] and [`ForEach`], any number of sources can be combined.
+///
+/// ```rust
+/// use cushy::value::{combine_latest, Destination, Dynamic, Source};
+///
+/// let sources = [Dynamic::new(1), Dynamic::new(2), Dynamic::new(3)];
+/// let combined = combine_latest(&sources);
+/// sources[1].set(4);
+/// assert_eq!(combined.get(), vec![1, 4, 3]);
+/// ```
+pub fn combine_latest<'a, T, S>(sources: impl IntoIterator<Item = &'a S>) -> Dynamic<Vec<T>>
+where
+    S: Source<T> + 'a,
+    T: Clone + PartialEq + Send + 'static,
+{
+    let sources = sources.into_iter().collect::<Vec<_>>();
+    let combined = Dynamic::new(
+        sources
+            .iter()
+            .map(|source| source.get())
+            .collect::<Vec<_>>(),
+    );
+    let mut handles = CallbackHandle::default();
+    for (index, source) in sources.into_iter().enumerate() {
+        let combined_weak = combined.downgrade();
+        handles += source.for_each_subsequent_try(move |value| {
+            let combined = combined_weak.upgrade().ok_or(CallbackDisconnected)?;
+            combined.map_mut(|mut values| {
+                if values[index] != *value {
+                    values[index] = value.clone();
+                }
+            });
+            Ok(())
+        });
+    }
+    combined.set_source(handles);
+    combined
+}
+
 /// A batch of invalidations across one or more windows.
 ///
 /// This type helps background tasks synchronize when to invalidate or redraw a
@@ -3943,64 +5071,418 @@ impl InvalidationBatch<'_> {
     }
 }
 
-/// Watches one or more [`Source`]s and invokes associated callbacks when
-/// changed.
+/// Executes `transaction`, deferring all change callbacks until it returns.
 ///
-/// This type is useful when needing to ensure logic is executed or a value is
-/// regenerated each time one of many sources are changed.
-#[derive(Debug, Clone, Default)]
-pub struct Watcher(Dynamic<usize>);
+/// This is equivalent to `Transaction::new().run(transaction)`. See
+/// [`Transaction`] for more information.
+pub fn transaction<R>(transaction: impl FnOnce() -> R) -> R {
+    Transaction::new().run(transaction)
+}
 
-impl Watcher {
-    /// Ensures all callbacks attached to this watcher are invoked when `other`
-    /// is changed.
-    pub fn watch<T>(&self, other: &impl Source<T>)
-    where
-        T: Send + 'static,
-    {
-        let counter = self.0.clone();
-        self.0
-            .set_source(other.for_each_subsequent_generational(move |guard| {
-                // We want to drop our guard before changing the counter to
-                // ensure all callbacks associated with our counter are executed
-                // without this type holding any source locks.
-                drop(guard);
-                let mut counter = counter.lock();
-                *counter = counter.wrapping_add(1);
-            }));
+/// A group of changes to [`Dynamic`]s that notify their observers together.
+///
+/// While a transaction is executing, change callbacks for every [`Dynamic`]
+/// updated on the current thread are deferred. When the transaction completes,
+/// each affected value's callbacks are invoked once, observing the final
+/// values. Callbacks that observe multiple values, such as those installed
+/// using [`ForEach`] on a tuple of sources, are also only invoked once. Window
+/// invalidations are also gathered into a single [`InvalidationBatch`].
+///
+/// Values are still updated immediately, so reading a value during the
+/// transaction, or from another thread, observes the updated contents.
+///
+/// Transactions do not roll back changes automatically. When an error is
+/// returned, [`Transaction::try_run`] only restores the values that were
+/// registered using [`Transaction::restoring`], or every value stored using
+/// [`Destination::set`] when [`Transaction::restoring_all`] is used. All other
+/// changes made during the transaction are kept:
+///
+/// ```rust
+/// use cushy::value::{Destination, Dynamic, Source, Transaction};
+///
+/// let a = Dynamic::new(1);
+/// let b = Dynamic::new(2);
+///
+/// let result: Result<(), &str> = Transaction::new().restoring(&a).try_run(|| {
+///     a.set(3);
+///     b.set(4);
+///     Err("invalid")
+/// });
+/// assert!(result.is_err());
+/// assert_eq!(a.get(), 1);
+/// // `b` was not registered, so its change was kept.
+/// assert_eq!(b.get(), 4);
+/// ```
+///
+/// Transactions can be nested. Nested transactions restore their own values
+/// when they fail, but change callbacks are deferred until the outermost
+/// transaction completes.
+#[derive(Default)]
+#[must_use]
+pub struct Transaction {
+    restorable: Vec<Box<dyn FnOnce() -> Box<dyn FnOnce()>>>,
+    restore_all: bool,
+}
+
+thread_local! {
+    static TRANSACTION_CALLBACKS: RefCell<Option<Vec<ChangeCallbacks>>> =
+        const { RefCell::new(None) };
+    static GROUPED_CALLBACKS: RefCell<Option<Vec<GroupedCallback>>> =
+        const { RefCell::new(None) };
+    static RESTORING_FRAMES: RefCell<Vec<RestoringFrameData>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// A callback shared between multiple sources that is invoked once after a
+/// transaction's deferred callbacks have been invoked.
+struct GroupedCallback {
+    group: *const (),
+    invoke: Box<dyn FnOnce()>,
+}
+
+impl Transaction {
+    /// Returns a new transaction.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    /// Returns a new dynamic populated by invoking `when_changed` each time any
-    /// watched source is updated.
-    pub fn map_changed<F, T>(&self, mut when_changed: F) -> Dynamic<T>
+    /// Restores `dynamic` to the value it contained when this transaction
+    /// began if the transaction fails.
+    pub fn restoring<T>(mut self, dynamic: &Dynamic<T>) -> Self
     where
-        F: FnMut() -> T + Send + 'static,
-        T: PartialEq + Send + 'static,
+        T: Clone + 'static,
     {
-        self.0.map_each(move |_| when_changed())
+        let dynamic = dynamic.clone();
+        self.restorable.push(Box::new(move || {
+            let (original, generation) =
+                dynamic.map_generational(|value| (value.clone(), value.generation()));
+            Box::new(move || {
+                if dynamic.generation() != generation {
+                    dynamic.map_mut(|mut value| *value = original);
+                }
+            })
+        }));
+        self
     }
 
-    /// Invokes `when_changed` each time any watched source is updated.
-    pub fn when_changed<F>(&self, mut when_changed: F) -> CallbackHandle
-    where
-        F: FnMut() + Send + 'static,
-    {
-        self.0.for_each(move |_| when_changed())
+    /// Restores every [`Dynamic`] stored using [`Destination::set`] during
+    /// this transaction to its original value if the transaction fails.
+    ///
+    /// The value a [`Dynamic`] contained before its first `set` is kept until
+    /// the transaction completes, so no [`Clone`] implementation is required.
+    /// Changes made in place, such as through [`Destination::map_mut`], cannot
+    /// be restored this way and are kept. A warning is logged for each of
+    /// these changes. Values that are modified in place can still be restored
+    /// by registering them using [`Self::restoring`].
+    ///
+    /// ```rust
+    /// use cushy::value::{Destination, Dynamic, Source, Transaction};
+    ///
+    /// let a = Dynamic::new(1);
+    /// let b = Dynamic::new(String::from("b"));
+    ///
+    /// let result: Result<(), &str> = Transaction::new().restoring_all().try_run(|| {
+    ///     a.set(2);
+    ///     b.set(String::from("c"));
+    ///     Err("invalid")
+    /// });
+    /// assert!(result.is_err());
+    /// assert_eq!(a.get(), 1);
+    /// assert_eq!(b.get(), "b");
+    /// ```
+    pub fn restoring_all(mut self) -> Self {
+        self.restore_all = true;
+        self
     }
-}
 
-/// A value that has its read and updated states tracked.
-pub struct Tracked<Source>
-where
-    Source: TrackedSource,
-{
-    current: Source::Value,
-    source: Source,
-    current_generation: Generation,
-    unread: bool,
-}
+    /// Executes `transaction`, deferring all change callbacks until it
+    /// returns.
+    pub fn run<R>(self, transaction: impl FnOnce() -> R) -> R {
+        self.execute(transaction, |_| false)
+    }
 
-impl<Source> Tracked<Source>
+    /// Executes `transaction`, deferring all change callbacks until it
+    /// returns.
+    ///
+    /// If `transaction` returns an error, all values registered with
+    /// [`Self::restoring`], or stored using [`Destination::set`] when
+    /// [`Self::restoring_all`] is used, are restored to their original
+    /// contents before the error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by `transaction`.
+    pub fn try_run<R, E>(self, transaction: impl FnOnce() -> Result<R, E>) -> Result<R, E> {
+        self.execute(transaction, Result::is_err)
+    }
+
+    fn execute<R>(
+        self,
+        transaction: impl FnOnce() -> R,
+        should_restore: impl FnOnce(&R) -> bool,
+    ) -> R {
+        let restore = self
+            .restorable
+            .into_iter()
+            .map(|snapshot| snapshot())
+            .collect::<Vec<_>>();
+        let mut result = None;
+        InvalidationBatch::batch(|_| {
+            let _commit = TransactionCommit::begin();
+            let mut frame = self.restore_all.then(RestoringFrame::begin);
+            let transaction_result = transaction();
+            if should_restore(&transaction_result) {
+                if let Some(frame) = &mut frame {
+                    frame.restore = true;
+                }
+                drop(frame);
+                for restore in restore {
+                    restore();
+                }
+            } else {
+                drop(frame);
+            }
+            result = Some(transaction_result);
+        });
+        result.expect("transaction always executes")
+    }
+
+    /// Defers `callbacks` if a transaction is active on the current thread.
+    ///
+    /// Returns true if the callbacks were deferred.
+    fn defer(callbacks: &ChangeCallbacks) -> bool {
+        TRANSACTION_CALLBACKS.with_borrow_mut(|pending| {
+            let Some(pending) = pending else {
+                return false;
+            };
+
+            if let Some(existing) = pending
+                .iter_mut()
+                .find(|existing| Arc::ptr_eq(&existing.data, &callbacks.data))
+            {
+                existing.changed_at = existing.changed_at.max(callbacks.changed_at);
+            } else {
+                pending.push(ChangeCallbacks {
+                    data: callbacks.data.clone(),
+                    changed_at: callbacks.changed_at,
+                });
+            }
+            true
+        })
+    }
+
+    /// Defers invoking a callback shared by multiple sources while the
+    /// callbacks deferred by a transaction are being invoked on the current
+    /// thread.
+    ///
+    /// Callbacks are identified by `group`, and `invoke` is only called for
+    /// the first deferral of each group. Returns true if the callback was
+    /// deferred.
+    fn defer_grouped<T>(group: &Arc<T>, invoke: impl FnOnce() -> Box<dyn FnOnce()>) -> bool {
+        GROUPED_CALLBACKS.with_borrow_mut(|pending| {
+            let Some(pending) = pending else {
+                return false;
+            };
+
+            let group = Arc::as_ptr(group).cast::<()>();
+            if !pending.iter().any(|existing| existing.group == group) {
+                pending.push(GroupedCallback {
+                    group,
+                    invoke: invoke(),
+                });
+            }
+            true
+        })
+    }
+}
+
+/// The outcome of a restoring transaction for a single [`Dynamic`], applied
+/// the next time the value is accessed.
+enum Restoration {
+    /// The original value recorded by `frame` should be restored.
+    Restore(usize),
+    /// The original value recorded by `frame` is no longer needed, unless an
+    /// enclosing restoring transaction still needs it.
+    Keep { frame: usize, parent: Option<usize> },
+}
+
+struct RestoringFrameData {
+    id: usize,
+    touched: Vec<Arc<ChangeCallbacksData>>,
+}
+
+/// Tracks the values stored during a transaction using
+/// [`Transaction::restoring_all`], resolving them when dropped.
+struct RestoringFrame {
+    restore: bool,
+}
+
+impl RestoringFrame {
+    fn begin() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        RESTORING_FRAMES.with_borrow_mut(|frames| {
+            frames.push(RestoringFrameData {
+                id,
+                touched: Vec::new(),
+            });
+        });
+        Self { restore: false }
+    }
+
+    fn current() -> Option<usize> {
+        RESTORING_FRAMES.with_borrow(|frames| frames.last().map(|frame| frame.id))
+    }
+
+    fn touch(data: &Arc<ChangeCallbacksData>) {
+        RESTORING_FRAMES.with_borrow_mut(|frames| {
+            if let Some(frame) = frames.last_mut() {
+                if !frame
+                    .touched
+                    .iter()
+                    .any(|touched| Arc::ptr_eq(touched, data))
+                {
+                    frame.touched.push(data.clone());
+                }
+            }
+        });
+    }
+}
+
+impl Drop for RestoringFrame {
+    fn drop(&mut self) {
+        RESTORING_FRAMES.with_borrow_mut(|frames| {
+            let Some(frame) = frames.pop() else {
+                return;
+            };
+            let mut parent = frames.last_mut();
+            for data in frame.touched {
+                if self.restore {
+                    data.push_restoration(Restoration::Restore(frame.id));
+                } else {
+                    data.push_restoration(Restoration::Keep {
+                        frame: frame.id,
+                        parent: parent.as_ref().map(|parent| parent.id),
+                    });
+                    if let Some(parent) = &mut parent {
+                        if !parent
+                            .touched
+                            .iter()
+                            .any(|touched| Arc::ptr_eq(touched, &data))
+                        {
+                            parent.touched.push(data);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Invokes all deferred change callbacks when the outermost transaction
+/// completes, even if the transaction panics.
+struct TransactionCommit {
+    outermost: bool,
+}
+
+impl TransactionCommit {
+    fn begin() -> Self {
+        let outermost = TRANSACTION_CALLBACKS.with_borrow_mut(|pending| {
+            if pending.is_some() {
+                false
+            } else {
+                *pending = Some(Vec::new());
+                true
+            }
+        });
+        Self { outermost }
+    }
+}
+
+impl Drop for TransactionCommit {
+    fn drop(&mut self) {
+        if self.outermost {
+            // Callbacks shared between multiple sources are gathered while the
+            // deferred callbacks are invoked so that they are only invoked
+            // once.
+            let grouping = GROUPED_CALLBACKS.with_borrow_mut(|grouped| {
+                if grouped.is_some() {
+                    false
+                } else {
+                    *grouped = Some(Vec::new());
+                    true
+                }
+            });
+            // Dropping each set of callbacks after the transaction is no longer
+            // active invokes them.
+            drop(TRANSACTION_CALLBACKS.take());
+            if grouping {
+                for grouped in GROUPED_CALLBACKS.take().into_iter().flatten() {
+                    (grouped.invoke)();
+                }
+            }
+        }
+    }
+}
+
+/// Watches one or more [`Source`]s and invokes associated callbacks when
+/// changed.
+///
+/// This type is useful when needing to ensure logic is executed or a value is
+/// regenerated each time one of many sources are changed.
+#[derive(Debug, Clone, Default)]
+pub struct Watcher(Dynamic<usize>);
+
+impl Watcher {
+    /// Ensures all callbacks attached to this watcher are invoked when `other`
+    /// is changed.
+    pub fn watch<T>(&self, other: &impl Source<T>)
+    where
+        T: Send + 'static,
+    {
+        let counter = self.0.clone();
+        self.0
+            .set_source(other.for_each_subsequent_generational(move |guard| {
+                // We want to drop our guard before changing the counter to
+                // ensure all callbacks associated with our counter are executed
+                // without this type holding any source locks.
+                drop(guard);
+                let mut counter = counter.lock();
+                *counter = counter.wrapping_add(1);
+            }));
+    }
+
+    /// Returns a new dynamic populated by invoking `when_changed` each time any
+    /// watched source is updated.
+    pub fn map_changed<F, T>(&self, mut when_changed: F) -> Dynamic<T>
+    where
+        F: FnMut() -> T + Send + 'static,
+        T: PartialEq + Send + 'static,
+    {
+        self.0.map_each(move |_| when_changed())
+    }
+
+    /// Invokes `when_changed` each time any watched source is updated.
+    pub fn when_changed<F>(&self, mut when_changed: F) -> CallbackHandle
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.0.for_each(move |_| when_changed())
+    }
+}
+
+/// A value that has its read and updated states tracked.
+pub struct Tracked<Source>
+where
+    Source: TrackedSource,
+{
+    current: Source::Value,
+    source: Source,
+    current_generation: Generation,
+    unread: bool,
+}
+
+impl<Source> Tracked<Source>
 where
     Source: TrackedSource,
 {
@@ -4243,83 +5725,924 @@ where
     }
 }
 
-#[test]
-fn map_cycle_is_finite() {
-    crate::initialize_tracing();
-    let a = Dynamic::new(0_usize);
+/// A change made to the contents of a [`DynamicVec`].
+///
+/// Indices refer to the collection as it existed immediately after the change
+/// was applied.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum VecChange {
+    /// A value was inserted at this index.
+    Insert(usize),
+    /// The value at this index was removed.
+    Remove(usize),
+    /// The value at `from` was removed and reinserted at `to`.
+    Move {
+        /// The index the value was removed from.
+        from: usize,
+        /// The index the value was inserted at after being removed.
+        to: usize,
+    },
+    /// The value at this index was replaced or updated in place.
+    Update(usize),
+    /// The entire contents of the collection were replaced.
+    Reset,
+}
+
+/// A change made to the contents of a [`DynamicMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MapChange<K> {
+    /// A value was inserted for a key that was not previously present.
+    Insert(K),
+    /// The value for this key was removed.
+    Remove(K),
+    /// The value for this key was replaced or updated in place.
+    Update(K),
+    /// The entire contents of the collection were replaced.
+    Reset,
+}
+
+/// Queues of changes for each observer of a collection.
+///
+/// Changes are published while the collection's lock is held, ensuring that
+/// observers always receive the changes that correspond to the contents they
+/// are given.
+struct ChangeQueues<C>(Arc<Mutex<Vec<Weak<Mutex<Vec<C>>>>>>);
 
-    // This callback updates a each time a is updated with a + 1, causing an
-    // infinite cycle if not broken by Cushy.
-    a.for_each_cloned({
-        let a = a.clone();
-        move |current| {
-            a.set(current + 1);
+impl<C> ChangeQueues<C>
+where
+    C: Clone,
+{
+    fn subscribe(&self) -> Arc<Mutex<Vec<C>>> {
+        let queue = Arc::new(Mutex::new(Vec::new()));
+        self.0.lock().push(Arc::downgrade(&queue));
+        queue
+    }
+
+    fn publish(&self, changes: &[C]) {
+        if changes.is_empty() {
+            return;
         }
-    })
-    .persist();
 
-    // Cushy will invoke the callback for the first set call, but the set call
-    // within the callback will not cause the callback to be invoked again.
-    // Thus, we expect setting the value to 1 to result in `a` containing 2.
-    a.set(1);
-    assert_eq!(a.get(), 2);
+        self.0.lock().retain(|queue| {
+            if let Some(queue) = queue.upgrade() {
+                queue.lock().extend_from_slice(changes);
+                true
+            } else {
+                false
+            }
+        });
+    }
 }
 
-#[test]
-fn compare_swap() {
-    let dynamic = Dynamic::new(1);
-    assert_eq!(dynamic.compare_swap(&1, 2), Ok(1));
-    assert_eq!(dynamic.compare_swap(&1, 0), Err(2));
-    assert_eq!(dynamic.compare_swap(&2, 0), Ok(2));
-    assert_eq!(dynamic.get(), 0);
+impl<C> Clone for ChangeQueues<C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
 }
 
-#[test]
-fn ref_counts() {
-    let dynamic = Dynamic::new(1);
-    assert_eq!(dynamic.instances(), 1);
-
-    let second = dynamic.clone();
-    assert_eq!(dynamic.instances(), 2);
-
-    assert_eq!(dynamic.readers(), 0);
-    let reader = second.into_reader();
-    assert_eq!(dynamic.instances(), 1);
-    assert_eq!(dynamic.readers(), 1);
+impl<C> Default for ChangeQueues<C> {
+    fn default() -> Self {
+        Self(Arc::default())
+    }
+}
 
-    // Test that once the last instance is dropped that the reader is no longer
-    // connected and that on_disconnect gets invoked.
-    assert!(reader.connected());
-    let invoked = Dynamic::new(false);
-    reader.on_disconnect({
-        let invoked = invoked.clone();
-        move || {
-            invoked.set(true);
+/// A reactive [`Vec`] that reports each individual change made to it.
+///
+/// A [`Dynamic<Vec<T>>`] can only report that its contents have changed. This
+/// type instead records a [`VecChange`] for every insert, removal, move, and
+/// update, allowing observers to react to only the affected items using
+/// [`for_each_change`](Self::for_each_change) or
+/// [`map_each_item`](Self::map_each_item).
+///
+/// All mutations must go through this type's functions, which is why it does
+/// not implement [`Destination`]. It does implement [`Source`], so it can be
+/// used anywhere a `Source<Vec<T>>` is accepted.
+///
+/// ```rust
+/// use cushy::value::DynamicVec;
+/// use cushy::widgets::Stack;
+///
+/// let names = DynamicVec::new(vec![String::from("Alice")]);
+/// let rows = Stack::rows(names.map_each_item(|name| name.clone()));
+/// // Only a widget for "Bob" is created by this push.
+/// names.push(String::from("Bob"));
+/// ```
+pub struct DynamicVec<T> {
+    items: Dynamic<Vec<T>>,
+    queues: ChangeQueues<VecChange>,
+}
+
+impl<T> DynamicVec<T> {
+    /// Returns a new collection containing `items`.
+    #[must_use]
+    pub fn new(items: Vec<T>) -> Self {
+        Self {
+            items: Dynamic::new(items),
+            queues: ChangeQueues::default(),
         }
-    });
-    drop(dynamic);
+    }
 
-    assert!(invoked.get());
-    assert!(!reader.connected());
-}
+    fn modify<R>(
+        &self,
+        modify: impl FnOnce(&mut Mutable<'_, Vec<T>>, &mut Vec<VecChange>) -> R,
+    ) -> R {
+        self.items.map_mut(|mut items| {
+            let mut changes = Vec::new();
+            let result = modify(&mut items, &mut changes);
+            self.queues.publish(&changes);
+            result
+        })
+    }
 
-#[test]
-fn linked_short_circuit() {
-    let usize = Dynamic::new(0_usize);
-    let string = usize.linked_string();
+    /// Returns the number of items in this collection.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this value is already locked by the current
+    /// thread.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.map_ref(Vec::len)
+    }
 
-    string.map_ref(|s| assert_eq!(s, "0"));
-    string.set(String::from("1"));
-    assert_eq!(usize.get(), 1);
-    usize.set(2);
-    string.map_ref(|s| assert_eq!(s, "2"));
-}
+    /// Returns true if this collection contains no items.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this value is already locked by the current
+    /// thread.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.map_ref(Vec::is_empty)
+    }
 
-#[test]
-fn graph_shortcircuit() {
-    let a = Dynamic::new(0_usize);
-    let doubled = a.map_each_cloned(|a| a * 2);
-    let quadrupled = doubled.map_each_cloned(|a| a * 2);
+    /// Pushes `value` to the end of this collection.
+    pub fn push(&self, value: T) {
+        self.modify(|items, changes| {
+            changes.push(VecChange::Insert(items.len()));
+            items.push(value);
+        });
+    }
+
+    /// Inserts `value` at `index`, shifting all items after it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is greater than the collection's
+    /// length.
+    pub fn insert(&self, index: usize, value: T) {
+        self.modify(|items, changes| {
+            items.insert(index, value);
+            changes.push(VecChange::Insert(index));
+        });
+    }
+
+    /// Appends each value in `values` to the end of this collection.
+    pub fn extend(&self, values: impl IntoIterator<Item = T>) {
+        self.modify(|items, changes| {
+            let mut values = values.into_iter().peekable();
+            if values.peek().is_some() {
+                let start = items.len();
+                items.extend(values);
+                changes.extend((start..items.len()).map(VecChange::Insert));
+            }
+        });
+    }
+
+    /// Removes and returns the item at `index`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        self.modify(|items, changes| {
+            let removed = items.remove(index);
+            changes.push(VecChange::Remove(index));
+            removed
+        })
+    }
+
+    /// Removes and returns the last item, if the collection is not empty.
+    pub fn pop(&self) -> Option<T> {
+        self.modify(|items, changes| {
+            if items.is_empty() {
+                return None;
+            }
+            let removed = items.pop();
+            changes.push(VecChange::Remove(items.len()));
+            removed
+        })
+    }
+
+    /// Replaces the item at `index` with `value`, returning the previous
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is out of bounds.
+    pub fn replace_item(&self, index: usize, value: T) -> T {
+        self.modify(|items, changes| {
+            let previous = std::mem::replace(&mut items[index], value);
+            changes.push(VecChange::Update(index));
+            previous
+        })
+    }
+
+    /// Invokes `update` with exclusive access to the item at `index`, and
+    /// reports the item as updated.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is out of bounds.
+    pub fn update<R>(&self, index: usize, update: impl FnOnce(&mut T) -> R) -> R {
+        self.modify(|items, changes| {
+            let result = update(&mut items[index]);
+            changes.push(VecChange::Update(index));
+            result
+        })
+    }
+
+    /// Moves the item at `from` so that it is located at `to`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if either index is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.modify(|items, changes| {
+            assert!(
+                from < items.len() && to < items.len(),
+                "index out of bounds"
+            );
+            if from != to {
+                let item = items.remove(from);
+                items.insert(to, item);
+                changes.push(VecChange::Move { from, to });
+            }
+        });
+    }
+
+    /// Swaps the items at `a` and `b`.
+    ///
+    /// This change is reported as two [`VecChange::Move`]s.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if either index is out of bounds.
+    pub fn swap(&self, a: usize, b: usize) {
+        self.modify(|items, changes| {
+            assert!(a < items.len() && b < items.len(), "index out of bounds");
+            if a != b {
+                let (low, high) = if a < b { (a, b) } else { (b, a) };
+                items.swap(low, high);
+                changes.push(VecChange::Move {
+                    from: low,
+                    to: high,
+                });
+                changes.push(VecChange::Move {
+                    from: high - 1,
+                    to: low,
+                });
+            }
+        });
+    }
+
+    /// Removes all items from this collection.
+    pub fn clear(&self) {
+        self.modify(|items, changes| {
+            if !items.is_empty() {
+                items.clear();
+                changes.push(VecChange::Reset);
+            }
+        });
+    }
+
+    /// Replaces the entire contents of this collection with `new_items`,
+    /// returning the previous contents.
+    pub fn replace_all(&self, new_items: Vec<T>) -> Vec<T> {
+        self.modify(|items, changes| {
+            changes.push(VecChange::Reset);
+            std::mem::replace(&mut **items, new_items)
+        })
+    }
+
+    /// Invokes `on_change` each time this collection is changed, providing
+    /// the updated contents and the changes that were made since the last
+    /// invocation.
+    pub fn for_each_change<F>(&self, mut on_change: F) -> CallbackHandle
+    where
+        T: Send + 'static,
+        F: for<'a> FnMut(&'a Vec<T>, &'a [VecChange]) + Send + 'static,
+    {
+        let queue = self.queues.subscribe();
+        self.items.for_each_subsequent(move |items| {
+            let changes = std::mem::take(&mut *queue.lock());
+            if !changes.is_empty() {
+                on_change(items, &changes);
+            }
+        })
+    }
+
+    /// Returns a [`WidgetList`] containing the result of invoking `map` for
+    /// each item in this collection.
+    ///
+    /// As this collection changes, `map` is only invoked for inserted and
+    /// updated items. Widgets for items that were not changed are reused, and
+    /// each change is recorded in the list. This allows
+    /// [`Stack`](crate::widgets::Stack), [`Wrap`](crate::widgets::Wrap),
+    /// [`Layers`](crate::widgets::Layers), and [`List`](crate::widgets::List)
+    /// to apply only the changes rather than comparing every child.
+    pub fn map_each_item<F, W>(&self, mut map: F) -> Dynamic<WidgetList>
+    where
+        T: Send + 'static,
+        F: for<'a> FnMut(&'a T) -> W + Send + 'static,
+        W: MakeWidget,
+    {
+        let (widgets, queue) = self.items.map_ref(|items| {
+            let mut widgets = items.iter().map(&mut map).collect::<WidgetList>();
+            widgets.track_changes();
+            (widgets, self.queues.subscribe())
+        });
+        let widgets = Dynamic::new(widgets);
+        let weak_widgets = widgets.downgrade();
+        widgets.set_source(self.items.for_each_subsequent_try(move |items| {
+            let widgets = weak_widgets.upgrade().ok_or(CallbackDisconnected)?;
+            let changes = std::mem::take(&mut *queue.lock());
+            if !changes.is_empty() {
+                widgets.map_mut(|mut widgets| {
+                    apply_vec_changes(&mut widgets, items, &changes, &mut map);
+                });
+            }
+            Ok(())
+        }));
+        widgets
+    }
+}
+
+fn apply_vec_changes<T, W>(
+    widgets: &mut WidgetList,
+    items: &[T],
+    changes: &[VecChange],
+    mut map: impl FnMut(&T) -> W,
+) where
+    W: MakeWidget,
+{
+    if changes
+        .iter()
+        .any(|change| matches!(change, VecChange::Reset))
+    {
+        *widgets = items.iter().map(map).collect();
+        widgets.track_changes();
+        return;
+    }
+
+    // Widgets for inserted and updated items are mapped from the final
+    // contents once all changes have been applied, since intermediate values
+    // are no longer available. Until then, each is represented by a
+    // placeholder along with its current index.
+    let mut pending = Vec::<(usize, WidgetInstance)>::new();
+    let mut sync = Vec::with_capacity(changes.len());
+    for change in changes {
+        match *change {
+            VecChange::Insert(index) => {
+                for (pending_index, _) in &mut pending {
+                    if *pending_index >= index {
+                        *pending_index += 1;
+                    }
+                }
+                let placeholder = Space::clear().make_widget();
+                sync.push(ChildrenSyncChange::Insert(index, placeholder.clone()));
+                pending.push((index, placeholder));
+            }
+            VecChange::Remove(index) => {
+                pending.retain(|(pending_index, _)| *pending_index != index);
+                for (pending_index, _) in &mut pending {
+                    if *pending_index > index {
+                        *pending_index -= 1;
+                    }
+                }
+                sync.push(ChildrenSyncChange::Remove(index));
+            }
+            VecChange::Move { from, to } => {
+                for (pending_index, _) in &mut pending {
+                    *pending_index = if *pending_index == from {
+                        to
+                    } else if from < *pending_index && *pending_index <= to {
+                        *pending_index - 1
+                    } else if to <= *pending_index && *pending_index < from {
+                        *pending_index + 1
+                    } else {
+                        *pending_index
+                    };
+                }
+                if from < to {
+                    sync.extend((from..to).map(|index| ChildrenSyncChange::Swap(index, index + 1)));
+                } else {
+                    sync.extend(
+                        (to..from)
+                            .rev()
+                            .map(|index| ChildrenSyncChange::Swap(index, index + 1)),
+                    );
+                }
+            }
+            VecChange::Update(index) => {
+                if !pending
+                    .iter()
+                    .any(|(pending_index, _)| *pending_index == index)
+                {
+                    let placeholder = Space::clear().make_widget();
+                    sync.push(ChildrenSyncChange::Remove(index));
+                    sync.push(ChildrenSyncChange::Insert(index, placeholder.clone()));
+                    pending.push((index, placeholder));
+                }
+            }
+            VecChange::Reset => unreachable!("resets are handled above"),
+        }
+    }
+
+    for (index, placeholder) in pending {
+        let widget = map(&items[index]).make_widget();
+        for change in &mut sync {
+            if let ChildrenSyncChange::Insert(_, inserted) = change {
+                if *inserted == placeholder {
+                    *inserted = widget.clone();
+                }
+            }
+        }
+    }
+
+    widgets.apply_changes(sync);
+}
+
+impl<T> Clone for DynamicVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            queues: self.queues.clone(),
+        }
+    }
+}
+
+impl<T> Default for DynamicVec<T> {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> From<Vec<T>> for DynamicVec<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self::new(items)
+    }
+}
+
+impl<T> FromIterator<T> for DynamicVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<T> Debug for DynamicVec<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynamicVec").field(&self.items).finish()
+    }
+}
+
+impl<T> Source<Vec<T>> for DynamicVec<T> {
+    fn try_map_generational<R>(
+        &self,
+        map: impl FnOnce(DynamicGuard<'_, Vec<T>, true>) -> R,
+    ) -> Result<R, DeadlockError> {
+        self.items.try_map_generational(map)
+    }
+
+    fn for_each_subsequent_generational_try<F>(&self, for_each: F) -> CallbackHandle
+    where
+        Vec<T>: Send + 'static,
+        F: for<'a> FnMut(DynamicGuard<'_, Vec<T>, true>) -> Result<(), CallbackDisconnected>
+            + Send
+            + 'static,
+    {
+        self.items.for_each_subsequent_generational_try(for_each)
+    }
+
+    fn for_each_generational_cloned_try<F>(&self, for_each: F) -> CallbackHandle
+    where
+        Vec<T>: Clone + Send + 'static,
+        F: FnMut(GenerationalValue<Vec<T>>) -> Result<(), CallbackDisconnected> + Send + 'static,
+    {
+        self.items.for_each_generational_cloned_try(for_each)
+    }
+}
+
+impl<T> context::sealed::Trackable for DynamicVec<T> {
+    fn inner_redraw_when_changed(&self, handle: WindowHandle) {
+        self.items.0.redraw_when_changed(handle);
+    }
+
+    fn inner_sync_when_changed(&self, handle: WindowHandle) {
+        self.items.0.sync_when_changed(handle);
+    }
+
+    fn inner_invalidate_when_changed(&self, handle: WindowHandle, id: WidgetId) {
+        self.items.0.invalidate_when_changed(handle, id);
+    }
+}
+
+/// A reactive, ordered map that reports each individual change made to it.
+///
+/// This type is the keyed equivalent of [`DynamicVec`]. Each insert, removal,
+/// and update is reported as a [`MapChange`], allowing observers to react to
+/// only the affected entries.
+pub struct DynamicMap<K, V> {
+    entries: Dynamic<BTreeMap<K, V>>,
+    queues: ChangeQueues<MapChange<K>>,
+}
+
+impl<K, V> DynamicMap<K, V>
+where
+    K: Ord + Clone,
+{
+    /// Returns a new collection containing `entries`.
+    #[must_use]
+    pub fn new(entries: BTreeMap<K, V>) -> Self {
+        Self {
+            entries: Dynamic::new(entries),
+            queues: ChangeQueues::default(),
+        }
+    }
+
+    fn modify<R>(
+        &self,
+        modify: impl FnOnce(&mut Mutable<'_, BTreeMap<K, V>>, &mut Vec<MapChange<K>>) -> R,
+    ) -> R {
+        self.entries.map_mut(|mut entries| {
+            let mut changes = Vec::new();
+            let result = modify(&mut entries, &mut changes);
+            self.queues.publish(&changes);
+            result
+        })
+    }
+
+    /// Returns the number of entries in this collection.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this value is already locked by the current
+    /// thread.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.map_ref(BTreeMap::len)
+    }
+
+    /// Returns true if this collection contains no entries.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this value is already locked by the current
+    /// thread.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.map_ref(BTreeMap::is_empty)
+    }
+
+    /// Returns true if this collection contains an entry for `key`.
+    #[must_use]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.map_ref(|entries| entries.contains_key(key))
+    }
+
+    /// Inserts `value` for `key`, returning the previous value if one was
+    /// present.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.modify(|entries, changes| {
+            let previous = entries.insert(key.clone(), value);
+            changes.push(if previous.is_some() {
+                MapChange::Update(key)
+            } else {
+                MapChange::Insert(key)
+            });
+            previous
+        })
+    }
+
+    /// Removes and returns the value for `key`, if present.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.modify(|entries, changes| {
+            if !entries.contains_key(key) {
+                return None;
+            }
+            let removed = entries.remove(key);
+            changes.push(MapChange::Remove(key.clone()));
+            removed
+        })
+    }
+
+    /// Invokes `update` with exclusive access to the value for `key`, and
+    /// reports the entry as updated.
+    ///
+    /// Returns `None` without invoking `update` if `key` is not present.
+    pub fn update<R>(&self, key: &K, update: impl FnOnce(&mut V) -> R) -> Option<R> {
+        self.modify(|entries, changes| {
+            if !entries.contains_key(key) {
+                return None;
+            }
+            let result = entries.get_mut(key).map(update);
+            changes.push(MapChange::Update(key.clone()));
+            result
+        })
+    }
+
+    /// Removes all entries from this collection.
+    pub fn clear(&self) {
+        self.modify(|entries, changes| {
+            if !entries.is_empty() {
+                entries.clear();
+                changes.push(MapChange::Reset);
+            }
+        });
+    }
+
+    /// Replaces the entire contents of this collection with `new_entries`,
+    /// returning the previous contents.
+    pub fn replace_all(&self, new_entries: BTreeMap<K, V>) -> BTreeMap<K, V> {
+        self.modify(|entries, changes| {
+            changes.push(MapChange::Reset);
+            std::mem::replace(&mut **entries, new_entries)
+        })
+    }
+
+    /// Invokes `on_change` each time this collection is changed, providing
+    /// the updated contents and the changes that were made since the last
+    /// invocation.
+    pub fn for_each_change<F>(&self, mut on_change: F) -> CallbackHandle
+    where
+        K: Send + 'static,
+        V: Send + 'static,
+        F: for<'a> FnMut(&'a BTreeMap<K, V>, &'a [MapChange<K>]) + Send + 'static,
+    {
+        let queue = self.queues.subscribe();
+        self.entries.for_each_subsequent(move |entries| {
+            let changes = std::mem::take(&mut *queue.lock());
+            if !changes.is_empty() {
+                on_change(entries, &changes);
+            }
+        })
+    }
+
+    /// Returns a [`WidgetList`] containing the result of invoking `map` for
+    /// each entry in this collection, in key order.
+    ///
+    /// As this collection changes, `map` is only invoked for inserted and
+    /// updated entries. Widgets for entries that were not changed are reused.
+    pub fn map_each_item<F, W>(&self, mut map: F) -> Dynamic<WidgetList>
+    where
+        K: Send + 'static,
+        V: Send + 'static,
+        F: for<'a> FnMut(&'a K, &'a V) -> W + Send + 'static,
+        W: MakeWidget,
+    {
+        // The keys of the displayed entries, in the same order as the widgets.
+        let (mut keys, widgets, queue) = self.entries.map_ref(|entries| {
+            let mut widgets = entries
+                .iter()
+                .map(|(key, value)| map(key, value))
+                .collect::<WidgetList>();
+            widgets.track_changes();
+            (
+                entries.keys().cloned().collect::<Vec<_>>(),
+                widgets,
+                self.queues.subscribe(),
+            )
+        });
+        let widgets = Dynamic::new(widgets);
+        let weak_widgets = widgets.downgrade();
+        widgets.set_source(self.entries.for_each_subsequent_try(move |entries| {
+            let widgets = weak_widgets.upgrade().ok_or(CallbackDisconnected)?;
+            let changes = std::mem::take(&mut *queue.lock());
+            if changes.is_empty() {
+                return Ok(());
+            }
+
+            widgets.map_mut(|mut widgets| {
+                if changes
+                    .iter()
+                    .any(|change| matches!(change, MapChange::Reset))
+                {
+                    keys = entries.keys().cloned().collect();
+                    *widgets = entries.iter().map(|(key, value)| map(key, value)).collect();
+                    widgets.track_changes();
+                    return;
+                }
+
+                let mut sync = Vec::with_capacity(changes.len());
+                for change in changes {
+                    match change {
+                        MapChange::Insert(key) | MapChange::Update(key) => {
+                            let index = keys.binary_search(&key);
+                            if let Some(value) = entries.get(&key) {
+                                let widget = map(&key, value).make_widget();
+                                match index {
+                                    Ok(index) => {
+                                        sync.push(ChildrenSyncChange::Remove(index));
+                                        sync.push(ChildrenSyncChange::Insert(index, widget));
+                                    }
+                                    Err(index) => {
+                                        keys.insert(index, key);
+                                        sync.push(ChildrenSyncChange::Insert(index, widget));
+                                    }
+                                }
+                            } else if let Ok(index) = index {
+                                keys.remove(index);
+                                sync.push(ChildrenSyncChange::Remove(index));
+                            }
+                        }
+                        MapChange::Remove(key) => {
+                            if let Ok(index) = keys.binary_search(&key) {
+                                keys.remove(index);
+                                sync.push(ChildrenSyncChange::Remove(index));
+                            }
+                        }
+                        MapChange::Reset => unreachable!("resets are handled above"),
+                    }
+                }
+                widgets.apply_changes(sync);
+            });
+            Ok(())
+        }));
+        widgets
+    }
+}
+
+impl<K, V> Clone for DynamicMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            queues: self.queues.clone(),
+        }
+    }
+}
+
+impl<K, V> Default for DynamicMap<K, V>
+where
+    K: Ord + Clone,
+{
+    fn default() -> Self {
+        Self::new(BTreeMap::new())
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for DynamicMap<K, V>
+where
+    K: Ord + Clone,
+{
+    fn from(entries: BTreeMap<K, V>) -> Self {
+        Self::new(entries)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for DynamicMap<K, V>
+where
+    K: Ord + Clone,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<K, V> Debug for DynamicMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynamicMap").field(&self.entries).finish()
+    }
+}
+
+impl<K, V> Source<BTreeMap<K, V>> for DynamicMap<K, V> {
+    fn try_map_generational<R>(
+        &self,
+        map: impl FnOnce(DynamicGuard<'_, BTreeMap<K, V>, true>) -> R,
+    ) -> Result<R, DeadlockError> {
+        self.entries.try_map_generational(map)
+    }
+
+    fn for_each_subsequent_generational_try<F>(&self, for_each: F) -> CallbackHandle
+    where
+        BTreeMap<K, V>: Send + 'static,
+        F: for<'a> FnMut(
+                DynamicGuard<'_, BTreeMap<K, V>, true>,
+            ) -> Result<(), CallbackDisconnected>
+            + Send
+            + 'static,
+    {
+        self.entries.for_each_subsequent_generational_try(for_each)
+    }
+
+    fn for_each_generational_cloned_try<F>(&self, for_each: F) -> CallbackHandle
+    where
+        BTreeMap<K, V>: Clone + Send + 'static,
+        F: FnMut(GenerationalValue<BTreeMap<K, V>>) -> Result<(), CallbackDisconnected>
+            + Send
+            + 'static,
+    {
+        self.entries.for_each_generational_cloned_try(for_each)
+    }
+}
+
+impl<K, V> context::sealed::Trackable for DynamicMap<K, V> {
+    fn inner_redraw_when_changed(&self, handle: WindowHandle) {
+        self.entries.0.redraw_when_changed(handle);
+    }
+
+    fn inner_sync_when_changed(&self, handle: WindowHandle) {
+        self.entries.0.sync_when_changed(handle);
+    }
+
+    fn inner_invalidate_when_changed(&self, handle: WindowHandle, id: WidgetId) {
+        self.entries.0.invalidate_when_changed(handle, id);
+    }
+}
+
+#[test]
+fn map_cycle_is_finite() {
+    crate::initialize_tracing();
+    let a = Dynamic::new(0_usize);
+
+    // This callback updates a each time a is updated with a + 1, causing an
+    // infinite cycle if not broken by Cushy.
+    a.for_each_cloned({
+        let a = a.clone();
+        move |current| {
+            a.set(current + 1);
+        }
+    })
+    .persist();
+
+    // Cushy will invoke the callback for the first set call, but the set call
+    // within the callback will not cause the callback to be invoked again.
+    // Thus, we expect setting the value to 1 to result in `a` containing 2.
+    a.set(1);
+    assert_eq!(a.get(), 2);
+}
+
+#[test]
+fn compare_swap() {
+    let dynamic = Dynamic::new(1);
+    assert_eq!(dynamic.compare_swap(&1, 2), Ok(1));
+    assert_eq!(dynamic.compare_swap(&1, 0), Err(2));
+    assert_eq!(dynamic.compare_swap(&2, 0), Ok(2));
+    assert_eq!(dynamic.get(), 0);
+}
+
+#[test]
+fn ref_counts() {
+    let dynamic = Dynamic::new(1);
+    assert_eq!(dynamic.instances(), 1);
+
+    let second = dynamic.clone();
+    assert_eq!(dynamic.instances(), 2);
+
+    assert_eq!(dynamic.readers(), 0);
+    let reader = second.into_reader();
+    assert_eq!(dynamic.instances(), 1);
+    assert_eq!(dynamic.readers(), 1);
+
+    // Test that once the last instance is dropped that the reader is no longer
+    // connected and that on_disconnect gets invoked.
+    assert!(reader.connected());
+    let invoked = Dynamic::new(false);
+    reader.on_disconnect({
+        let invoked = invoked.clone();
+        move || {
+            invoked.set(true);
+        }
+    });
+    drop(dynamic);
+
+    assert!(invoked.get());
+    assert!(!reader.connected());
+}
+
+#[test]
+fn linked_short_circuit() {
+    let usize = Dynamic::new(0_usize);
+    let string = usize.linked_string();
+
+    string.map_ref(|s| assert_eq!(s, "0"));
+    string.set(String::from("1"));
+    assert_eq!(usize.get(), 1);
+    usize.set(2);
+    string.map_ref(|s| assert_eq!(s, "2"));
+}
+
+#[test]
+fn graph_shortcircuit() {
+    let a = Dynamic::new(0_usize);
+    let doubled = a.map_each_cloned(|a| a * 2);
+    let quadrupled = doubled.map_each_cloned(|a| a * 2);
     a.set_source(quadrupled.for_each_cloned({
         let a = a.clone();
         move |quad| a.set(quad / 4)
@@ -4333,3 +6656,295 @@ fn graph_shortcircuit() {
     assert_eq!(a.get(), 4);
     assert_eq!(doubled.get(), 8);
 }
+
+#[test]
+fn dynamic_vec_maps_changed_items() {
+    let mapped = Arc::new(Mutex::new(0_usize));
+    let values = DynamicVec::new(vec![1, 2, 3]);
+    let widgets = values.map_each_item({
+        let mapped = mapped.clone();
+        move |value: &i32| {
+            *mapped.lock() += 1;
+            value.to_string()
+        }
+    });
+    let changes = Dynamic::new(Vec::new());
+    values
+        .for_each_change({
+            let changes = changes.clone();
+            move |_, new_changes| changes.lock().extend_from_slice(new_changes)
+        })
+        .persist();
+    assert_eq!(*mapped.lock(), 3);
+    let first = widgets.map_ref(|widgets| widgets[0].clone());
+
+    values.push(4);
+    assert_eq!(*mapped.lock(), 4);
+    values.swap(0, 2);
+    assert_eq!(*mapped.lock(), 4);
+    assert_eq!(widgets.map_ref(|widgets| widgets[2].clone()), first);
+    values.update(1, |value| *value = 5);
+    assert_eq!(*mapped.lock(), 5);
+    values.remove(2);
+    assert_eq!(widgets.map_ref(WidgetList::len), 3);
+    assert_eq!(
+        changes.get(),
+        vec![
+            VecChange::Insert(3),
+            VecChange::Move { from: 0, to: 2 },
+            VecChange::Move { from: 1, to: 0 },
+            VecChange::Update(1),
+            VecChange::Remove(2),
+        ]
+    );
+}
+
+#[test]
+fn dynamic_vec_records_widget_changes() {
+    fn synchronize(
+        widgets: &Dynamic<WidgetList>,
+        revision: &mut Option<u64>,
+        synced: &mut Vec<WidgetInstance>,
+    ) -> usize {
+        let mut changes = 0;
+        widgets.map_ref(|widgets| {
+            widgets.synchronize_changes_with(
+                revision,
+                synced,
+                |synced, index| synced.get(index),
+                |synced, change| {
+                    changes += 1;
+                    match change {
+                        ChildrenSyncChange::Insert(index, widget) => synced.insert(index, widget),
+                        ChildrenSyncChange::Remove(index) => {
+                            synced.remove(index);
+                        }
+                        ChildrenSyncChange::Swap(a, b) => synced.swap(a, b),
+                        ChildrenSyncChange::Truncate(length) => synced.truncate(length),
+                    }
+                },
+            );
+            assert_eq!(&**widgets, synced.as_slice());
+        });
+        changes
+    }
+
+    let values = DynamicVec::new(vec![1, 2, 3]);
+    let widgets = values.map_each_item(|value: &i32| value.to_string());
+    let mut revision = None;
+    let mut synced = Vec::new();
+    // The first synchronization compares every child.
+    assert_eq!(synchronize(&widgets, &mut revision, &mut synced), 4);
+
+    // Afterwards, only the recorded changes are applied.
+    values.push(4);
+    values.remove(0);
+    assert_eq!(synchronize(&widgets, &mut revision, &mut synced), 2);
+    values.update(1, |value| *value = 5);
+    assert_eq!(synchronize(&widgets, &mut revision, &mut synced), 2);
+    values.swap(0, 2);
+    synchronize(&widgets, &mut revision, &mut synced);
+
+    // Changes made directly to the list are not recorded.
+    widgets.map_mut(|mut widgets| widgets.push("5"));
+    synchronize(&widgets, &mut revision, &mut synced);
+    assert_eq!(revision, None);
+}
+
+#[test]
+fn computed_retracks_dependencies() {
+    let evaluations = Arc::new(Mutex::new(0_usize));
+    let use_a = Dynamic::new(true);
+    let a = Dynamic::new(1);
+    let b = Dynamic::new(2);
+    let computed = Dynamic::computed({
+        let evaluations = evaluations.clone();
+        let (use_a, a, b) = (use_a.clone(), a.clone(), b.clone());
+        move || {
+            *evaluations.lock() += 1;
+            if use_a.get() {
+                a.get()
+            } else {
+                b.get()
+            }
+        }
+    });
+    assert_eq!(computed.get(), 1);
+    assert_eq!(*evaluations.lock(), 1);
+
+    // `b` has not been read, so it is not a dependency.
+    b.set(3);
+    assert_eq!(*evaluations.lock(), 1);
+    a.set(4);
+    assert_eq!(computed.get(), 4);
+    assert_eq!(*evaluations.lock(), 2);
+
+    use_a.set(false);
+    assert_eq!(computed.get(), 3);
+    assert_eq!(*evaluations.lock(), 3);
+    // `a` is no longer a dependency.
+    a.set(5);
+    assert_eq!(*evaluations.lock(), 3);
+
+    // Computed values can depend on other computed values.
+    let doubled = Dynamic::computed({
+        let computed = computed.clone();
+        move || computed.get() * 2
+    });
+    b.set(10);
+    assert_eq!(doubled.get(), 20);
+
+    // Dropping the computed values uninstalls their callbacks.
+    drop(doubled);
+    drop(computed);
+    b.set(11);
+    assert_eq!(*evaluations.lock(), 4);
+}
+
+#[test]
+fn transactions_notify_once() {
+    let a = Dynamic::new(0);
+    let b = Dynamic::new(0);
+    let invocations = Dynamic::new(Vec::new());
+    (&a, &b)
+        .for_each_subsequent({
+            let invocations = invocations.clone();
+            move |(a, b)| invocations.lock().push((*a, *b))
+        })
+        .persist();
+
+    transaction(|| {
+        a.set(1);
+        b.set(2);
+        a.set(3);
+        // Callbacks have not been invoked yet.
+        assert!(invocations.lock().is_empty());
+    });
+    // The callback is invoked once, observing the final state.
+    assert_eq!(invocations.get(), vec![(3, 2)]);
+
+    let result: Result<(), ()> = Transaction::new().restoring(&a).try_run(|| {
+        a.set(4);
+        b.set(5);
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(a.get(), 3);
+    assert_eq!(b.get(), 5);
+
+    let result: Result<(), ()> = Transaction::new().restoring_all().try_run(|| {
+        a.set(6);
+        b.set(7);
+        b.set(8);
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(a.get(), 3);
+    assert_eq!(b.get(), 5);
+
+    // A failed nested transaction only restores its own changes, and the
+    // outer transaction restores the values it had originally.
+    let result: Result<(), ()> = Transaction::new().restoring_all().try_run(|| {
+        a.set(9);
+        let inner: Result<(), ()> = Transaction::new().restoring_all().try_run(|| {
+            a.set(10);
+            b.set(11);
+            Err(())
+        });
+        assert!(inner.is_err());
+        assert_eq!(a.get(), 9);
+        assert_eq!(b.get(), 5);
+        Transaction::new().restoring_all().run(|| b.set(12));
+        Err(())
+    });
+    assert!(result.is_err());
+    assert_eq!(a.get(), 3);
+    assert_eq!(b.get(), 5);
+
+    let result: Result<(), ()> = Transaction::new().restoring_all().try_run(|| {
+        a.set(13);
+        Ok(())
+    });
+    assert!(result.is_ok());
+    assert_eq!(a.get(), 13);
+}
+
+#[test]
+fn value_streams() {
+    use std::task::Context;
+
+    struct NoopWaker;
+    impl std::task::Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+
+    let value = Dynamic::new(1);
+    let mut stream = std::pin::pin!(value.to_stream());
+    assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Pending);
+    // Only the most recent value is produced.
+    value.set(2);
+    value.set(3);
+    assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(Some(3)));
+    drop(value);
+    assert_eq!(stream.as_mut().poll_next(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn operators() {
+    let value = Dynamic::new(1);
+    let even = value.filter(|value| value % 2 == 0);
+    let tens = value.distinct_by(|value| value / 10);
+    let pair = value.previous();
+    let sum = value.scan(0, |sum, value| *sum += value);
+
+    value.set(2);
+    value.set(3);
+    value.set(12);
+    assert_eq!(even.get(), 12);
+    assert_eq!(tens.get(), 12);
+    assert_eq!(pair.get(), (Some(3), 12));
+    assert_eq!(sum.get(), 18);
+
+    value.set(13);
+    assert_eq!(even.get(), 12);
+    assert_eq!(tens.get(), 12);
+}
+
+#[test]
+fn throttled_edges() {
+    let clock = clock::ManualClock::install();
+    let period = Duration::from_millis(100);
+    let value = Dynamic::new(0);
+    let leading = value.throttled(period, ThrottleEdges::Leading);
+    let trailing = value.throttled(period, ThrottleEdges::Trailing);
+    let both = value.throttled(period, ThrottleEdges::Both);
+    let throttled = || (leading.get(), trailing.get(), both.get());
+
+    // The first update begins a period. Leading edges store it immediately,
+    // and later updates during the period are held for trailing edges.
+    value.set(1);
+    assert_eq!(throttled(), (1, 0, 1));
+    value.set(2);
+    value.set(3);
+    assert_eq!(throttled(), (1, 0, 1));
+    clock.advance(period);
+    assert_eq!(throttled(), (1, 3, 3));
+
+    // Storing a trailing value begins a new period, which delays the next
+    // update.
+    value.set(4);
+    assert_eq!(throttled(), (4, 3, 3));
+    clock.advance(period);
+    assert_eq!(throttled(), (4, 4, 4));
+
+    // After a quiet period, leading edges store updates immediately again,
+    // while trailing-only throttles wait until the period ends.
+    clock.advance(period);
+    value.set(5);
+    assert_eq!(throttled(), (5, 4, 5));
+    clock.advance(period);
+    assert_eq!(throttled(), (5, 5, 5));
+}
@@ -1,7 +1,9 @@
 //! Types for creating reusable widgets (aka components or views).
 
 use std::any::Any;
+use std::cell::Cell;
 use std::clone::Clone;
+use std::collections::VecDeque;
 use std::fmt::{self, Debug};
 use std::ops::{ControlFlow, Deref, DerefMut};
 use std::sync::atomic::{self, AtomicU64};
@@ -14,15 +16,16 @@ use figures::{IntoSigned, IntoUnsigned, Point, Rect, Size, Zero};
 use intentional::Assert;
 use kludgine::app::winit::event::{Ime, MouseButton, MouseScrollDelta, TouchPhase};
 use kludgine::app::winit::keyboard::ModifiersState;
-use kludgine::app::winit::window::CursorIcon;
 use kludgine::Color;
 use parking_lot::{Mutex, MutexGuard};
 
+use crate::animation::ZeroToOne;
 use crate::app::Run;
 use crate::context::sealed::Trackable as _;
 use crate::context::{
     AsEventContext, EventContext, GraphicsContext, LayoutContext, ManageWidget, WidgetContext,
 };
+use crate::cursor::MouseCursor;
 use crate::styles::components::{
     FontFamily, FontStyle, FontWeight, Heading1FontFamily, Heading1Style, Heading1Weight,
     Heading2FontFamily, Heading2Style, Heading2Weight, Heading3FontFamily, Heading3Style,
@@ -39,12 +42,13 @@ use crate::styles::{
 use crate::tree::{Tree, WeakTree};
 use crate::value::{Dynamic, Generation, IntoDynamic, IntoValue, Validation, Value};
 use crate::widgets::checkbox::{Checkable, CheckboxState};
+use crate::widgets::effects::Transform;
 use crate::widgets::layers::{OverlayLayer, Tooltipped};
 use crate::widgets::list::List;
 use crate::widgets::shortcuts::{ShortcutKey, Shortcuts};
 use crate::widgets::{
-    Align, Button, Checkbox, Collapse, Container, Disclose, Expand, Layers, Resize, Scroll, Space,
-    Stack, Style, Themed, ThemedMode, Validated, Wrap,
+    Align, Button, Cached, Checkbox, Collapse, Container, Disclose, Effects, Expand, Layers,
+    Resize, Scroll, Space, Stack, Style, Themed, ThemedMode, Validated, Wrap,
 };
 use crate::window::sealed::WindowCommand;
 use crate::window::{
@@ -280,6 +284,14 @@ pub trait Widget: Send + Debug + 'static {
         Debug::fmt(self, f)
     }
 
+    /// Returns the text this widget last displayed, if it displays text.
+    ///
+    /// This is used to find widgets by their contents, such as with
+    /// [`VirtualWindow::find_text`](crate::window::VirtualWindow::find_text).
+    fn displayed_text(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns true if this widget handles all built-in style components that
     /// apply.
     ///
@@ -326,7 +338,11 @@ pub trait Widget: Send + Debug + 'static {
 
     /// The widget is currently has a cursor hovering it at `location`.
     #[allow(unused_variables)]
-    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<MouseCursor> {
         None
     }
 
@@ -553,6 +569,19 @@ pub trait WrapperWidget: Debug + Send + 'static {
     #[allow(unused_variables)]
     fn redraw_foreground(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {}
 
+    /// Draws the wrapped widget.
+    ///
+    /// This is invoked between [`redraw_background()`](Self::redraw_background)
+    /// and [`redraw_foreground()`](Self::redraw_foreground). The provided
+    /// implementation draws `child` directly into `context`.
+    fn redraw_child(
+        &mut self,
+        child: &MountedWidget,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        context.for_other(child).redraw();
+    }
+
     /// Returns the rectangle that the child widget should occupy given
     /// `available_space`.
     #[allow(unused_variables)]
@@ -625,7 +654,11 @@ pub trait WrapperWidget: Debug + Send + 'static {
 
     /// The widget is currently has a cursor hovering it at `location`.
     #[allow(unused_variables)]
-    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<MouseCursor> {
         None
     }
 
@@ -773,7 +806,7 @@ where
         self.redraw_background(context);
 
         let child = self.child_mut().mounted(&mut context.as_event_context());
-        context.for_other(&child).redraw();
+        self.redraw_child(&child, context);
 
         self.redraw_foreground(context);
     }
@@ -801,7 +834,11 @@ where
         T::hit_test(self, location, context)
     }
 
-    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<MouseCursor> {
         T::hover(self, location, context)
     }
 
@@ -1347,6 +1384,39 @@ pub trait MakeWidget: Sized {
         ThemedMode::new(mode, self)
     }
 
+    /// Caches the rendered contents of `self` in a texture, only redrawing
+    /// `self` when it is invalidated.
+    ///
+    /// See [`Cached`] for more information.
+    fn cached(self) -> Cached {
+        Cached::new(self)
+    }
+
+    /// Draws `self` with `opacity` applied to its contents as a single group.
+    ///
+    /// Unlike the [`Opacity`](crate::styles::components::Opacity) component,
+    /// overlapping children are not blended with each other.
+    fn with_group_opacity(self, opacity: impl IntoValue<ZeroToOne>) -> Effects {
+        Effects::new(self).group_opacity(opacity)
+    }
+
+    /// Draws `self` with `transform` applied.
+    ///
+    /// Transformations only affect how `self` is drawn, not how it is laid out
+    /// or how it receives input.
+    fn transformed(self, transform: impl IntoValue<Transform>) -> Effects {
+        Effects::new(self).transform(transform)
+    }
+
+    /// Draws `self` blurred by `radius`.
+    ///
+    /// Only the contents of `self` are blurred, not what is drawn beneath it.
+    /// See [`Modal::backdrop_blur`](crate::widgets::layers::Modal::backdrop_blur)
+    /// to blur the contents behind a modal.
+    fn blurred(self, radius: impl IntoValue<Dimension>) -> Effects {
+        Effects::new(self).blur(radius)
+    }
+
     /// Returns a widget that collapses `self` horizontally based on the dynamic boolean value.
     ///
     /// This widget will be collapsed when the dynamic contains `true`, and
@@ -1437,6 +1507,7 @@ pub const IGNORED: EventHandling = EventHandling::Continue(EventIgnored);
 pub(crate) trait AnyWidget: Widget {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn type_name(&self) -> &'static str;
 }
 
 impl<T> AnyWidget for T
@@ -1450,6 +1521,10 @@ where
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 /// An instance of a [`Widget`].
@@ -1458,8 +1533,26 @@ pub struct WidgetInstance {
     data: Arc<WidgetInstanceData>,
 }
 
+thread_local! {
+    static DESCRIBING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Formats a widget using [`Widget::summarize`].
+struct Summary<'a>(&'a dyn AnyWidget);
+
+impl Debug for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.summarize(f)
+    }
+}
+
 impl Debug for WidgetInstance {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // While a widget is being described, its children are only identified
+        // rather than summarized.
+        if DESCRIBING.get() {
+            return f.debug_tuple("WidgetInstance").field(&self.id()).finish();
+        }
         match self.data.widget.try_lock() {
             Some(widget) => widget.summarize(f),
             None => f.debug_struct("WidgetInstance").finish_non_exhaustive(),
@@ -1510,6 +1603,18 @@ impl WidgetInstance {
         self.data.id
     }
 
+    /// Returns this widget's type name and summary, or `None` if the widget
+    /// is currently locked.
+    ///
+    /// The summary does not include summaries of this widget's children.
+    pub(crate) fn try_describe(&self) -> Option<(&'static str, String)> {
+        let widget = self.data.widget.try_lock()?;
+        let describing = DESCRIBING.replace(true);
+        let summary = format!("{:?}", Summary(&*widget));
+        DESCRIBING.set(describing);
+        Some((widget.type_name(), summary))
+    }
+
     /// Sets the widget that should be focused next.
     ///
     /// Cushy automatically determines reverse tab order by using this same
@@ -2070,10 +2175,11 @@ impl WidgetGuard<'_> {
 ///   [`Self::into_layers`]
 /// - Layout horizontally, wrapping into multiple rows as needed: [`Wrap::new`]
 ///   / [`Self::into_wrap`].
-#[derive(Default, Eq, PartialEq)]
+#[derive(Default)]
 #[must_use]
 pub struct WidgetList {
     ordered: Vec<WidgetInstance>,
+    history: Option<Box<ChangeHistory>>,
 }
 
 impl WidgetList {
@@ -2081,6 +2187,7 @@ impl WidgetList {
     pub const fn new() -> Self {
         Self {
             ordered: Vec::new(),
+            history: None,
         }
     }
 
@@ -2089,15 +2196,23 @@ impl WidgetList {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             ordered: Vec::with_capacity(capacity),
+            history: None,
         }
     }
 
+    /// Returns the contents for modification. Changes made this way are not
+    /// recorded, so widgets displaying this list must fully resynchronize.
+    fn untracked_mut(&mut self) -> &mut Vec<WidgetInstance> {
+        self.history = None;
+        &mut self.ordered
+    }
+
     /// Pushes `widget` into the list.
     pub fn push<W>(&mut self, widget: W)
     where
         W: MakeWidget,
     {
-        self.ordered.push(widget.make_widget());
+        self.untracked_mut().push(widget.make_widget());
     }
 
     /// Inserts `widget` into the list at `index`.
@@ -2105,7 +2220,7 @@ impl WidgetList {
     where
         W: MakeWidget,
     {
-        self.ordered.insert(index, widget.make_widget());
+        self.untracked_mut().insert(index, widget.make_widget());
     }
 
     /// Extends this collection with the contents of `iter`.
@@ -2114,7 +2229,8 @@ impl WidgetList {
         Iter: IntoIterator<Item = T>,
         T: MakeWidget,
     {
-        self.ordered.extend(iter.into_iter().map(T::make_widget));
+        self.untracked_mut()
+            .extend(iter.into_iter().map(T::make_widget));
     }
 
     /// Adds `widget` to self and returns the updated list.
@@ -2153,12 +2269,44 @@ impl WidgetList {
     /// If this collection is already smaller or the same size as `length`, this
     /// function does nothing.
     pub fn truncate(&mut self, length: usize) {
-        self.ordered.truncate(length);
+        self.untracked_mut().truncate(length);
     }
 
     /// Clear the list
     pub fn clear(&mut self) {
-        self.ordered.clear();
+        self.untracked_mut().clear();
+    }
+
+    /// Begins recording the changes made using
+    /// [`apply_changes()`](Self::apply_changes).
+    ///
+    /// Widgets displaying a list that records its changes can apply only the
+    /// changes made since they last synchronized rather than comparing every
+    /// child.
+    pub(crate) fn track_changes(&mut self) {
+        self.history
+            .get_or_insert_with(|| Box::new(ChangeHistory::new()));
+    }
+
+    /// Applies `changes` to this list, recording them if this list is tracking
+    /// its changes.
+    pub(crate) fn apply_changes(&mut self, changes: Vec<ChildrenSyncChange>) {
+        for change in &changes {
+            match change {
+                ChildrenSyncChange::Insert(index, widget) => {
+                    self.ordered.insert(*index, widget.clone());
+                }
+                ChildrenSyncChange::Remove(index) => {
+                    self.ordered.remove(*index);
+                }
+                ChildrenSyncChange::Swap(a, b) => self.ordered.swap(*a, *b),
+                ChildrenSyncChange::Truncate(length) => self.ordered.truncate(*length),
+            }
+        }
+
+        if let Some(history) = &mut self.history {
+            history.record(changes, self.ordered.len());
+        }
     }
 
     /// Returns `self` as a vertical [`Stack`] of rows.
@@ -2232,6 +2380,35 @@ impl WidgetList {
 
         change_fn(collection, ChildrenSyncChange::Truncate(self.len()));
     }
+
+    /// Synchronizes this list of children with another collection, only
+    /// applying the changes made since `revision` when they are known.
+    ///
+    /// `revision` should initially be `None`, and is updated to the revision
+    /// `collection` has been synchronized to. If the changes since `revision`
+    /// are not known, this function falls back to
+    /// [`synchronize_with()`](Self::synchronize_with).
+    pub(crate) fn synchronize_changes_with<Collection>(
+        &self,
+        revision: &mut Option<u64>,
+        collection: &mut Collection,
+        get_index: impl Fn(&Collection, usize) -> Option<&WidgetInstance>,
+        mut change_fn: impl FnMut(&mut Collection, ChildrenSyncChange),
+    ) {
+        if let Some(changes) = self
+            .history
+            .as_ref()
+            .zip(*revision)
+            .and_then(|(history, revision)| history.changes_since(revision))
+        {
+            for change in changes {
+                change_fn(collection, change.clone());
+            }
+        } else {
+            self.synchronize_with(collection, get_index, change_fn);
+        }
+        *revision = self.history.as_ref().map(|history| history.revision);
+    }
 }
 
 impl Debug for WidgetList {
@@ -2240,6 +2417,70 @@ impl Debug for WidgetList {
     }
 }
 
+impl Eq for WidgetList {}
+
+impl PartialEq for WidgetList {
+    fn eq(&self, other: &Self) -> bool {
+        self.ordered == other.ordered
+    }
+}
+
+/// The most recent changes made to a [`WidgetList`] using
+/// [`WidgetList::apply_changes`].
+#[derive(Debug)]
+struct ChangeHistory {
+    revision: u64,
+    /// Each batch of changes, along with the revision the changes were applied
+    /// to.
+    batches: VecDeque<(u64, Vec<ChildrenSyncChange>)>,
+    change_count: usize,
+}
+
+impl ChangeHistory {
+    /// The minimum number of changes to retain, regardless of the length of
+    /// the list.
+    const MINIMUM_RETAINED: usize = 64;
+
+    fn new() -> Self {
+        Self {
+            revision: Self::next_revision(),
+            batches: VecDeque::new(),
+            change_count: 0,
+        }
+    }
+
+    fn next_revision() -> u64 {
+        static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+        NEXT_REVISION.fetch_add(1, atomic::Ordering::Relaxed)
+    }
+
+    fn record(&mut self, changes: Vec<ChildrenSyncChange>, length: usize) {
+        let previous = std::mem::replace(&mut self.revision, Self::next_revision());
+        self.change_count += changes.len();
+        self.batches.push_back((previous, changes));
+
+        // Once more changes are retained than there are children, replaying
+        // the changes is no faster than comparing every child.
+        while self.change_count > length.max(Self::MINIMUM_RETAINED) {
+            let Some((_, removed)) = self.batches.pop_front() else {
+                break;
+            };
+            self.change_count -= removed.len();
+        }
+    }
+
+    fn changes_since(&self, revision: u64) -> Option<impl Iterator<Item = &ChildrenSyncChange>> {
+        let first = if revision == self.revision {
+            self.batches.len()
+        } else {
+            self.batches
+                .iter()
+                .position(|(applied_to, _)| *applied_to == revision)?
+        };
+        Some(self.batches.range(first..).flat_map(|(_, changes)| changes))
+    }
+}
+
 impl Dynamic<WidgetList> {
     /// Returns `self` as a vertical [`Stack`] of rows.
     #[must_use]
@@ -2326,6 +2567,7 @@ where
     fn from_iter<T: IntoIterator<Item = W>>(iter: T) -> Self {
         Self {
             ordered: iter.into_iter().map(MakeWidget::make_widget).collect(),
+            history: None,
         }
     }
 }
@@ -2340,7 +2582,7 @@ impl Deref for WidgetList {
 
 impl DerefMut for WidgetList {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.ordered
+        self.untracked_mut()
     }
 }
 
@@ -2458,9 +2700,12 @@ impl IntoWidgetList for Value<WidgetList> {
 }
 
 /// A change to perform during [`WidgetList::synchronize_with`].
+#[derive(Debug, Clone)]
 pub enum ChildrenSyncChange {
     /// Insert a new widget at the given index.
     Insert(usize, WidgetInstance),
+    /// Remove the widget at the given index.
+    Remove(usize),
     /// Swap the widgets at the given indices.
     Swap(usize, usize),
     /// Truncate the collection to the length given.
@@ -2475,6 +2720,7 @@ pub enum ChildrenSyncChange {
 #[derive(Debug)]
 pub struct MountedChildren<T = MountedWidget> {
     generation: Option<Generation>,
+    revision: Option<u64>,
     children: Vec<T>,
 }
 
@@ -2494,8 +2740,10 @@ where
             |gen| Some(gen) != self.generation,
         ) {
             self.generation = current_generation;
+            let mut revision = self.revision;
             children.map(|children| {
-                children.synchronize_with(
+                children.synchronize_changes_with(
+                    &mut revision,
                     self,
                     |this, index| {
                         this.children
@@ -2507,6 +2755,9 @@ where
                             this.children
                                 .insert(index, T::mount(context.push_child(widget), this, index));
                         }
+                        ChildrenSyncChange::Remove(index) => {
+                            context.remove_child(&this.children.remove(index).unmount());
+                        }
                         ChildrenSyncChange::Swap(a, b) => {
                             this.children.swap(a, b);
                         }
@@ -2518,6 +2769,7 @@ where
                     },
                 );
             });
+            self.revision = revision;
         }
     }
 
@@ -2526,6 +2778,7 @@ where
     /// When the iterator is dropped, this collection will be empty.
     pub fn drain(&mut self) -> vec::Drain<'_, T> {
         self.generation = None;
+        self.revision = None;
         self.children.drain(..)
     }
 
@@ -2540,6 +2793,7 @@ impl<T> Default for MountedChildren<T> {
     fn default() -> Self {
         Self {
             generation: None,
+            revision: None,
             children: Vec::default(),
         }
     }
@@ -2669,6 +2923,8 @@ impl ManageWidget for WidgetRef {
 /// Each [`WidgetInstance`] is guaranteed to have a unique [`WidgetId`] across
 /// the lifetime of an application.
 #[derive(Clone, Copy, Eq, PartialEq, Debug, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct WidgetId(u64);
 
 impl WidgetId {
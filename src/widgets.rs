@@ -2,6 +2,7 @@
 
 mod align;
 pub mod button;
+pub mod cached;
 mod canvas;
 pub mod checkbox;
 mod collapse;
@@ -12,7 +13,9 @@ mod custom;
 mod data;
 pub mod delimiter;
 pub mod disclose;
+pub mod effects;
 mod expand;
+pub mod form;
 pub mod grid;
 pub mod image;
 pub mod input;
@@ -39,6 +42,7 @@ pub mod wrap;
 
 pub use self::align::Align;
 pub use self::button::Button;
+pub use self::cached::Cached;
 pub use self::canvas::Canvas;
 pub use self::checkbox::Checkbox;
 pub use self::collapse::Collapse;
@@ -48,6 +52,7 @@ pub use self::custom::Custom;
 pub use self::data::Data;
 pub use self::delimiter::Delimiter;
 pub use self::disclose::Disclose;
+pub use self::effects::Effects;
 pub use self::expand::Expand;
 pub use self::grid::Grid;
 pub use self::image::Image;
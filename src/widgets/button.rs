@@ -8,21 +8,26 @@ use kludgine::app::winit::window::CursorIcon;
 use kludgine::shapes::{Shape, StrokeOptions};
 use kludgine::Color;
 
-use crate::animation::{AnimationHandle, AnimationTarget, IntoAnimate, LinearInterpolate, Spawn};
+use crate::animation::{
+    AnimationHandle, AnimationTarget, IntoAnimate, LinearInterpolate, Spawn, ZeroToOne,
+};
 use crate::context::{AsEventContext, EventContext, GraphicsContext, LayoutContext, WidgetContext};
+use crate::cursor::MouseCursor;
 use crate::styles::components::{
     AutoFocusableControls, DefaultActiveBackgroundColor, DefaultActiveForegroundColor,
     DefaultBackgroundColor, DefaultDisabledBackgroundColor, DefaultDisabledForegroundColor,
     DefaultForegroundColor, DefaultHoveredBackgroundColor, DefaultHoveredForegroundColor, Easing,
-    HighlightColor, IntrinsicPadding, OpaqueWidgetColor, OutlineColor, SurfaceColor, TextColor,
+    HighlightColor, IntrinsicPadding, Opacity, OpaqueWidgetColor, OutlineColor, SurfaceColor,
+    TextColor,
 };
 use crate::styles::{ColorExt, Styles};
 use crate::value::{Destination, Dynamic, IntoValue, Source, Value};
 use crate::widget::{
     Callback, EventHandling, MakeWidget, SharedCallback, Widget, WidgetRef, HANDLED,
 };
+use crate::widgets::image::NineSlice;
 use crate::window::{DeviceId, WindowLocal};
-use crate::FitMeasuredSize;
+use crate::{clock, FitMeasuredSize};
 
 /// A clickable button.
 #[derive(Debug)]
@@ -33,6 +38,8 @@ pub struct Button {
     pub on_click: Option<Callback<Option<ButtonClick>>>,
     /// The kind of button to draw.
     pub kind: Value<ButtonKind>,
+    /// Textures to draw instead of the button's background and outline.
+    pub images: Option<Value<ButtonImages>>,
     focusable: bool,
     per_window: WindowLocal<PerWindow>,
 }
@@ -49,6 +56,7 @@ struct PerWindow {
 #[derive(Default, Debug, Eq, PartialEq, Clone, Copy)]
 struct CacheState {
     style: Option<ButtonColors>,
+    visual_state: Option<VisualState>,
 }
 
 /// The type of a [`Button`] or similar clickable widget.
@@ -141,6 +149,7 @@ impl Button {
             on_click: None,
             per_window: WindowLocal::default(),
             kind: Value::Constant(ButtonKind::default()),
+            images: None,
             focusable: true,
         }
     }
@@ -152,6 +161,16 @@ impl Button {
         self
     }
 
+    /// Draws `images` instead of the button's background color and outline,
+    /// and returns self.
+    ///
+    /// The image drawn is selected using the button's [`VisualState`].
+    #[must_use]
+    pub fn images(mut self, images: impl IntoValue<ButtonImages>) -> Self {
+        self.images = Some(images.into_value());
+        self
+    }
+
     /// Sets the `on_click` callback and returns self.
     ///
     /// This callback will be invoked each time the button is clicked.
@@ -241,8 +260,18 @@ impl Button {
     }
 
     fn update_colors(&mut self, context: &mut WidgetContext<'_>, immediate: bool) {
+        let visual_state = Self::visual_style(context);
         let new_style = self.determine_stateful_colors(context);
         let window_local = self.per_window.entry(context).or_default();
+        let state_changed = window_local
+            .cached_state
+            .visual_state
+            .replace(visual_state)
+            .is_some_and(|previous| previous != visual_state);
+        if state_changed && self.images.is_some() {
+            // The image may change even if the colors do not.
+            context.set_needs_redraw();
+        }
         if window_local.cached_state.style.as_ref() == Some(&new_style) {
             return;
         }
@@ -291,6 +320,74 @@ impl Button {
     }
 }
 
+/// A set of [`NineSlice`] textures used to draw a [`Button`].
+///
+/// When a texture is not provided for a [`VisualState`], the
+/// [`normal`](Self::normal) texture is used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ButtonImages {
+    /// The texture to draw when no other state applies.
+    pub normal: NineSlice,
+    /// The texture to draw when the mouse cursor is hovering over the button.
+    pub hovered: Option<NineSlice>,
+    /// The texture to draw when the button is active (depressed).
+    pub active: Option<NineSlice>,
+    /// The texture to draw when the button is disabled.
+    pub disabled: Option<NineSlice>,
+}
+
+impl ButtonImages {
+    /// Returns a set of images that draws `normal` in every state.
+    #[must_use]
+    pub const fn new(normal: NineSlice) -> Self {
+        Self {
+            normal,
+            hovered: None,
+            active: None,
+            disabled: None,
+        }
+    }
+
+    /// Sets the texture to draw while hovered and returns self.
+    #[must_use]
+    pub fn hovered(mut self, hovered: NineSlice) -> Self {
+        self.hovered = Some(hovered);
+        self
+    }
+
+    /// Sets the texture to draw while active and returns self.
+    #[must_use]
+    pub fn active(mut self, active: NineSlice) -> Self {
+        self.active = Some(active);
+        self
+    }
+
+    /// Sets the texture to draw while disabled and returns self.
+    #[must_use]
+    pub fn disabled(mut self, disabled: NineSlice) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// Returns the texture to draw for `state`.
+    #[must_use]
+    pub fn for_state(&self, state: VisualState) -> &NineSlice {
+        match state {
+            VisualState::Normal => None,
+            VisualState::Hovered => self.hovered.as_ref(),
+            VisualState::Active => self.active.as_ref(),
+            VisualState::Disabled => self.disabled.as_ref(),
+        }
+        .unwrap_or(&self.normal)
+    }
+}
+
+impl From<NineSlice> for ButtonImages {
+    fn from(normal: NineSlice) -> Self {
+        Self::new(normal)
+    }
+}
+
 /// The effective visual state of an element.
 ///
 /// While an element may be multiple states (e.g., active and hovered), when
@@ -358,6 +455,7 @@ impl Widget for Button {
         fmt.debug_struct("Button")
             .field("content", &self.content)
             .field("kind", &self.kind)
+            .field("images", &self.images)
             .finish()
     }
 
@@ -368,10 +466,19 @@ impl Widget for Button {
         self.update_colors(context, false);
 
         let style = self.current_style(context);
-        context.fill(style.background);
-
         let two_lp_stroke = StrokeOptions::lp_wide(Lp::points(2));
-        context.stroke_outline(style.outline, two_lp_stroke);
+        if let Some(images) = &self.images {
+            let images = images.get_tracking_redraw(context);
+            let area = Rect::from(context.gfx.region().size);
+            context.gfx.draw_nine_slice(
+                images.for_state(Self::visual_style(context)),
+                area,
+                ZeroToOne::ONE,
+            );
+        } else {
+            context.fill(style.background);
+            context.stroke_outline(style.outline, two_lp_stroke);
+        }
 
         if context.focused(true) {
             if current_style == ButtonKind::Transparent {
@@ -513,13 +620,13 @@ impl Widget for Button {
         &mut self,
         _location: Point<Px>,
         context: &mut EventContext<'_>,
-    ) -> Option<CursorIcon> {
+    ) -> Option<MouseCursor> {
         self.update_colors(context, false);
 
         if context.enabled() {
-            Some(CursorIcon::Pointer)
+            Some(MouseCursor::Icon(CursorIcon::Pointer))
         } else {
-            Some(CursorIcon::NotAllowed)
+            Some(MouseCursor::Icon(CursorIcon::NotAllowed))
         }
     }
 
@@ -646,7 +753,7 @@ impl ClickCounter {
 
     /// Notes a single click.
     pub fn click(&mut self, click: Option<ButtonClick>) {
-        let now = Instant::now();
+        let now = clock::now();
         let threshold = self.threshold.get();
         if let Some(last_click) = self.last_click {
             let elapsed = now.saturating_duration_since(last_click);
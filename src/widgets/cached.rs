@@ -0,0 +1,322 @@
+//! Widgets that render their contents into a cached texture.
+use std::sync::Arc;
+use std::task::Poll;
+
+use alot::LotId;
+use figures::units::{Px, UPx};
+use figures::{FloatConversion, IntoSigned, Point, Rect, Size, Zero};
+use image::RgbaImage;
+use parking_lot::Mutex;
+
+use crate::animation::ZeroToOne;
+use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::graphics::{OffscreenRead, OffscreenTarget};
+use crate::value::{CallbackHandle, Destination, Dynamic, Source};
+use crate::widget::{MakeWidget, MountedWidget, Widget, WidgetRef, WrapperWidget};
+use crate::window::WindowLocal;
+use crate::{ConstraintLimit, FitMeasuredSize};
+
+/// A widget that renders its child into a texture, only re-rendering when
+/// needed.
+///
+/// When the cached texture is up-to-date, it is drawn instead of redrawing the
+/// child. This can significantly reduce the cost of drawing expensive, mostly
+/// static contents, such as large [`Grid`](crate::widgets::Grid)s or chart
+/// backgrounds.
+///
+/// The child is re-rendered when:
+///
+/// - The child's size changes.
+/// - The child or any of its descendants is invalidated.
+/// - A value a descendant is tracking for redraws changes, such as a
+///   [`Dynamic`] read using
+///   [`get_tracking_redraw()`](crate::value::Source::get_tracking_redraw).
+/// - [`RenderTarget::invalidate`] is called.
+/// - A source registered with [`refresh_when_changed()`](Self::refresh_when_changed)
+///   changes.
+///
+/// The cached contents can be displayed elsewhere in the same window using a
+/// [`Thumbnail`], or read back into an image using [`RenderTarget::image`].
+#[derive(Debug)]
+pub struct Cached {
+    child: WidgetRef,
+    target: RenderTarget,
+    refresh_callbacks: Vec<CallbackHandle>,
+}
+
+impl Cached {
+    /// Returns a widget that caches the rendered contents of `child`.
+    #[must_use]
+    pub fn new(child: impl MakeWidget) -> Self {
+        Self::with_target(RenderTarget::default(), child)
+    }
+
+    /// Returns a widget that caches the rendered contents of `child` in
+    /// `target`.
+    #[must_use]
+    pub fn with_target(target: RenderTarget, child: impl MakeWidget) -> Self {
+        Self {
+            child: WidgetRef::new(child),
+            target,
+            refresh_callbacks: Vec::new(),
+        }
+    }
+
+    /// Re-renders the child whenever `source` is updated, and returns self.
+    #[must_use]
+    pub fn refresh_when_changed<T>(mut self, source: &impl Source<T>) -> Self
+    where
+        T: Send + 'static,
+    {
+        let target = self.target.clone();
+        self.refresh_callbacks
+            .push(source.for_each(move |_| target.invalidate()));
+        self
+    }
+
+    /// Returns the render target this widget renders into.
+    #[must_use]
+    pub const fn target(&self) -> &RenderTarget {
+        &self.target
+    }
+}
+
+impl WrapperWidget for Cached {
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.child
+    }
+
+    fn mounted(&mut self, context: &mut EventContext<'_>) {
+        // Redraw requests from descendants are converted into invalidations
+        // so that the cached texture is refreshed.
+        context.tree.set_caches_contents(context.widget().node_id);
+    }
+
+    fn redraw_child(
+        &mut self,
+        child: &MountedWidget,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let refresh = self.target.refresh.get_tracking_redraw(context);
+        let invalidations = context.tree.invalidation_generation(child.node_id);
+        let Some(layout) = child.last_layout() else {
+            return;
+        };
+        let region = layout - context.gfx.region().origin;
+
+        let mut renders = self.target.renders.lock();
+        let cached = renders.entry(context).or_default();
+        let up_to_date = cached.refresh == refresh
+            && cached.invalidations == invalidations
+            && cached.size == layout.size
+            && cached.offscreen.texture().is_some();
+        if up_to_date {
+            // The child's widgets must still be noted as rendered so that
+            // they can receive input.
+            context.tree.note_widgets_rendered(&cached.rendered_widgets);
+        } else {
+            let rendered_before = context.tree.rendered_widget_count();
+            if !context.redraw_offscreen(child, &mut cached.offscreen) {
+                drop(renders);
+                context.for_other(child).redraw();
+                return;
+            }
+            cached.rendered_widgets = context.tree.rendered_widgets_since(rendered_before);
+            cached.refresh = refresh;
+            cached.invalidations = invalidations;
+            cached.size = layout.size;
+        }
+
+        let texture_size = cached.offscreen.texture().map(|texture| {
+            context.gfx.draw_texture(texture, region, ZeroToOne::ONE);
+            texture.size()
+        });
+        if !up_to_date && self.target.capturing.get() {
+            // Any read still in progress is for outdated contents.
+            cached.read = context.gfx.read_offscreen(&cached.offscreen);
+        }
+        // The copy is finished on a later frame rather than blocking this one
+        // until the GPU is done.
+        let image = match cached
+            .read
+            .as_ref()
+            .map(|read| context.gfx.finish_offscreen_read(read))
+        {
+            Some(Poll::Ready(image)) => {
+                cached.read = None;
+                Some(image)
+            }
+            Some(Poll::Pending) => {
+                context.set_needs_redraw();
+                None
+            }
+            None => None,
+        };
+        drop(renders);
+
+        if let Some(image) = image {
+            self.target.image.set(image);
+        }
+
+        if !up_to_date {
+            if let Some(texture_size) = texture_size {
+                if self.target.size.get() != texture_size {
+                    self.target.size.set(texture_size);
+                }
+            }
+            self.target
+                .rendered
+                .map_mut(|mut rendered| *rendered = rendered.wrapping_add(1));
+        }
+    }
+
+    fn summarize(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Cached")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A texture that a [`Cached`] widget renders into.
+///
+/// Render targets are cheap to clone, and each clone refers to the same
+/// texture. This allows a [`Thumbnail`] to display the contents of a
+/// [`Cached`] widget.
+#[derive(Debug, Clone, Default)]
+pub struct RenderTarget {
+    renders: Arc<Mutex<WindowLocal<CachedRender>>>,
+    refresh: Dynamic<usize>,
+    rendered: Dynamic<usize>,
+    size: Dynamic<Size<UPx>>,
+    capturing: Dynamic<bool>,
+    image: Dynamic<Option<RgbaImage>>,
+}
+
+impl RenderTarget {
+    /// Returns a new, empty render target.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forces the contents of this target to be re-rendered the next time it
+    /// is drawn.
+    pub fn invalidate(&self) {
+        self.refresh
+            .map_mut(|mut refresh| *refresh = refresh.wrapping_add(1));
+    }
+
+    /// Returns a dynamic that contains the contents of this target as an image.
+    ///
+    /// Once this function has been called, the texture is copied from the GPU
+    /// each time the contents are re-rendered. The copy is performed in the
+    /// background, so the image is updated a frame or more after the contents
+    /// are rendered. Unlike a [`Thumbnail`], the image can be used outside of
+    /// the window that rendered it.
+    #[must_use]
+    pub fn image(&self) -> Dynamic<Option<RgbaImage>> {
+        if !self.capturing.replace(true).unwrap_or(true) {
+            self.invalidate();
+        }
+        self.image.clone()
+    }
+
+    /// Returns a widget that displays the contents of this render target,
+    /// scaled to fit while preserving its aspect ratio.
+    #[must_use]
+    pub fn thumbnail(&self) -> Thumbnail {
+        Thumbnail::new(self.clone())
+    }
+}
+
+#[derive(Debug, Default)]
+struct CachedRender {
+    offscreen: OffscreenTarget,
+    size: Size<Px>,
+    refresh: usize,
+    invalidations: usize,
+    rendered_widgets: Vec<LotId>,
+    read: Option<OffscreenRead>,
+}
+
+/// A widget that displays the contents of a [`RenderTarget`].
+///
+/// The contents are scaled to fit the widget while preserving the aspect
+/// ratio. Thumbnails only display contents that were rendered in the same
+/// window.
+#[derive(Debug)]
+pub struct Thumbnail {
+    target: RenderTarget,
+}
+
+impl Thumbnail {
+    /// Returns a widget that displays the contents of `target`.
+    #[must_use]
+    pub const fn new(target: RenderTarget) -> Self {
+        Self { target }
+    }
+
+    fn fit_size(texture_size: Size<UPx>, within: Size<UPx>) -> Size<UPx> {
+        if texture_size.width == 0 || texture_size.height == 0 {
+            return Size::ZERO;
+        }
+        let scale = (within.width.into_float() / texture_size.width.into_float())
+            .min(within.height.into_float() / texture_size.height.into_float())
+            .min(1.);
+        Size::new(
+            UPx::from(texture_size.width.into_float() * scale),
+            UPx::from(texture_size.height.into_float() * scale),
+        )
+    }
+}
+
+impl Widget for Thumbnail {
+    fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
+        context.redraw_when_changed(&self.target.rendered);
+        let renders = self.target.renders.lock();
+        let Some(texture) = renders
+            .get(context)
+            .and_then(|cached| cached.offscreen.texture())
+        else {
+            return;
+        };
+        let available = context.gfx.size();
+        let size = Self::fit_size(texture.size(), available);
+        let origin = Point::new(
+            (available.width - size.width) / 2,
+            (available.height - size.height) / 2,
+        );
+        context.gfx.draw_texture(
+            texture,
+            Rect::new(origin, size).into_signed(),
+            ZeroToOne::ONE,
+        );
+    }
+
+    fn layout(
+        &mut self,
+        available_space: Size<ConstraintLimit>,
+        context: &mut LayoutContext<'_, '_, '_, '_>,
+    ) -> Size<UPx> {
+        let texture_size = self.target.size.get_tracking_invalidate(context);
+        let fit = Self::fit_size(texture_size, available_space.map(ConstraintLimit::max));
+        available_space.fit_measured(fit)
+    }
+}
+
+#[test]
+fn thumbnail_fit() {
+    assert_eq!(
+        Thumbnail::fit_size(Size::upx(200, 100), Size::upx(100, 100)),
+        Size::upx(100, 50)
+    );
+    // Thumbnails are never scaled up.
+    assert_eq!(
+        Thumbnail::fit_size(Size::upx(20, 10), Size::upx(100, 100)),
+        Size::upx(20, 10)
+    );
+    assert_eq!(
+        Thumbnail::fit_size(Size::upx(0, 10), Size::upx(100, 100)),
+        Size::ZERO
+    );
+}
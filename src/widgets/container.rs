@@ -12,6 +12,7 @@ use crate::styles::components::{CornerRadius, IntrinsicPadding, Opacity, Surface
 use crate::styles::{Component, ContainerLevel, Dimension, Edges, RequireInvalidation, Styles};
 use crate::value::{Dynamic, IntoValue, Source, Value};
 use crate::widget::{MakeWidget, RootBehavior, Widget, WidgetInstance, WidgetRef};
+use crate::widgets::image::NineSlice;
 use crate::ConstraintLimit;
 
 /// A visual container widget, optionally applying padding and a background
@@ -43,6 +44,11 @@ pub struct Container {
     pub padding: Option<Value<Edges<Dimension>>>,
     /// The shadow to apply behind the container's background.
     pub shadow: Value<ContainerShadow>,
+    /// A texture to draw as the background instead of a solid color.
+    ///
+    /// When set, the image is drawn across the container's entire area and
+    /// [`shadow`](Self::shadow) is not drawn.
+    pub background_image: Option<Value<NineSlice>>,
     child: WidgetRef,
     applied_background: Option<EffectiveBackground>,
 }
@@ -111,6 +117,7 @@ impl Container {
             applied_background: None,
             background: Value::default(),
             shadow: Value::default(),
+            background_image: None,
             child: WidgetRef::new(child),
         }
     }
@@ -158,6 +165,17 @@ impl Container {
         self
     }
 
+    /// Draws `image` as this container's background instead of a solid
+    /// color, and then returns the updated container.
+    ///
+    /// The background color selection still determines the
+    /// [`ContainerLevel`] used by nested containers.
+    #[must_use]
+    pub fn background_image(mut self, image: impl IntoValue<NineSlice>) -> Self {
+        self.background_image = Some(image.into_value());
+        self
+    }
+
     fn padding(&self, context: &GraphicsContext<'_, '_, '_, '_>) -> Edges<Px> {
         match &self.padding {
             Some(padding) => padding.get(),
@@ -202,6 +220,7 @@ impl Widget for Container {
             .field("background", &self.background)
             .field("padding", &self.padding)
             .field("shadow", &self.shadow)
+            .field("background_image", &self.background_image)
             .field("child", &self.child)
             .finish()
     }
@@ -220,7 +239,11 @@ impl Widget for Container {
 
         let background = self.effective_background_color(context);
         let background = background.with_alpha_f32(background.alpha_f32() * *opacity);
-        if background.alpha() > 0 {
+        if let Some(image) = &self.background_image {
+            let image = image.get_tracking_redraw(context);
+            let area = Rect::from(context.gfx.region().size);
+            context.gfx.draw_nine_slice(&image, area, opacity);
+        } else if background.alpha() > 0 {
             let shadow = self
                 .shadow
                 .get_tracking_invalidate(context)
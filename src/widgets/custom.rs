@@ -3,10 +3,10 @@ use std::fmt::Debug;
 use figures::units::Px;
 use figures::{Point, Size};
 use kludgine::app::winit::event::{Ime, MouseButton, MouseScrollDelta, TouchPhase};
-use kludgine::app::winit::window::CursorIcon;
 use kludgine::Color;
 
 use crate::context::{EventContext, GraphicsContext, LayoutContext, WidgetContext};
+use crate::cursor::MouseCursor;
 use crate::styles::VisualOrder;
 use crate::value::{IntoValue, Value};
 use crate::widget::{EventHandling, MakeWidget, WidgetRef, WrappedLayout, WrapperWidget, IGNORED};
@@ -35,7 +35,7 @@ pub struct Custom {
     adjust_child: Option<Box<dyn AdjustChildConstraintsFunc>>,
     position_child: Option<Box<dyn PositionChildFunc>>,
     hit_test: Option<Box<dyn OneParamEventFunc<Point<Px>, bool>>>,
-    hover: Option<Box<dyn OneParamEventFunc<Point<Px>, Option<CursorIcon>>>>,
+    hover: Option<Box<dyn OneParamEventFunc<Point<Px>, Option<MouseCursor>>>>,
     mouse_down:
         Option<Box<dyn ThreeParamEventFunc<Point<Px>, DeviceId, MouseButton, EventHandling>>>,
     mouse_drag: Option<Box<dyn ThreeParamEventFunc<Point<Px>, DeviceId, MouseButton>>>,
@@ -323,13 +323,18 @@ impl Custom {
     /// Invokes `hover` when a mouse cursor is above this widget.
     ///
     /// This callback corresponds to [`WrapperWidget::hover`].
-    pub fn on_hover<Hover>(mut self, hover: Hover) -> Self
+    pub fn on_hover<Hover, Cursor>(mut self, mut hover: Hover) -> Self
     where
         Hover: Send
             + 'static
-            + for<'context> FnMut(Point<Px>, &mut EventContext<'context>) -> Option<CursorIcon>,
+            + for<'context> FnMut(Point<Px>, &mut EventContext<'context>) -> Option<Cursor>,
+        Cursor: Into<MouseCursor>,
     {
-        self.hover = Some(Box::new(hover));
+        self.hover = Some(Box::new(
+            move |location: Point<Px>, context: &mut EventContext<'_>| {
+                hover(location, context).map(Into::into)
+            },
+        ));
         self
     }
 
@@ -512,7 +517,11 @@ impl WrapperWidget for Custom {
         }
     }
 
-    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<MouseCursor> {
         let hover = self.hover.as_mut()?;
         hover.invoke(location, context)
     }
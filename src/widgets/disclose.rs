@@ -11,6 +11,7 @@ use kludgine::{Color, DrawableExt};
 use super::button::{ButtonActiveBackground, ButtonBackground, ButtonHoverBackground};
 use crate::animation::{AnimationHandle, AnimationTarget, Spawn};
 use crate::context::{EventContext, LayoutContext};
+use crate::cursor::MouseCursor;
 use crate::styles::components::{HighlightColor, IntrinsicPadding, LineHeight, OutlineColor};
 use crate::styles::Dimension;
 use crate::value::{Destination, Dynamic, IntoDynamic, IntoValue, Source, Value};
@@ -297,14 +298,18 @@ impl Widget for DiscloseIndicator {
         }
     }
 
-    fn hover(&mut self, location: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(
+        &mut self,
+        location: Point<Px>,
+        context: &mut EventContext<'_>,
+    ) -> Option<MouseCursor> {
         let hovering = self.hit_test(location, context);
         if self.hovering_indicator != hovering {
             context.set_needs_redraw();
             self.hovering_indicator = hovering;
         }
 
-        hovering.then_some(CursorIcon::Pointer)
+        hovering.then_some(MouseCursor::Icon(CursorIcon::Pointer))
     }
 
     fn unhover(&mut self, context: &mut EventContext<'_>) {
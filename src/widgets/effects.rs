@@ -0,0 +1,287 @@
+//! Widgets that apply visual effects to their contents.
+use std::f32::consts::PI;
+
+use figures::units::Px;
+use figures::{Angle, FloatConversion, Point, Rect, ScreenScale, Zero};
+use intentional::Assert;
+use kludgine::shapes::{CornerRadii, Shape};
+use kludgine::{Color, Drawable, Texture};
+
+use crate::animation::{LinearInterpolate, ZeroToOne};
+use crate::context::GraphicsContext;
+use crate::graphics::{Graphics, OffscreenTarget};
+use crate::styles::Dimension;
+use crate::value::{IntoValue, Value};
+use crate::widget::{MakeWidget, MountedWidget, WidgetRef, WrapperWidget};
+use crate::window::WindowLocal;
+
+/// The number of samples drawn on each ring of a blur.
+const BLUR_SAMPLES_PER_RING: u8 = 8;
+/// The number of rings of samples drawn when blurring.
+const BLUR_RINGS: u8 = 2;
+
+/// A widget that renders its child with group opacity, a [`Transform`],
+/// and/or a blur.
+///
+/// When any effect is active, the child is first rendered into an offscreen
+/// texture which is then drawn into the window. This ensures that:
+///
+/// - Group opacity is applied once to the combined result, preventing
+///   overlapping children from being blended multiple times.
+/// - Transformations and blurs affect everything the child draws, including
+///   text.
+///
+/// When no effects are active, the child is drawn directly.
+///
+/// Effects are purely visual. Transformed contents are clipped to this
+/// widget's bounds, and input events are still delivered using the child's
+/// untransformed layout.
+///
+/// Effects only apply to the child's own contents. In particular, a blur
+/// does not affect what is drawn beneath this widget. To blur the contents
+/// behind a [`Modal`](crate::widgets::layers::Modal), use
+/// [`Modal::backdrop_blur`](crate::widgets::layers::Modal::backdrop_blur).
+#[derive(Debug)]
+pub struct Effects {
+    child: WidgetRef,
+    opacity: Value<ZeroToOne>,
+    transform: Value<Transform>,
+    blur: Value<Dimension>,
+    offscreen: WindowLocal<OffscreenTarget>,
+    blurred: WindowLocal<OffscreenTarget>,
+}
+
+impl Effects {
+    /// Returns a new widget that draws `child` without any effects applied.
+    #[must_use]
+    pub fn new(child: impl MakeWidget) -> Self {
+        Self {
+            child: WidgetRef::new(child),
+            opacity: Value::Constant(ZeroToOne::ONE),
+            transform: Value::Constant(Transform::IDENTITY),
+            blur: Value::Constant(Dimension::ZERO),
+            offscreen: WindowLocal::default(),
+            blurred: WindowLocal::default(),
+        }
+    }
+
+    /// Sets the opacity to apply to the child as a single group, and returns
+    /// self.
+    #[must_use]
+    pub fn group_opacity(mut self, opacity: impl IntoValue<ZeroToOne>) -> Self {
+        self.opacity = opacity.into_value();
+        self
+    }
+
+    /// Sets the transformation to apply to the child, and returns self.
+    #[must_use]
+    pub fn transform(mut self, transform: impl IntoValue<Transform>) -> Self {
+        self.transform = transform.into_value();
+        self
+    }
+
+    /// Sets the radius of the blur to apply to the child, and returns self.
+    ///
+    /// Blurring is performed by drawing the child's contents multiple times
+    /// at offsets within `radius`. This produces a soft, "frosted" look
+    /// rather than a true gaussian blur.
+    ///
+    /// Only the child's contents are blurred. Contents drawn beneath this
+    /// widget are not affected.
+    #[must_use]
+    pub fn blur(mut self, radius: impl IntoValue<Dimension>) -> Self {
+        self.blur = radius.into_value();
+        self
+    }
+}
+
+impl WrapperWidget for Effects {
+    fn child_mut(&mut self) -> &mut WidgetRef {
+        &mut self.child
+    }
+
+    fn redraw_child(
+        &mut self,
+        child: &MountedWidget,
+        context: &mut GraphicsContext<'_, '_, '_, '_>,
+    ) {
+        let opacity = self.opacity.get_tracking_redraw(context);
+        let transform = self.transform.get_tracking_redraw(context);
+        let blur = self
+            .blur
+            .get_tracking_redraw(context)
+            .into_px(context.gfx.scale())
+            .max(Px::ZERO);
+        if opacity == ZeroToOne::ONE && transform.is_identity() && blur == 0 {
+            context.for_other(child).redraw();
+            return;
+        }
+
+        let Some(layout) = child.last_layout() else {
+            return;
+        };
+        let region = layout - context.gfx.region().origin;
+        let target = self.offscreen.entry(context).or_default();
+        if !context.redraw_offscreen(child, target) {
+            context.for_other(child).redraw();
+            return;
+        }
+        let mut texture = target.texture().assert("rendered above");
+
+        if blur > 0 {
+            // The samples are averaged at full opacity so that the group
+            // opacity is only applied once, to the blurred result.
+            let blurred = self.blurred.entry(context).or_default();
+            if context.gfx.render_offscreen(blurred, region, |mut gfx| {
+                draw_blur_samples(&mut gfx, texture, Rect::from(region.size), blur);
+            }) {
+                texture = blurred.texture().assert("rendered above");
+            }
+        }
+
+        draw_effect_texture(context, texture, region, transform, opacity);
+    }
+
+    fn summarize(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Effects")
+            .field("opacity", &self.opacity)
+            .field("transform", &self.transform)
+            .field("blur", &self.blur)
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+/// Draws `texture` into `destination` multiple times at offsets within
+/// `radius`, averaging the samples.
+pub(crate) fn draw_blur_samples(
+    gfx: &mut Graphics<'_, '_, '_>,
+    texture: &Texture,
+    destination: Rect<Px>,
+    radius: Px,
+) {
+    // Average the samples by drawing each successive sample with a decreasing
+    // opacity.
+    let radius = radius.into_float();
+    let mut samples = 1_u8;
+    gfx.draw_texture(texture, destination, ZeroToOne::ONE);
+    for ring in 1..=BLUR_RINGS {
+        let distance = radius * f32::from(ring) / f32::from(BLUR_RINGS);
+        for sample in 0..BLUR_SAMPLES_PER_RING {
+            // Stagger each ring to avoid samples lining up.
+            let angle = (f32::from(sample) + f32::from(ring) / 2.)
+                / f32::from(BLUR_SAMPLES_PER_RING)
+                * 2.
+                * PI;
+            let offset = Point::new(
+                Px::from(angle.cos() * distance),
+                Px::from(angle.sin() * distance),
+            );
+            samples += 1;
+            gfx.draw_texture(
+                texture,
+                destination + offset,
+                ZeroToOne::new(1. / f32::from(samples)),
+            );
+        }
+    }
+}
+
+fn draw_effect_texture(
+    context: &mut GraphicsContext<'_, '_, '_, '_>,
+    texture: &Texture,
+    region: Rect<Px>,
+    transform: Transform,
+    opacity: ZeroToOne,
+) {
+    if transform.is_identity() {
+        context.gfx.draw_texture(texture, region, opacity);
+        return;
+    }
+
+    // The shape is centered on the origin so that rotation and scaling are
+    // performed around the center of the widget.
+    let half_size = Point::new(region.size.width / 2, region.size.height / 2);
+    let shape = Shape::textured_round_rect(
+        Rect::new(-half_size, region.size),
+        CornerRadii::ZERO,
+        Rect::from(texture.size()),
+        Color::WHITE,
+    );
+    let mut drawable = Drawable::from(&shape);
+    drawable.translation = region.origin + half_size + transform.translation;
+    drawable.rotation = Some(transform.rotation);
+    drawable.scale = Some(transform.scale);
+    context.gfx.draw_textured_shape(drawable, texture, opacity);
+}
+
+/// A 2D transformation applied when drawing a widget.
+///
+/// Scaling and rotation are performed around the center of the widget. This
+/// type implements [`LinearInterpolate`], allowing transformations to be
+/// animated.
+#[derive(Debug, Clone, Copy, PartialEq, LinearInterpolate)]
+pub struct Transform {
+    /// The offset to draw the widget at.
+    pub translation: Point<Px>,
+    /// The scaling factor to draw the widget with.
+    pub scale: f32,
+    /// The amount to rotate the widget by.
+    pub rotation: Angle,
+}
+
+impl Transform {
+    /// A transformation that has no effect.
+    pub const IDENTITY: Self = Self {
+        translation: Point::ZERO,
+        scale: 1.,
+        rotation: Angle::MIN,
+    };
+
+    /// Returns this transformation with its translation set to `translation`.
+    #[must_use]
+    pub const fn translated_by(mut self, translation: Point<Px>) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Returns this transformation with its scale set to `scale`.
+    #[must_use]
+    pub const fn scaled_by(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns this transformation with its rotation set to `rotation`.
+    #[must_use]
+    pub const fn rotated_by(mut self, rotation: Angle) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Returns true if this transformation has no effect.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.translation == Point::ZERO
+            && (self.scale - 1.).abs() < f32::EPSILON
+            && (self.rotation.into_degrees::<f32>() % 360.).abs() < f32::EPSILON
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[test]
+fn transform_identity() {
+    assert!(Transform::IDENTITY.is_identity());
+    assert!(Transform::default()
+        .rotated_by(Angle::degrees(360))
+        .is_identity());
+    assert!(!Transform::IDENTITY.scaled_by(2.).is_identity());
+    assert!(!Transform::IDENTITY
+        .translated_by(Point::new(Px::new(1), Px::ZERO))
+        .is_identity());
+}
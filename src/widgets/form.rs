@@ -0,0 +1,246 @@
+//! Widgets for editing structured data.
+//!
+//! The [`Form`] derive macro generates a form for a struct with named fields.
+//! Each field is edited using its type's [`FormField`] implementation:
+//!
+//! | Type                  | Editor                                     |
+//! |-----------------------|--------------------------------------------|
+//! | `String`              | [`Input`](crate::widgets::Input)           |
+//! | Numeric types         | [`Input`](crate::widgets::Input), parsed   |
+//! | `bool`                | [`Checkbox`](crate::widgets::Checkbox)     |
+//! | `#[derive(Form)]` enum | [`Radio`](crate::widgets::Radio)s or [`Select`](crate::widgets::Select)s |
+//! | `#[derive(Form)]` struct | A nested form                           |
+//!
+//! Each field can be customized using the `form` attribute:
+//!
+//! - `label = "Label"`: The label displayed next to the field. By default, the
+//!   field's name is used.
+//! - `hint = "Hint"`: A hint displayed below the field.
+//! - `range = 0..=100`: Validates that the value is contained in the range.
+//! - `validator = function`: Validates the value using a function that
+//!   accepts a reference to the field's value and returns a `Result<(), E>`
+//!   where `E` implements `Display`.
+//! - `skip`: The field is not included in the form.
+//!
+//! Fieldless enums can also derive [`Form`]. Each variant is shown as a
+//! [`Radio`](crate::widgets::Radio), or a [`Select`](crate::widgets::Select)
+//! if the enum has the `#[form(select)]` attribute. Variants support the
+//! `label` attribute. Using `select` anywhere other than on an enum is a
+//! compilation error.
+//!
+//! ```rust
+//! use cushy::value::{Dynamic, Validations};
+//! use cushy::widgets::form::Form;
+//!
+//! #[derive(Form, Default, Clone, PartialEq)]
+//! struct Profile {
+//!     #[form(hint = "* required", validator = required)]
+//!     name: String,
+//!     #[form(range = 13..=130)]
+//!     age: u8,
+//!     #[form(label = "Subscribe to the newsletter")]
+//!     subscribed: bool,
+//!     theme: Theme,
+//! }
+//!
+//! #[derive(Form, Default, Clone, Copy, PartialEq)]
+//! #[form(select)]
+//! enum Theme {
+//!     Light,
+//!     #[default]
+//!     Dark,
+//! }
+//!
+//! #[allow(clippy::ptr_arg)]
+//! fn required(value: &String) -> Result<(), &'static str> {
+//!     if value.is_empty() {
+//!         Err("This field cannot be empty")
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let profile = Dynamic::<Profile>::default();
+//! let validations = Validations::default();
+//! let form = Profile::form(&profile, &validations);
+//! ```
+
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+
+pub use cushy_macros::Form;
+
+use crate::value::{Dynamic, IntoDynamic, IntoValue, MapEach, Validation, Validations, Value};
+use crate::widget::{MakeWidget, WidgetInstance};
+use crate::widgets::checkbox::Checkable;
+use crate::widgets::grid::{GridDimension, GridWidgets};
+use crate::widgets::input::InputValue;
+use crate::widgets::{Grid, Validated};
+
+/// A type that can be edited using a generated form.
+///
+/// This trait can be derived for structs with named fields. See the [module
+/// documentation](self) for more information.
+pub trait Form: FormField {
+    /// Returns a widget that edits each field of `value`.
+    ///
+    /// All validations of the form's fields are added to `validations`.
+    fn form(value: &Dynamic<Self>, validations: &Validations) -> WidgetInstance;
+}
+
+/// A type that can be edited by a field in a [`Form`].
+pub trait FormField: Sized + Send + 'static {
+    /// Returns an editor for `value`.
+    ///
+    /// Any validations required to ensure the user's input is valid are added
+    /// to `validations`.
+    fn form_field(value: &Dynamic<Self>, validations: &Validations) -> FieldEditor;
+}
+
+/// A widget that edits a single field of a [`Form`].
+#[must_use]
+pub struct FieldEditor {
+    widget: WidgetInstance,
+    validation: Option<Dynamic<Validation>>,
+    hint: Option<Value<String>>,
+}
+
+impl FieldEditor {
+    /// Returns an editor that displays `widget`.
+    pub fn new(widget: impl MakeWidget) -> Self {
+        Self {
+            widget: widget.make_widget(),
+            validation: None,
+            hint: None,
+        }
+    }
+
+    /// Displays `validation` with this editor.
+    ///
+    /// If this editor already has a validation, the two validations are
+    /// [combined](Validation::and).
+    pub fn validated_by(mut self, validation: impl IntoDynamic<Validation>) -> Self {
+        let validation = validation.into_dynamic();
+        self.validation = Some(match self.validation {
+            Some(existing) => (&existing, &validation).map_each(|(a, b)| a.and(b)),
+            None => validation,
+        });
+        self
+    }
+
+    /// Displays `hint` below this editor when there is no validation error.
+    pub fn hint(mut self, hint: impl IntoValue<String>) -> Self {
+        self.hint = Some(hint.into_value());
+        self
+    }
+}
+
+impl MakeWidget for FieldEditor {
+    fn make_widget(self) -> WidgetInstance {
+        if self.validation.is_none() && self.hint.is_none() {
+            return self.widget;
+        }
+
+        let validated = Validated::new(
+            self.validation
+                .unwrap_or_else(|| Dynamic::new(Validation::None)),
+            self.widget,
+        );
+        match self.hint {
+            Some(hint) => validated.hint(hint).make_widget(),
+            None => validated.make_widget(),
+        }
+    }
+}
+
+/// A two-column grid of labeled [`FieldEditor`]s.
+#[derive(Default)]
+#[must_use]
+pub struct FormFields(GridWidgets<2>);
+
+impl FormFields {
+    /// Returns an empty list of fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `editor` labeled with `label`.
+    pub fn field(self, label: impl MakeWidget, editor: FieldEditor) -> Self {
+        Self(self.0.and((label, editor)))
+    }
+}
+
+impl MakeWidget for FormFields {
+    fn make_widget(self) -> WidgetInstance {
+        Grid::from_rows(self.0)
+            .dimensions([
+                GridDimension::FitContent,
+                GridDimension::Fractional { weight: 1 },
+            ])
+            .make_widget()
+    }
+}
+
+/// Returns a validation function that checks that a value is contained in
+/// `range`.
+///
+/// This function is used by the `range` attribute of the [`Form`] derive.
+pub fn in_range<T, Range>(
+    range: Range,
+) -> impl for<'a> FnMut(&'a T) -> Result<(), String> + Send + 'static
+where
+    T: PartialOrd + Display + 'static,
+    Range: RangeBounds<T> + Send + 'static,
+{
+    move |value| {
+        if range.contains(value) {
+            return Ok(());
+        }
+
+        let start = match range.start_bound() {
+            Bound::Included(start) => Some(format!("at least {start}")),
+            Bound::Excluded(start) => Some(format!("greater than {start}")),
+            Bound::Unbounded => None,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => Some(format!("at most {end}")),
+            Bound::Excluded(end) => Some(format!("less than {end}")),
+            Bound::Unbounded => None,
+        };
+        Err(match (start, end) {
+            (Some(start), Some(end)) => format!("Must be {start} and {end}"),
+            (Some(bound), None) | (None, Some(bound)) => format!("Must be {bound}"),
+            (None, None) => unreachable!("unbounded ranges contain all values"),
+        })
+    }
+}
+
+impl FormField for String {
+    fn form_field(value: &Dynamic<Self>, _validations: &Validations) -> FieldEditor {
+        FieldEditor::new(value.to_input())
+    }
+}
+
+impl FormField for bool {
+    fn form_field(value: &Dynamic<Self>, _validations: &Validations) -> FieldEditor {
+        FieldEditor::new(value.to_checkbox(""))
+    }
+}
+
+macro_rules! impl_numeric_form_field {
+    ($($type:ident),+) => {
+        $(
+            impl FormField for $type {
+                fn form_field(value: &Dynamic<Self>, validations: &Validations) -> FieldEditor {
+                    let text = value.linked_string();
+                    let validation = validations.validate(&text, |text: &String| {
+                        text.parse::<$type>().map(|_| ())
+                    });
+                    FieldEditor::new(text.into_input()).validated_by(validation)
+                }
+            }
+        )+
+    };
+}
+
+impl_numeric_form_field!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
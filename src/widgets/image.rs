@@ -1,5 +1,7 @@
 //! A widget that displays an image/texture.
 
+use std::sync::Arc;
+
 use figures::units::{Px, UPx};
 use figures::{FloatConversion, IntoSigned, IntoUnsigned, Point, Rect, ScreenScale, Size, Zero};
 use kludgine::shapes::{CornerRadii, Shape};
@@ -9,7 +11,7 @@ use kludgine::{
 
 use crate::animation::ZeroToOne;
 use crate::context::{LayoutContext, Trackable};
-use crate::styles::Dimension;
+use crate::styles::{Dimension, Edges};
 use crate::value::{IntoValue, Source, Value};
 use crate::widget::Widget;
 use crate::ConstraintLimit;
@@ -272,3 +274,325 @@ define_components! {
         ImageCornerRadius(CornerRadii<Dimension>, "corner_radius", CornerRadii::ZERO)
     }
 }
+
+/// A texture that is split into nine regions so that it can be drawn at any
+/// size without distorting its corners.
+///
+/// This technique is also known as nine-patch scaling. The `insets` divide the
+/// texture into four corners, four edges, and a center. When drawn:
+///
+/// - The corners are drawn without scaling.
+/// - The top and bottom edges are stretched or tiled horizontally.
+/// - The left and right edges are stretched or tiled vertically.
+/// - The center is stretched or tiled in both directions.
+///
+/// Insets are measured in texture pixels, and the borders are drawn using the
+/// window's DPI scale multiplied by [`scale`](Self::scale).
+#[derive(Debug, Clone)]
+pub struct NineSlice {
+    /// The texture to draw.
+    pub texture: Arc<AnyTexture>,
+    /// The size of each border of the texture, in texture pixels.
+    pub insets: Edges<UPx>,
+    /// Controls how the edges and center are filled.
+    pub mode: SliceMode,
+    /// An additional scaling factor to apply to the borders.
+    pub scale: f32,
+}
+
+impl NineSlice {
+    /// Returns a nine-slice texture that divides `texture` using `insets`.
+    pub fn new(texture: impl Into<AnyTexture>, insets: impl Into<Edges<UPx>>) -> Self {
+        Self {
+            texture: Arc::new(texture.into()),
+            insets: insets.into(),
+            mode: SliceMode::default(),
+            scale: 1.,
+        }
+    }
+
+    /// Tiles the edges and center of the texture rather than stretching them,
+    /// and returns self.
+    #[must_use]
+    pub fn tiled(mut self) -> Self {
+        self.mode = SliceMode::Tile;
+        self
+    }
+
+    /// Sets the additional scaling factor for the borders and returns self.
+    #[must_use]
+    pub fn scaled(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the pairs of destination and texture rectangles needed to draw
+    /// this texture within `destination`.
+    ///
+    /// `scale` is the number of destination pixels per texture pixel.
+    pub(crate) fn slices(
+        &self,
+        texture_size: Size<UPx>,
+        destination: Rect<Px>,
+        scale: f32,
+    ) -> Vec<(Rect<Px>, Rect<UPx>)> {
+        let tile = matches!(self.mode, SliceMode::Tile).then_some(scale);
+        let columns = slice_axis(
+            destination.origin.x,
+            destination.size.width,
+            texture_size.width,
+            self.insets.left,
+            self.insets.right,
+            scale,
+            tile,
+        );
+        let rows = slice_axis(
+            destination.origin.y,
+            destination.size.height,
+            texture_size.height,
+            self.insets.top,
+            self.insets.bottom,
+            scale,
+            tile,
+        );
+
+        let mut slices = Vec::with_capacity(columns.len() * rows.len());
+        for row in &rows {
+            for column in &columns {
+                slices.push((
+                    Rect::from_extents(
+                        Point::new(column.dest_start, row.dest_start),
+                        Point::new(column.dest_end, row.dest_end),
+                    ),
+                    Rect::from_extents(
+                        Point::new(column.src_start, row.src_start),
+                        Point::new(column.src_end, row.src_end),
+                    ),
+                ));
+            }
+        }
+        slices
+    }
+}
+
+impl PartialEq for NineSlice {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.texture, &other.texture)
+            && self.insets == other.insets
+            && self.mode == other.mode
+            && self.scale.total_cmp(&other.scale).is_eq()
+    }
+}
+
+/// A strategy for filling the edges and center of a [`NineSlice`].
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SliceMode {
+    /// The edges and center are stretched to fill the available space.
+    #[default]
+    Stretch,
+    /// The edges and center are repeated to fill the available space.
+    ///
+    /// If a region would need more than 64 tiles along either axis, it is
+    /// stretched along that axis instead. This keeps the number of quads drawn
+    /// for textures with very small edges bounded.
+    Tile,
+}
+
+/// The maximum number of tiles along a single axis before [`SliceMode::Tile`]
+/// falls back to stretching.
+const MAX_TILES_PER_AXIS: f32 = 64.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AxisSlice {
+    dest_start: Px,
+    dest_end: Px,
+    src_start: UPx,
+    src_end: UPx,
+}
+
+fn slice_axis(
+    origin: Px,
+    length: Px,
+    texture_length: UPx,
+    start_inset: UPx,
+    end_inset: UPx,
+    scale: f32,
+    tile: Option<f32>,
+) -> Vec<AxisSlice> {
+    let start_inset = start_inset.min(texture_length);
+    let end_inset = end_inset.min(texture_length - start_inset);
+
+    // Borders are shrunk proportionally if they do not fit within the
+    // destination.
+    let mut dest_start_inset = start_inset.into_float() * scale;
+    let mut dest_end_inset = end_inset.into_float() * scale;
+    let available = length.into_float().max(0.);
+    let border_total = dest_start_inset + dest_end_inset;
+    if border_total > available {
+        let shrink = available / border_total;
+        dest_start_inset *= shrink;
+        dest_end_inset *= shrink;
+    }
+    let dest_start_inset = Px::from(dest_start_inset.round());
+    let dest_end_inset = Px::from(dest_end_inset.round());
+
+    let middle_start = origin + dest_start_inset;
+    let middle_end = (origin + length - dest_end_inset).max(middle_start);
+    let mut slices = Vec::new();
+    let mut push = |dest_start: Px, dest_end: Px, src_start: UPx, src_end: UPx| {
+        if dest_end > dest_start && src_end > src_start {
+            slices.push(AxisSlice {
+                dest_start,
+                dest_end,
+                src_start,
+                src_end,
+            });
+        }
+    };
+
+    push(origin, middle_start, UPx::ZERO, start_inset);
+
+    let src_middle_start = start_inset;
+    let src_middle_end = texture_length - end_inset;
+    let tiling = tile
+        .filter(|_| src_middle_end > src_middle_start)
+        .map(|tile_scale| {
+            let src_length = (src_middle_end - src_middle_start).into_float();
+            (src_length, (src_length * tile_scale).max(1.))
+        })
+        .filter(|(_, tile_length)| {
+            (middle_end - middle_start).into_float() / tile_length <= MAX_TILES_PER_AXIS
+        });
+    match tiling {
+        Some((src_length, tile_length)) => {
+            let mut tile_start = middle_start;
+            while tile_start < middle_end {
+                let tile_end = Px::from(tile_start.into_float() + tile_length).min(middle_end);
+                // The final tile is cropped rather than squished.
+                let fraction = (tile_end - tile_start).into_float() / tile_length;
+                let src_end = src_middle_start + UPx::from((src_length * fraction).round());
+                push(
+                    tile_start,
+                    tile_end,
+                    src_middle_start,
+                    src_end.max(src_middle_start + UPx::new(1)),
+                );
+                if tile_end <= tile_start {
+                    break;
+                }
+                tile_start = tile_end;
+            }
+        }
+        None => push(middle_start, middle_end, src_middle_start, src_middle_end),
+    }
+
+    push(middle_end, origin + length, src_middle_end, texture_length);
+
+    slices
+}
+
+#[test]
+fn nine_slice_stretch() {
+    let slices = slice_axis(
+        Px::new(10),
+        Px::new(100),
+        UPx::new(30),
+        UPx::new(10),
+        UPx::new(10),
+        1.,
+        None,
+    );
+    assert_eq!(
+        slices,
+        [
+            AxisSlice {
+                dest_start: Px::new(10),
+                dest_end: Px::new(20),
+                src_start: UPx::new(0),
+                src_end: UPx::new(10),
+            },
+            AxisSlice {
+                dest_start: Px::new(20),
+                dest_end: Px::new(100),
+                src_start: UPx::new(10),
+                src_end: UPx::new(20),
+            },
+            AxisSlice {
+                dest_start: Px::new(100),
+                dest_end: Px::new(110),
+                src_start: UPx::new(20),
+                src_end: UPx::new(30),
+            },
+        ]
+    );
+
+    // Borders that don't fit are shrunk proportionally.
+    let slices = slice_axis(
+        Px::ZERO,
+        Px::new(10),
+        UPx::new(30),
+        UPx::new(10),
+        UPx::new(10),
+        1.,
+        None,
+    );
+    assert_eq!(slices.len(), 2);
+    assert_eq!(slices[0].dest_end, Px::new(5));
+    assert_eq!(slices[1].dest_start, Px::new(5));
+}
+
+#[test]
+fn nine_slice_tile() {
+    let slices = slice_axis(
+        Px::ZERO,
+        Px::new(45),
+        UPx::new(30),
+        UPx::new(10),
+        UPx::new(10),
+        1.,
+        Some(1.),
+    );
+    // Corner, two 10px tiles, a 5px cropped tile, and a corner.
+    assert_eq!(slices.len(), 5);
+    assert_eq!(slices[3].dest_start, Px::new(30));
+    assert_eq!(slices[3].dest_end, Px::new(35));
+    assert_eq!(slices[3].src_end, UPx::new(15));
+    assert_eq!(slices[4].dest_end, Px::new(45));
+}
+
+#[test]
+fn nine_slice_tile_budget() {
+    // A single texel middle across a large destination would need thousands
+    // of tiles, so it is stretched instead.
+    let slices = slice_axis(
+        Px::ZERO,
+        Px::new(4096),
+        UPx::new(3),
+        UPx::new(1),
+        UPx::new(1),
+        1.,
+        Some(1.),
+    );
+    assert_eq!(
+        slices[1],
+        AxisSlice {
+            dest_start: Px::new(1),
+            dest_end: Px::new(4095),
+            src_start: UPx::new(1),
+            src_end: UPx::new(2),
+        }
+    );
+    assert_eq!(slices.len(), 3);
+
+    // Exactly the maximum number of tiles is still tiled.
+    let slices = slice_axis(
+        Px::ZERO,
+        Px::new(66),
+        UPx::new(3),
+        UPx::new(1),
+        UPx::new(1),
+        1.,
+        Some(1.),
+    );
+    assert_eq!(slices.len(), 66);
+}
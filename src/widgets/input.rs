@@ -23,6 +23,7 @@ use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 use zeroize::Zeroizing;
 
 use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::cursor::MouseCursor;
 use crate::styles::components::{HighlightColor, IntrinsicPadding, OutlineColor, TextColor};
 use crate::utils::ModifiersExt;
 use crate::value::{Destination, Dynamic, Generation, IntoDynamic, IntoValue, Source, Value};
@@ -192,8 +193,8 @@ where
     fn replace_range(&mut self, start: Cursor, end: Cursor, new_text: &str) {
         self.value.map_mut(|mut value| {
             let value = value.as_string_mut();
-            let start = start.offset.min(value.len().saturating_sub(1));
-            let end = end.offset.min(value.len());
+            let end = char_boundary_at(value, end.offset);
+            let start = char_boundary_at(value, start.offset).min(end);
             value.replace_range(start..end, new_text);
 
             self.selection.cursor.offset = start + new_text.len();
@@ -241,6 +242,8 @@ where
         // @ecton: After a lot of thought, it seems like the only way for
         // affinity to be switched to After is via dragging the mouse.
         self.selection.cursor.affinity = Affinity::Before;
+        // The value may have been changed since the cursor was last placed.
+        self.constrain_selection();
         match mode {
             CursorNavigationMode::Grapheme => self.move_cursor_by_grapheme(direction),
             CursorNavigationMode::Word => self.move_cursor_by_word(direction),
@@ -364,11 +367,13 @@ where
     }
 
     fn constrain_selection(&mut self) {
-        let length = self.value.map_ref(|s| s.as_str().len());
-        self.selection.cursor.offset = self.selection.cursor.offset.min(length);
-        if let Some(start) = &mut self.selection.start {
-            start.offset = start.offset.min(length);
-        }
+        self.value.map_ref(|value| {
+            let value = value.as_str();
+            self.selection.cursor.offset = char_boundary_at(value, self.selection.cursor.offset);
+            if let Some(start) = &mut self.selection.start {
+                start.offset = char_boundary_at(value, start.offset);
+            }
+        });
     }
 
     fn selected_range(&mut self) -> (Cursor, Option<Cursor>) {
@@ -633,13 +638,17 @@ where
         if masked {
             self.value.map_ref(|value| {
                 let value = value.as_str();
-                assert!(cache.key.cursor.offset <= value.len());
-                cursor.offset =
-                    value[..cache.key.cursor.offset].graphemes(true).count() * cache.key.mask_bytes;
+                // The value may have changed since the cached layout was
+                // created.
+                cursor.offset = value[..char_boundary_at(value, cache.key.cursor.offset)]
+                    .graphemes(true)
+                    .count()
+                    * cache.key.mask_bytes;
                 if let Some(selection) = &mut selection {
-                    assert!(selection.offset <= value.len());
-                    selection.offset =
-                        value[..selection.offset].graphemes(true).count() * cache.key.mask_bytes;
+                    selection.offset = value[..char_boundary_at(value, selection.offset)]
+                        .graphemes(true)
+                        .count()
+                        * cache.key.mask_bytes;
                 }
             });
         }
@@ -946,6 +955,15 @@ enum CursorNavigationMode {
     // Document,
 }
 
+/// Returns the nearest character boundary in `text` at or before `offset`.
+fn char_boundary_at(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
 impl<Storage> Debug for Input<Storage>
 where
     Storage: Debug,
@@ -991,8 +1009,8 @@ where
         &mut self,
         _location: Point<Px>,
         _context: &mut EventContext<'_>,
-    ) -> Option<CursorIcon> {
-        Some(CursorIcon::Text)
+    ) -> Option<MouseCursor> {
+        Some(MouseCursor::Icon(CursorIcon::Text))
     }
 
     fn mouse_drag(
@@ -1017,7 +1035,7 @@ where
         _button: kludgine::app::winit::event::MouseButton,
         _context: &mut EventContext<'_>,
     ) {
-        self.mouse_buttons_down -= 1;
+        self.mouse_buttons_down = self.mouse_buttons_down.saturating_sub(1);
     }
 
     #[allow(clippy::too_many_lines)]
@@ -1549,3 +1567,40 @@ macro_rules! impl_cow_string {
 
 impl_cow_string!(CowString, false);
 impl_cow_string!(MaskedString, true);
+
+#[test]
+fn char_boundaries() {
+    assert_eq!(char_boundary_at("a\u{e9}b", 2), 1);
+    assert_eq!(char_boundary_at("a\u{e9}b", 3), 3);
+    assert_eq!(char_boundary_at("a\u{e9}b", 10), 4);
+    assert_eq!(char_boundary_at("", 1), 0);
+}
+
+#[test]
+fn edits_snap_to_char_boundaries() {
+    let value = Dynamic::new(String::from("a\u{e9}b"));
+    let mut input = Input::new(value.clone());
+
+    input.selection.cursor.offset = 2;
+    input.selection.start = Some(Cursor {
+        offset: 10,
+        affinity: Affinity::Before,
+    });
+    input.constrain_selection();
+    assert_eq!(input.selection.cursor.offset, 1);
+    assert_eq!(input.selection.start.map(|start| start.offset), Some(4));
+
+    input.replace_range(
+        Cursor {
+            offset: 2,
+            affinity: Affinity::Before,
+        },
+        Cursor {
+            offset: 3,
+            affinity: Affinity::Before,
+        },
+        "x",
+    );
+    assert_eq!(value.get(), "axb");
+    assert_eq!(input.selection.cursor.offset, 2);
+}
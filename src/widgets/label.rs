@@ -121,6 +121,10 @@ where
         fmt.debug_tuple("Label").field(&self.display).finish()
     }
 
+    fn displayed_text(&self) -> Option<&str> {
+        Some(&self.displayed)
+    }
+
     fn unmounted(&mut self, context: &mut crate::context::EventContext<'_>) {
         self.prepared_text.clear_for(context);
     }
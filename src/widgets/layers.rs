@@ -15,13 +15,17 @@ use super::super::widget::MountedWidget;
 use super::Space;
 use crate::animation::{AnimationHandle, AnimationTarget, IntoAnimate, Spawn, ZeroToOne};
 use crate::context::{AsEventContext, EventContext, GraphicsContext, LayoutContext, Trackable};
+use crate::graphics::OffscreenTarget;
 use crate::styles::components::{EasingIn, ScrimColor};
+use crate::styles::Dimension;
 use crate::value::{Destination, Dynamic, DynamicGuard, DynamicRead, IntoValue, Source, Value};
 use crate::widget::{
     Callback, MakeWidget, MakeWidgetWithTag, MountedChildren, SharedCallback, Widget, WidgetId,
     WidgetList, WidgetRef, WidgetTag, WrapperWidget,
 };
 use crate::widgets::container::ContainerShadow;
+use crate::widgets::effects::draw_blur_samples;
+use crate::window::WindowLocal;
 use crate::ConstraintLimit;
 
 /// A Z-direction stack of widgets.
@@ -30,6 +34,8 @@ pub struct Layers {
     /// The children that are laid out as layers with index 0 being the lowest (bottom).
     pub children: Value<WidgetList>,
     mounted: MountedChildren,
+    backdrop: WindowLocal<OffscreenTarget>,
+    blurred_backdrop: WindowLocal<OffscreenTarget>,
 }
 
 impl Layers {
@@ -38,6 +44,8 @@ impl Layers {
         Self {
             children: children.into_value(),
             mounted: MountedChildren::default(),
+            backdrop: WindowLocal::default(),
+            blurred_backdrop: WindowLocal::default(),
         }
     }
 
@@ -45,13 +53,56 @@ impl Layers {
         self.children.invalidate_when_changed(context);
         self.mounted.synchronize_with(&self.children, context);
     }
+
+    /// Returns the index and radius of the topmost presenting [`Modal`] layer
+    /// that blurs the layers beneath it.
+    fn backdrop_blur(&self, context: &mut GraphicsContext<'_, '_, '_, '_>) -> Option<(usize, Px)> {
+        let children = self.mounted.children();
+        let (index, blur) = children
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, child)| {
+                let child = child.lock();
+                let modal = child.downcast_ref::<ModalLayer>()?;
+                (!modal.presented.is_empty()).then(|| (index, modal.backdrop_blur.clone()))
+            })?;
+        let blur = blur
+            .get_tracking_redraw(context)
+            .into_px(context.gfx.scale())
+            .max(Px::ZERO);
+        (index > 0 && blur > 0).then_some((index, blur))
+    }
 }
 
 impl Widget for Layers {
     fn redraw(&mut self, context: &mut GraphicsContext<'_, '_, '_, '_>) {
         self.synchronize_children(&mut context.as_event_context());
 
-        for mounted in self.mounted.children() {
+        let mut first_unblurred = 0;
+        if let Some((index, blur)) = self.backdrop_blur(context) {
+            // The layers beneath the modal are rendered offscreen so that they
+            // can be drawn blurred.
+            let children = &self.mounted.children()[..index];
+            let target = self.backdrop.entry(context).or_default();
+            if context.redraw_children_offscreen(children, target) {
+                let texture = target.texture().assert("rendered above");
+                let region = Rect::from(context.gfx.region().size);
+                let blurred = self.blurred_backdrop.entry(context).or_default();
+                if context.gfx.render_offscreen(blurred, region, |mut gfx| {
+                    draw_blur_samples(&mut gfx, texture, region, blur);
+                }) {
+                    context.gfx.draw_texture(
+                        blurred.texture().assert("rendered above"),
+                        region,
+                        ZeroToOne::ONE,
+                    );
+                    first_unblurred = index;
+                }
+            }
+        }
+
+        for mounted in &self.mounted.children()[first_unblurred..] {
             context.for_other(mounted).redraw();
         }
     }
@@ -890,6 +941,7 @@ impl WrapperWidget for Tooltipped {
 #[derive(Debug, Clone, Default)]
 pub struct Modal {
     modal: Dynamic<OrderedLots<WidgetInstance>>,
+    backdrop_blur: Value<Dimension>,
 }
 
 impl Modal {
@@ -898,9 +950,23 @@ impl Modal {
     pub fn new() -> Self {
         Self {
             modal: Dynamic::default(),
+            backdrop_blur: Value::Constant(Dimension::ZERO),
         }
     }
 
+    /// Blurs the layers beneath this modal by `radius` while a modal session
+    /// is presented, and returns self.
+    ///
+    /// The layers beneath are rendered into an offscreen texture which is
+    /// drawn blurred behind the modal's scrim, creating a frosted-glass
+    /// backdrop. This requires this modal to be a direct child of a
+    /// [`Layers`] widget.
+    #[must_use]
+    pub fn backdrop_blur(mut self, radius: impl IntoValue<Dimension>) -> Self {
+        self.backdrop_blur = radius.into_value();
+        self
+    }
+
     /// Presents `contents` as the modal session.
     pub fn present(&self, contents: impl MakeWidget) {
         self.present_inner(contents);
@@ -969,6 +1035,7 @@ impl MakeWidgetWithTag for Modal {
             presented: Vec::new(),
             focus_top_layer: false,
             modal: self.modal,
+            backdrop_blur: self.backdrop_blur,
         }
         .make_with_tag(tag)
     }
@@ -981,6 +1048,7 @@ struct ModalLayer {
     layers: WidgetRef,
     modal: Dynamic<OrderedLots<WidgetInstance>>,
     focus_top_layer: bool,
+    backdrop_blur: Value<Dimension>,
 }
 
 impl WrapperWidget for ModalLayer {
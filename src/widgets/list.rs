@@ -14,13 +14,15 @@ use nominals::{
     TraditionalChineseFormal, TraditionalChineseInformal,
 };
 
-use super::grid::GridWidgets;
+use super::grid::{GridSection, GridWidgets};
 use super::input::CowString;
 use super::label::DynamicDisplay;
 use super::{Grid, Label};
 use crate::styles::{Component, RequireInvalidation};
-use crate::value::{IntoValue, MapEach, Source, Value};
-use crate::widget::{MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetList};
+use crate::value::{Destination, Dynamic, IntoValue, MapEach, Source, Value};
+use crate::widget::{
+    ChildrenSyncChange, MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetList,
+};
 
 /// A list of items displayed with an optional item indicator.
 pub struct List {
@@ -447,41 +449,89 @@ impl ListIndicator for ListStyle {
 
 impl MakeWidgetWithTag for List {
     fn make_with_tag(self, tag: crate::widget::WidgetTag) -> WidgetInstance {
+        let mut rows = ListRows::default();
         let rows = match (self.children, self.style) {
             (children, Value::Constant(style)) => {
-                children.map_each(move |children| build_grid_widgets(&style, children))
+                children.map_each(move |children| rows.synchronize(&style, children))
             }
             (Value::Dynamic(children), Value::Dynamic(style)) => Value::Dynamic(
                 (&style, &children)
-                    .map_each(|(style, children)| build_grid_widgets(style, children)),
+                    .map_each(move |(style, children)| rows.synchronize(style, children)),
             ),
             (Value::Constant(children), Value::Dynamic(style)) => {
-                Value::Dynamic(style.map_each(move |style| build_grid_widgets(style, &children)))
+                Value::Dynamic(style.map_each(move |style| rows.synchronize(style, &children)))
             }
         };
         Grid::from_rows(rows).make_with_tag(tag)
     }
 }
 
-fn build_grid_widgets(style: &ListStyle, children: &WidgetList) -> GridWidgets<2> {
-    // This is horrible. We should be be using synchronize_with to avoid
-    // recreating the gridwidgets every time.
-    children
-        .iter()
-        .enumerate()
-        .map(|(index, child)| {
-            (
-                Label::new(
+/// The rows of a [`List`].
+///
+/// Rows are reused as the list's children change so that only the rows for
+/// new children are created, and the [`Grid`] displaying the rows only needs
+/// to mount new rows.
+#[derive(Default)]
+struct ListRows {
+    revision: Option<u64>,
+    rows: Vec<ListRow>,
+}
+
+impl ListRows {
+    fn synchronize(&mut self, style: &ListStyle, children: &WidgetList) -> GridWidgets<2> {
+        children.synchronize_changes_with(
+            &mut self.revision,
+            &mut self.rows,
+            |rows, index| rows.get(index).map(|row| &row.child),
+            |rows, change| match change {
+                ChildrenSyncChange::Insert(index, child) => {
+                    rows.insert(index, ListRow::new(child));
+                }
+                ChildrenSyncChange::Remove(index) => {
+                    rows.remove(index);
+                }
+                ChildrenSyncChange::Swap(a, b) => rows.swap(a, b),
+                ChildrenSyncChange::Truncate(length) => rows.truncate(length),
+            },
+        );
+
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                row.indicator.set(
                     style
                         .list_indicator(index.wrapping_add(1))
                         .unwrap_or_default(),
-                )
+                );
+                GridSection::from(row.widgets.clone())
+            })
+            .collect()
+    }
+}
+
+struct ListRow {
+    child: WidgetInstance,
+    indicator: Dynamic<Indicator>,
+    widgets: [WidgetInstance; 2],
+}
+
+impl ListRow {
+    fn new(child: WidgetInstance) -> Self {
+        let indicator = Dynamic::<Indicator>::default();
+        let widgets = [
+            Label::new(indicator.clone())
                 .align_right()
-                .align_top(),
-                child.clone().align_left().make_widget(),
-            )
-        })
-        .collect()
+                .align_top()
+                .make_widget(),
+            child.clone().align_left().make_widget(),
+        ];
+        Self {
+            child,
+            indicator,
+            widgets,
+        }
+    }
 }
 
 /// An indicator used in a [`List`] widget.
@@ -8,12 +8,12 @@ use figures::{FloatConversion, IntoSigned, IntoUnsigned, Point, Rect, ScreenScal
 use intentional::Cast;
 use kempt::Set;
 use kludgine::app::winit::event::{MouseScrollDelta, TouchPhase};
-use kludgine::app::winit::window::CursorIcon;
 use kludgine::shapes::Shape;
 use kludgine::Color;
 
 use crate::animation::{AnimationHandle, AnimationTarget, IntoAnimate, Spawn, ZeroToOne};
 use crate::context::{AsEventContext, EventContext, LayoutContext};
+use crate::cursor::MouseCursor;
 use crate::styles::components::{EasingIn, EasingOut, LineHeight};
 use crate::styles::Dimension;
 use crate::value::{
@@ -257,7 +257,7 @@ impl Widget for Scroll {
         &mut self,
         _location: Point<Px>,
         context: &mut EventContext<'_>,
-    ) -> Option<CursorIcon> {
+    ) -> Option<MouseCursor> {
         self.show_scrollbars(context);
 
         None
@@ -422,6 +422,15 @@ struct DragInfo {
 }
 
 impl DragInfo {
+    /// Records a released mouse button, returning true if no buttons remain
+    /// pressed.
+    fn release(&mut self) -> bool {
+        // Releases can arrive without a matching press, such as when a button
+        // was pressed before the cursor entered the widget.
+        self.mouse_buttons_down = self.mouse_buttons_down.saturating_sub(1);
+        self.mouse_buttons_down == 0
+    }
+
     fn update(
         &self,
         location: Px,
@@ -451,7 +460,10 @@ impl DragInfo {
         bar: &ScrollbarInfo,
         control_size: UPx,
     ) -> UPx {
-        if self.in_bar {
+        if control_size <= bar.size {
+            // The bar fills the entire track, so there is nowhere to drag it.
+            start_scroll
+        } else if self.in_bar {
             let dy = location - start;
             if dy == 0 {
                 start_scroll
@@ -465,11 +477,12 @@ impl DragInfo {
                 .min(max_scroll)
             }
         } else {
-            max_scroll
+            (max_scroll
                 * ((location - bar.size.into_signed() / 2)
                     .max(Px::ZERO)
                     .into_float()
-                    / (control_size - bar.size).into_float())
+                    / (control_size - bar.size).into_float()))
+            .min(max_scroll)
         }
     }
 }
@@ -734,7 +747,7 @@ impl Widget for ScrollBar {
         &mut self,
         _location: Point<Px>,
         context: &mut EventContext<'_>,
-    ) -> Option<CursorIcon> {
+    ) -> Option<MouseCursor> {
         self.scrollbar_opacity_animation
             .lock()
             .hovering
@@ -844,9 +857,7 @@ impl Widget for ScrollBar {
         _button: kludgine::app::winit::event::MouseButton,
         context: &mut EventContext<'_>,
     ) {
-        self.drag.mouse_buttons_down -= 1;
-
-        if self.drag.mouse_buttons_down == 0 {
+        if self.drag.release() {
             if location.map_or(false, |location| {
                 let offset = if self.vertical {
                     location.y
@@ -863,3 +874,49 @@ impl Widget for ScrollBar {
         }
     }
 }
+
+#[test]
+fn unbalanced_release() {
+    let mut drag = DragInfo::default();
+    assert!(drag.release());
+    assert_eq!(drag.mouse_buttons_down, 0);
+
+    drag.mouse_buttons_down = 2;
+    assert!(!drag.release());
+    assert!(drag.release());
+}
+
+#[test]
+fn track_click_does_not_overshoot() {
+    let drag = DragInfo::default();
+    let bar = ScrollbarInfo {
+        offset: UPx::ZERO,
+        amount_hidden: UPx::new(50),
+        size: UPx::new(20),
+    };
+    let max_scroll = UPx::new(50);
+    let scroll = drag.update_bar(
+        Px::new(1000),
+        Px::ZERO,
+        max_scroll,
+        UPx::ZERO,
+        &bar,
+        UPx::new(100),
+    );
+    assert_eq!(scroll, max_scroll);
+
+    // A bar that fills the track cannot be moved.
+    let bar = ScrollbarInfo {
+        size: UPx::new(100),
+        ..bar
+    };
+    let scroll = drag.update_bar(
+        Px::new(1000),
+        Px::ZERO,
+        max_scroll,
+        UPx::new(10),
+        &bar,
+        UPx::new(100),
+    );
+    assert_eq!(scroll, UPx::new(10));
+}
@@ -14,6 +14,7 @@ use kludgine::{Color, DrawableExt, Origin};
 
 use crate::animation::{LinearInterpolate, PercentBetween, ZeroToOne};
 use crate::context::{EventContext, GraphicsContext, LayoutContext};
+use crate::cursor::MouseCursor;
 use crate::styles::components::{
     AutoFocusableControls, DisabledWidgetAccentColor, OpaqueWidgetColor, OutlineColor,
     WidgetAccentColor,
@@ -290,9 +291,13 @@ where
         } else {
             self.rendered_size - position.y - knob_size / 2
         };
-        let track_width = self.rendered_size - knob_size;
+        let track_width = (self.rendered_size - knob_size).max(Px::ZERO);
         let position = position.clamp(Px::ZERO, track_width);
-        let percent = position.into_float() / track_width.into_float();
+        let percent = if track_width > 0 {
+            position.into_float() / track_width.into_float()
+        } else {
+            0.
+        };
 
         let min = self.minimum.get();
         let max = self.maximum.get();
@@ -358,12 +363,18 @@ where
         let min = self.minimum.get();
         let max = self.maximum.get();
         let step = self.step.get();
+        // Stepping by zero, such as from a horizontal-only mouse wheel event,
+        // would never produce a new value.
+        let increment = *step * factor;
+        if increment <= 0. || increment.is_nan() {
+            return;
+        }
         let mut current_percent = current.percent_between(&min, &max);
         let new_value = loop {
             let next = if forwards {
-                *current_percent + *step * factor
+                *current_percent + increment
             } else {
-                *current_percent - *step * factor
+                *current_percent - increment
             };
             if next < 0. {
                 break min.clone();
@@ -561,8 +572,8 @@ where
         &mut self,
         _location: Point<Px>,
         context: &mut EventContext<'_>,
-    ) -> Option<CursorIcon> {
-        (self.interactive && self.knob_visible).then_some({
+    ) -> Option<MouseCursor> {
+        (self.interactive && self.knob_visible).then_some(MouseCursor::Icon({
             if context.enabled() {
                 if self.mouse_buttons_down > 0 {
                     CursorIcon::Grabbing
@@ -572,7 +583,7 @@ where
             } else {
                 CursorIcon::NotAllowed
             }
-        })
+        }))
     }
 
     fn accept_focus(&mut self, context: &mut EventContext<'_>) -> bool {
@@ -667,7 +678,7 @@ where
         _button: MouseButton,
         _context: &mut EventContext<'_>,
     ) {
-        self.mouse_buttons_down -= 1;
+        self.mouse_buttons_down = self.mouse_buttons_down.saturating_sub(1);
     }
 
     fn keyboard_input(
@@ -879,3 +890,28 @@ where
     U: SliderValue,
 {
 }
+
+#[test]
+fn zero_step_does_not_hang() {
+    let value = Dynamic::new(50_u8);
+    let mut slider = Slider::new(value.clone(), 0, 100);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        slider.step(true, 0.);
+        sender.send(()).expect("receiver dropped");
+    });
+    receiver
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .expect("stepping by zero never returned");
+    assert_eq!(value.get(), 50);
+}
+
+#[test]
+fn track_smaller_than_knob() {
+    let value = Dynamic::new(50_u8);
+    let mut slider = Slider::new(value.clone(), 0, 100);
+    slider.knob_size = UPx::new(20);
+    slider.rendered_size = Px::new(10);
+    slider.update_from_click(Point::px(5, 5), None);
+    assert_eq!(value.get(), 0);
+}
@@ -23,6 +23,7 @@ pub struct Stack {
     pub gutter: Value<FlexibleDimension>,
     layout: GridLayout,
     layout_generation: Option<Generation>,
+    synced_revision: Option<u64>,
     synced_children: Vec<MountedWidget>,
 }
 
@@ -35,6 +36,7 @@ impl Stack {
             gutter: Value::Constant(FlexibleDimension::Auto),
             layout: GridLayout::new(orientation),
             layout_generation: None,
+            synced_revision: None,
             synced_children: Vec::new(),
         }
     }
@@ -65,7 +67,8 @@ impl Stack {
         ) {
             self.layout_generation = self.children.generation();
             self.children.map(|children| {
-                children.synchronize_with(
+                children.synchronize_changes_with(
+                    &mut self.synced_revision,
                     &mut self.synced_children,
                     |this, index| this.get(index).map(MountedWidget::instance),
                     |this, change| match change {
@@ -105,6 +108,10 @@ impl Stack {
                             self.layout
                                 .insert(index, dimension, context.kludgine.scale());
                         }
+                        ChildrenSyncChange::Remove(index) => {
+                            context.remove_child(&this.remove(index));
+                            self.layout.remove(index);
+                        }
                         ChildrenSyncChange::Swap(a, b) => {
                             this.swap(a, b);
                             self.layout.swap(a, b);
@@ -4,11 +4,11 @@ use figures::units::{Px, UPx};
 use figures::{Point, Size};
 use intentional::Cast;
 use kludgine::app::winit::event::{ElementState, MouseScrollDelta, TouchPhase};
-use kludgine::app::winit::window::CursorIcon;
 use kludgine::tilemap;
 use kludgine::tilemap::TileMapFocus;
 
 use crate::context::{EventContext, GraphicsContext, LayoutContext, Trackable};
+use crate::cursor::MouseCursor;
 use crate::tick::Tick;
 use crate::value::{Dynamic, IntoValue, Value};
 use crate::widget::{EventHandling, Widget, HANDLED, IGNORED};
@@ -142,7 +142,7 @@ where
         HANDLED
     }
 
-    fn hover(&mut self, local: Point<Px>, context: &mut EventContext<'_>) -> Option<CursorIcon> {
+    fn hover(&mut self, local: Point<Px>, context: &mut EventContext<'_>) -> Option<MouseCursor> {
         if let Some(tick) = &self.tick {
             let size = context.last_layout().map(|rect| rect.size)?;
 
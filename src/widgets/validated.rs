@@ -2,6 +2,8 @@
 
 use std::fmt::Debug;
 
+use figures::units::Lp;
+use figures::Size;
 use kludgine::Color;
 
 use crate::styles::components::{
@@ -12,6 +14,7 @@ use crate::value::{
     Destination, Dynamic, IntoDynamic, IntoValue, MapEach, Source, Validation, Value,
 };
 use crate::widget::{MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetRef, WrapperWidget};
+use crate::widgets::ProgressBar;
 
 /// A widget that displays validation information around another widget.
 ///
@@ -20,7 +23,8 @@ use crate::widget::{MakeWidget, MakeWidgetWithTag, WidgetInstance, WidgetRef, Wr
 ///
 /// Additionally, a message may be shown below the content widget. If there is a
 /// validation error, it is shown. Otherwise, an optional hint message is
-/// supported.
+/// supported. While the validation is [pending](Validation::Pending), a spinner
+/// is shown next to the message.
 #[derive(Debug)]
 pub struct Validated {
     hint: Value<String>,
@@ -70,15 +74,27 @@ impl MakeWidgetWithTag for Validated {
             },
         );
 
+        let not_pending = self
+            .validation
+            .map_each(|validation| !validation.is_pending());
+
         ValidatedWidget {
             contents: WidgetRef::new(
                 self.validated
                     .with(&OutlineColor, color.clone())
                     .and(
-                        message
-                            .with(&TextColor, color)
-                            .with_dynamic(&TextSize, ValidatedTextSize)
-                            .with_dynamic(&LineHeight, ValidatedLineHeight)
+                        ProgressBar::indeterminant()
+                            .spinner()
+                            .size(Size::squared(Lp::points(10)))
+                            .centered()
+                            .collapse_horizontally(not_pending)
+                            .and(
+                                message
+                                    .with(&TextColor, color)
+                                    .with_dynamic(&TextSize, ValidatedTextSize)
+                                    .with_dynamic(&LineHeight, ValidatedLineHeight),
+                            )
+                            .into_columns()
                             .align_left(),
                     )
                     .into_rows(),
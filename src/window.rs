@@ -4,6 +4,7 @@
 use std::cell::RefCell;
 use std::collections::hash_map;
 use std::ffi::OsStr;
+use std::fs::File;
 use std::hash::Hash;
 use std::io;
 use std::marker::PhantomData;
@@ -31,7 +32,9 @@ use kludgine::app::winit::event::{
 use kludgine::app::winit::keyboard::{
     Key, KeyLocation, ModifiersState, NamedKey, NativeKeyCode, PhysicalKey, SmolStr,
 };
-use kludgine::app::winit::window::{self, Cursor, Fullscreen, Icon, WindowButtons, WindowLevel};
+use kludgine::app::winit::window::{
+    self, Cursor, CustomCursor, Fullscreen, Icon, WindowButtons, WindowLevel,
+};
 use kludgine::app::{winit, WindowAttributes, WindowBehavior as _};
 use kludgine::cosmic_text::{fontdb, Family, FamilyOwned};
 use kludgine::drawing::Drawing;
@@ -52,8 +55,15 @@ use crate::context::{
     AsEventContext, EventContext, Exclusive, GraphicsContext, LayoutContext, Trackable,
     WidgetContext,
 };
+use crate::cursor::CursorImage;
+use crate::debug::{Inspector, WidgetTreeDump};
 use crate::fonts::FontCollection;
 use crate::graphics::{FontState, Graphics};
+use crate::profiler::Profiler;
+#[cfg(feature = "replay")]
+use crate::replay::{InputRecorder, RecordedInput};
+#[cfg(feature = "settings")]
+use crate::settings::{Settings, SettingsFormat};
 use crate::styles::{Edges, FontFamilyList, ThemePair};
 use crate::tree::Tree;
 use crate::utils::ModifiersExt;
@@ -62,11 +72,11 @@ use crate::value::{
 };
 use crate::widget::{
     Callback, EventHandling, MakeWidget, MountedWidget, OnceCallback, RootBehavior, SharedCallback,
-    WidgetId, WidgetInstance, HANDLED, IGNORED,
+    Widget, WidgetId, WidgetInstance, HANDLED, IGNORED,
 };
 use crate::widgets::shortcuts::{ShortcutKey, ShortcutMap};
 use crate::window::sealed::WindowCommand;
-use crate::{App, ConstraintLimit};
+use crate::{clock, App, ConstraintLimit};
 
 /// A platform-dependent window implementation.
 pub trait PlatformWindowImplementation {
@@ -575,6 +585,12 @@ where
     fullscreen: Option<Value<Option<Fullscreen>>>,
     shortcuts: Value<ShortcutMap>,
     on_file_drop: Option<Callback<FileDrop>>,
+    #[cfg(feature = "settings")]
+    remembered: Option<RememberedState>,
+    #[cfg(feature = "replay")]
+    input_recorder: Option<InputRecorder>,
+    inspector: Option<Inspector>,
+    profiler: Option<Profiler>,
 }
 
 impl<Behavior> Default for Window<Behavior>
@@ -673,6 +689,12 @@ where
             shortcuts: Value::default(),
             on_init: None,
             on_file_drop: None,
+            #[cfg(feature = "settings")]
+            remembered: None,
+            #[cfg(feature = "replay")]
+            input_recorder: None,
+            inspector: None,
+            profiler: None,
         }
     }
 
@@ -736,6 +758,227 @@ where
         self.open(app)
     }
 
+    /// Records the input delivered to this window using `recorder`.
+    ///
+    /// See [`replay`](crate::replay) for more information.
+    #[cfg(feature = "replay")]
+    #[must_use]
+    pub fn record_input(mut self, recorder: &InputRecorder) -> Self {
+        self.input_recorder = Some(recorder.clone());
+        self
+    }
+
+    /// Attaches `inspector` to this window.
+    ///
+    /// Pressing the inspector's hotkey while this window is focused toggles
+    /// the inspector. See [`Inspector`] for more information.
+    #[must_use]
+    pub fn inspector(mut self, inspector: &Inspector) -> Self {
+        self.inspector = Some(inspector.clone());
+        self
+    }
+
+    /// Records the work performed by this window each frame using `profiler`.
+    ///
+    /// See [`profiler`](crate::profiler) for more information.
+    #[must_use]
+    pub fn profiler(mut self, profiler: &Profiler) -> Self {
+        self.profiler = Some(profiler.clone());
+        self
+    }
+
+    /// Restores and persists this window's size, position, maximized, and
+    /// fullscreen states using the [`WindowState`] stored in `settings` under
+    /// `key`.
+    ///
+    /// When the window is opened, the stored state is applied. If the restored
+    /// position would place the window outside of the currently connected
+    /// monitors, the window is moved to be fully visible on the nearest
+    /// monitor. While the window is open, any changes to its state are stored,
+    /// and the settings are saved when the window is closed.
+    ///
+    /// The size and position are not updated while the window is maximized or
+    /// fullscreen, which allows the window to be restored to its previous size
+    /// and position after being reopened in either state.
+    #[cfg(feature = "settings")]
+    pub fn remember_state<Format>(mut self, settings: &Settings<Format>, key: &str) -> Self
+    where
+        Format: SettingsFormat,
+    {
+        let state = settings.get_or_default::<WindowState>(key);
+        let saved = state.get();
+
+        let inner_size = self.inner_size.clone().unwrap_or_default();
+        if let Some(size) = saved.inner_size {
+            inner_size.set(size);
+        }
+        self = self.inner_size(inner_size.clone());
+
+        let outer_position = self.outer_position.clone().unwrap_or_default();
+        if let Some(position) = saved.outer_position {
+            outer_position.set(position);
+            self.attributes.position = Some(winit::dpi::Position::Physical(position.into()));
+        }
+        self.outer_position = Some(outer_position.clone());
+
+        let maximized = self.maximized.clone().unwrap_or_default();
+        if saved.maximized {
+            maximized.set(true);
+        }
+        self = self.maximized(maximized.clone());
+
+        let fullscreen = match self.fullscreen.take() {
+            Some(Value::Dynamic(fullscreen)) => Some(fullscreen),
+            Some(Value::Constant(fullscreen)) => {
+                self.fullscreen = Some(Value::Constant(fullscreen));
+                None
+            }
+            None => Some(Dynamic::default()),
+        };
+        if let Some(fullscreen) = fullscreen {
+            if saved.fullscreen && fullscreen.map_ref(Option::is_none) {
+                fullscreen.set(Some(Fullscreen::Borderless(None)));
+            }
+            fullscreen
+                .for_each_subsequent({
+                    let state = state.clone();
+                    move |fullscreen| {
+                        let fullscreen = fullscreen.is_some();
+                        state.map_mut(|mut state| state.fullscreen = fullscreen);
+                    }
+                })
+                .persist();
+            self = self.fullscreen(fullscreen);
+        }
+
+        // Maximizing a window resizes and moves it before the window reports
+        // that it is maximized. The most recent normal geometry is kept to be
+        // restored when the window becomes maximized.
+        let previous = Arc::new(Mutex::new(WindowState::default()));
+        inner_size
+            .for_each_subsequent({
+                let state = state.clone();
+                let previous = previous.clone();
+                move |size| {
+                    let size = *size;
+                    state.map_mut(|mut state| {
+                        if !state.maximized && !state.fullscreen {
+                            previous.lock().inner_size = state.inner_size;
+                            state.inner_size = Some(size);
+                        }
+                    });
+                }
+            })
+            .persist();
+        outer_position
+            .for_each_subsequent({
+                let state = state.clone();
+                let previous = previous.clone();
+                move |position| {
+                    let position = *position;
+                    state.map_mut(|mut state| {
+                        if !state.maximized && !state.fullscreen {
+                            previous.lock().outer_position = state.outer_position;
+                            state.outer_position = Some(position);
+                        }
+                    });
+                }
+            })
+            .persist();
+        maximized
+            .for_each_subsequent({
+                let state = state.clone();
+                move |maximized| {
+                    let maximized = *maximized;
+                    state.map_mut(|mut state| {
+                        if maximized && !state.maximized {
+                            let previous = previous.lock();
+                            state.inner_size = previous.inner_size.or(state.inner_size);
+                            state.outer_position = previous.outer_position.or(state.outer_position);
+                        }
+                        state.maximized = maximized;
+                    });
+                }
+            })
+            .persist();
+
+        let settings = settings.clone();
+        self.remembered = Some(RememberedState {
+            restore_position: saved.outer_position.is_some(),
+            save: Box::new(move || {
+                if let Err(err) = settings.save() {
+                    tracing::error!("error saving window state: {err}");
+                }
+            }),
+        });
+        self
+    }
+
+    #[cfg(feature = "settings")]
+    fn restore_on_open(&mut self, app: App) {
+        let Some(remembered) = self.remembered.take() else {
+            return;
+        };
+
+        let save = remembered.save;
+        let on_closed = self.on_closed.take();
+        self.on_closed = Some(OnceCallback::new(move |()| {
+            save();
+            if let Some(on_closed) = on_closed {
+                on_closed.invoke(());
+            }
+        }));
+
+        if !remembered.restore_position {
+            return;
+        }
+
+        // The monitors and the window's outer size are not known until the
+        // window has been opened, so the position is checked the first time
+        // the outer size is reported.
+        let outer_position = self.outer_position.clone().unwrap_or_default();
+        self.outer_position = Some(outer_position.clone());
+        let outer_size = self.outer_size.clone().unwrap_or_else(|| {
+            let outer_size = Dynamic::new(Size::default());
+            self.outer_size = Some(outer_size.clone());
+            outer_size
+        });
+        let callback_handle = Dynamic::new(None);
+        callback_handle.set(Some(outer_size.for_each_subsequent({
+            let callback_handle = callback_handle.clone();
+            move |new_size| {
+                let Some(monitors) = app.monitors() else {
+                    return;
+                };
+                let position = outer_position.get();
+                if let Some(region) = monitors
+                    .available
+                    .into_iter()
+                    .map(|m| m.region())
+                    .find(|region| region.contains(position))
+                    .or_else(|| monitors.primary.map(|m| m.region()))
+                {
+                    let size = new_size.into_signed();
+                    let clamped = Point::new(
+                        position
+                            .x
+                            .min(region.origin.x + region.size.width - size.width)
+                            .max(region.origin.x),
+                        position
+                            .y
+                            .min(region.origin.y + region.size.height - size.height)
+                            .max(region.origin.y),
+                    );
+                    if clamped != position {
+                        outer_position.set(clamped);
+                    }
+                }
+                // Uninstall this callback to ensure it doesn't fire again.
+                let _ = callback_handle.take();
+            }
+        })));
+    }
+
     /// Sets `focused` to be the dynamic updated when this window's focus status
     /// is changed.
     ///
@@ -1127,6 +1370,12 @@ where
     {
         let this = self.make_window();
         let app_app = app.as_app();
+        #[cfg(feature = "settings")]
+        let this = {
+            let mut this = this;
+            this.restore_on_open(app_app.clone());
+            this
+        };
         let handle = this.pending.handle();
         OpenWindow::<T::Behavior>::open_with(
             app,
@@ -1179,6 +1428,10 @@ where
                     fullscreen: this.fullscreen.unwrap_or_default(),
                     shortcuts: this.shortcuts,
                     on_file_drop: this.on_file_drop,
+                    #[cfg(feature = "replay")]
+                    input_recorder: this.input_recorder,
+                    inspector: this.inspector,
+                    profiler: this.profiler,
                 }),
                 pending: this.pending,
             },
@@ -1378,6 +1631,10 @@ struct OpenWindow<T> {
     shortcuts: Value<ShortcutMap>,
     on_file_drop: Option<Callback<FileDrop>>,
     disabled_resize_automatically: bool,
+    #[cfg(feature = "replay")]
+    input_recorder: Option<InputRecorder>,
+    inspector: Option<Inspector>,
+    profiler: Option<Profiler>,
 }
 
 impl<T> OpenWindow<T>
@@ -1752,6 +2009,11 @@ where
         };
 
         let tree = Tree::default();
+        let profiler = settings.profiler.as_ref().map(Profiler::for_window);
+        if let Some(profiler) = &profiler {
+            redraw_status.set_profiler(profiler.clone());
+            tree.set_profiler(profiler.clone());
+        }
         let root = tree.push_boxed(behavior.make_root(), None);
 
         let theme = settings.theme.unwrap_or_default();
@@ -1773,6 +2035,8 @@ where
             cursor: CursorState {
                 location: None,
                 widget: None,
+                image: None,
+                custom: AHashMap::default(),
             },
             mouse_buttons: AHashMap::default(),
             redraw_status,
@@ -1816,6 +2080,10 @@ where
             shortcuts: settings.shortcuts,
             on_file_drop: settings.on_file_drop,
             disabled_resize_automatically: false,
+            #[cfg(feature = "replay")]
+            input_recorder: settings.input_recorder,
+            inspector: settings.inspector,
+            profiler,
         };
 
         this.synchronize_platform_window(&mut window);
@@ -1852,6 +2120,9 @@ where
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
 
+        if let Some(profiler) = &self.profiler {
+            profiler.begin_frame();
+        }
         self.synchronize_platform_window(&mut window);
         self.new_frame(graphics);
 
@@ -1896,6 +2167,7 @@ where
             layout_context.graphics.gfx.fill(background_color);
         }
 
+        let layout_start = Instant::now();
         let layout_size =
             layout_context.layout(if matches!(root_mode, RootMode::Expand | RootMode::Align) {
                 window_size.map(ConstraintLimit::Fill)
@@ -1915,6 +2187,10 @@ where
             self.initial_frame = false;
             Self::mount_and_focus_root(&self.root, &mut layout_context);
         }
+        let redraw_start = Instant::now();
+        if let Some(profiler) = &self.profiler {
+            profiler.layout_finished(redraw_start - layout_start);
+        }
 
         if render_size.width < window_size.width || render_size.height < window_size.height {
             layout_context
@@ -1923,6 +2199,15 @@ where
         } else {
             layout_context.redraw();
         }
+        if let Some(profiler) = &self.profiler {
+            profiler.redraw_finished(redraw_start.elapsed());
+        }
+        if let Some(inspector) = &self.inspector {
+            inspector.redraw(&mut layout_context);
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.draw_overlay(&mut layout_context);
+        }
 
         let resizable = resizable
             && !Self::enforce_fixed_size(
@@ -2019,6 +2304,8 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::Resized(new_size));
         self.inner_size.set_and_read(new_size);
         self.outer_size.set(window.outer_size());
         self.update_ized(window);
@@ -2122,9 +2409,24 @@ where
     }
 
     pub fn set_focused(&mut self, focused: bool) {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::Focused(focused));
         self.focused.set(focused);
     }
 
+    fn render_finished(&self, render_start: Instant) {
+        if let Some(profiler) = &self.profiler {
+            profiler.render_finished(render_start.elapsed());
+        }
+    }
+
+    #[cfg(feature = "replay")]
+    fn record_input(&self, input: impl FnOnce() -> RecordedInput) {
+        if let Some(recorder) = &self.input_recorder {
+            recorder.record(input());
+        }
+    }
+
     pub fn set_occluded<W>(&mut self, window: &W, occluded: bool)
     where
         W: PlatformWindowImplementation,
@@ -2144,6 +2446,13 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::keyboard(&input, is_synthetic));
+        if let Some(inspector) = &self.inspector {
+            if inspector.handle_key(&input, &self.root, &self.app) {
+                return HANDLED;
+            }
+        }
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
         let mut window = RunningWindow::new(
@@ -2192,6 +2501,36 @@ where
         self.handle_window_keyboard_input(&mut window, kludgine, input)
     }
 
+    pub fn focus<W>(&mut self, window: W, kludgine: &mut Kludgine, widget: &MountedWidget)
+    where
+        W: PlatformWindowImplementation,
+    {
+        let cushy = self.app.cushy().clone();
+        let _guard = cushy.enter_runtime();
+        let mut window = RunningWindow::new(
+            window,
+            kludgine.id(),
+            &self.redraw_status,
+            &self.app,
+            &self.focused,
+            &self.occluded,
+            self.inner_size.source(),
+            &self.close_requested,
+        );
+        EventContext::new(
+            WidgetContext::new(
+                widget.clone(),
+                &self.current_theme,
+                &mut window,
+                &mut self.fonts,
+                self.theme_mode.get(),
+                &mut self.cursor,
+            ),
+            kludgine,
+        )
+        .focus();
+    }
+
     pub fn mouse_wheel<W>(
         &mut self,
         window: W,
@@ -2203,6 +2542,8 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::MouseWheel { delta, phase });
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
         let mut window = RunningWindow::new(
@@ -2247,6 +2588,8 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::Ime(ime.clone()));
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
         let mut window = RunningWindow::new(
@@ -2306,8 +2649,13 @@ where
         );
 
         let location = position.into();
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::CursorMoved(location));
         self.cursor.location = Some(location);
         self.cursor_position.set_and_read(location);
+        if let Some(inspector) = &self.inspector {
+            inspector.hover(self.tree.widgets_under_point(location).into_iter().next());
+        }
 
         EventContext::new(
             WidgetContext::new(
@@ -2351,6 +2699,8 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::CursorLeft);
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
         self.cursor.location = None;
@@ -2394,6 +2744,8 @@ where
     where
         W: PlatformWindowImplementation,
     {
+        #[cfg(feature = "replay")]
+        self.record_input(|| RecordedInput::MouseInput { state, button });
         let cushy = self.app.cushy().clone();
         let _guard = cushy.enter_runtime();
         let mut window = RunningWindow::new(
@@ -2631,7 +2983,9 @@ where
         _window: kludgine::app::Window<'_, WindowCommand>,
         graphics: &mut kludgine::RenderingGraphics<'_, 'pass>,
     ) {
+        let render_start = Instant::now();
         self.contents.render(1., graphics);
+        self.render_finished(render_start);
     }
 
     fn initial_window_attributes(context: &Self::Context) -> kludgine::app::WindowAttributes {
@@ -2980,6 +3334,10 @@ fn recursively_handle_event(
 pub(crate) struct CursorState {
     pub(crate) location: Option<Point<Px>>,
     pub(crate) widget: Option<WidgetCursorState>,
+    /// The custom cursor image most recently requested by a widget.
+    pub(crate) image: Option<CursorImage>,
+    /// Native cursors that have been created for custom cursor images.
+    pub(crate) custom: AHashMap<CursorImage, CustomCursor>,
 }
 
 #[derive(Eq, PartialEq)]
@@ -3003,7 +3361,11 @@ pub(crate) mod sealed {
 
     use crate::context::sealed::InvalidationStatus;
     use crate::context::EventContext;
+    use crate::debug::Inspector;
     use crate::fonts::FontCollection;
+    use crate::profiler::Profiler;
+    #[cfg(feature = "replay")]
+    use crate::replay::InputRecorder;
     use crate::styles::{FontFamilyList, ThemePair};
     use crate::value::{Dynamic, Value};
     use crate::widget::{Callback, OnceCallback, SharedCallback};
@@ -3062,6 +3424,10 @@ pub(crate) mod sealed {
         pub fullscreen: Value<Option<Fullscreen>>,
         pub shortcuts: Value<ShortcutMap>,
         pub on_file_drop: Option<Callback<FileDrop>>,
+        #[cfg(feature = "replay")]
+        pub input_recorder: Option<InputRecorder>,
+        pub inspector: Option<Inspector>,
+        pub profiler: Option<Profiler>,
     }
 
     pub struct WindowExecute(Box<dyn ExecuteFunc>);
@@ -3143,8 +3509,35 @@ pub(crate) mod sealed {
     }
 }
 
+/// The size, position, and state of a window.
+///
+/// This type is used by [`Window::remember_state`] to persist the state of a
+/// window between launches.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WindowState {
+    /// The inner size of the window when it is neither maximized nor
+    /// fullscreen.
+    pub inner_size: Option<Size<UPx>>,
+    /// The outer position of the window when it is neither maximized nor
+    /// fullscreen.
+    pub outer_position: Option<Point<Px>>,
+    /// If true, the window is maximized.
+    pub maximized: bool,
+    /// If true, the window is fullscreen.
+    pub fullscreen: bool,
+}
+
+#[cfg(feature = "settings")]
+struct RememberedState {
+    restore_position: bool,
+    save: Box<dyn FnOnce() + Send>,
+}
+
 /// Controls whether the light or dark theme is applied.
 #[derive(Default, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, LinearInterpolate)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ThemeMode {
     /// Applies the light theme
     Light,
@@ -3302,6 +3695,18 @@ impl WindowHandle {
         self.inner
             .send(WindowCommand::Execute(WindowExecute::new(func)));
     }
+
+    /// Invokes `on_dump` on the window thread with a snapshot of the widgets
+    /// mounted in this window.
+    ///
+    /// Virtual windows do not support executing functions through their
+    /// handles. Use [`VirtualWindow::dump_tree`] instead.
+    pub fn dump_tree<F>(&self, on_dump: F)
+    where
+        F: FnOnce(WidgetTreeDump) + Send + 'static,
+    {
+        self.execute(move |context| on_dump(context.tree.dump()));
+    }
 }
 
 impl Eq for WindowHandle {}
@@ -3574,7 +3979,7 @@ impl PlatformWindowImplementation for &mut VirtualState {
     }
 
     fn redraw_in(&mut self, duration: Duration) {
-        self.redraw_at(Instant::now() + duration);
+        self.redraw_at(clock::now() + duration);
     }
 
     fn redraw_at(&mut self, moment: Instant) {
@@ -3621,6 +4026,10 @@ pub struct StandaloneWindowBuilder {
     transparent: bool,
     zoom: Dynamic<Fraction>,
     resize_to_fit: Value<bool>,
+    #[cfg(feature = "replay")]
+    input_recorder: Option<InputRecorder>,
+    inspector: Option<Inspector>,
+    profiler: Option<Profiler>,
 }
 
 impl StandaloneWindowBuilder {
@@ -3635,6 +4044,10 @@ impl StandaloneWindowBuilder {
             zoom: Dynamic::new(Fraction::ONE),
             transparent: false,
             resize_to_fit: Value::Constant(false),
+            #[cfg(feature = "replay")]
+            input_recorder: None,
+            inspector: None,
+            profiler: None,
         }
     }
 
@@ -3679,6 +4092,35 @@ impl StandaloneWindowBuilder {
         self
     }
 
+    /// Records the input delivered to this window using `recorder`.
+    ///
+    /// See [`replay`](crate::replay) for more information.
+    #[cfg(feature = "replay")]
+    #[must_use]
+    pub fn record_input(mut self, recorder: &InputRecorder) -> Self {
+        self.input_recorder = Some(recorder.clone());
+        self
+    }
+
+    /// Attaches `inspector` to this window.
+    ///
+    /// Pressing the inspector's hotkey while this window is focused toggles
+    /// the inspector. See [`Inspector`] for more information.
+    #[must_use]
+    pub fn inspector(mut self, inspector: &Inspector) -> Self {
+        self.inspector = Some(inspector.clone());
+        self
+    }
+
+    /// Records the work performed by this window each frame using `profiler`.
+    ///
+    /// See [`profiler`](crate::profiler) for more information.
+    #[must_use]
+    pub fn profiler(mut self, profiler: &Profiler) -> Self {
+        self.profiler = Some(profiler.clone());
+        self
+    }
+
     /// Returns the initialized window.
     #[must_use]
     pub fn finish<W>(self, window: W, device: &wgpu::Device, queue: &wgpu::Queue) -> CushyWindow
@@ -3745,6 +4187,10 @@ impl StandaloneWindowBuilder {
                 shortcuts: Value::default(),
                 on_init: None,
                 on_file_drop: None,
+                #[cfg(feature = "replay")]
+                input_recorder: self.input_recorder,
+                inspector: self.inspector,
+                profiler: self.profiler,
             },
         );
 
@@ -3813,6 +4259,7 @@ impl CushyWindow {
         queue: &wgpu::Queue,
         additional_drawing: Option<&Drawing>,
     ) -> Option<wgpu::SubmissionIndex> {
+        let render_start = Instant::now();
         let mut frame = self.kludgine.next_frame();
         let mut gfx = frame.render(pass, device, queue);
         self.window.contents.render(1., &mut gfx);
@@ -3820,7 +4267,9 @@ impl CushyWindow {
             additional.render(1., &mut gfx);
         }
         drop(gfx);
-        frame.submit(queue)
+        let submission = frame.submit(queue);
+        self.window.render_finished(render_start);
+        submission
     }
 
     /// Renders this window into `texture` after performing `load_op`.
@@ -3831,11 +4280,14 @@ impl CushyWindow {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
     ) -> Option<wgpu::SubmissionIndex> {
+        let render_start = Instant::now();
         let mut frame = self.kludgine.next_frame();
         let mut gfx = frame.render_into(texture, load_op, device, queue);
         self.window.contents.render(1., &mut gfx);
         drop(gfx);
-        frame.submit(queue)
+        let submission = frame.submit(queue);
+        self.window.render_finished(render_start);
+        submission
     }
 
     /// Returns a new [`kludgine::Graphics`] context for this window.
@@ -3957,6 +4409,39 @@ impl CushyWindow {
         self.window.ime(window, &mut self.kludgine, ime)
     }
 
+    /// Focuses `widget`.
+    pub fn focus<W>(&mut self, window: W, widget: &MountedWidget)
+    where
+        W: PlatformWindowImplementation,
+    {
+        self.window.focus(window, &mut self.kludgine, widget);
+    }
+
+    /// Returns the root widget of this window.
+    #[must_use]
+    pub fn root(&self) -> &MountedWidget {
+        &self.window.root
+    }
+
+    /// Returns all widgets mounted in this window, with parents before their
+    /// children.
+    #[must_use]
+    pub fn widgets(&self) -> Vec<MountedWidget> {
+        self.window.tree.widgets()
+    }
+
+    /// Returns a snapshot of the widgets mounted in this window.
+    #[must_use]
+    pub fn dump_tree(&self) -> WidgetTreeDump {
+        self.window.tree.dump()
+    }
+
+    /// Returns the mounted widget with `id`, if found.
+    #[must_use]
+    pub fn widget(&self, id: impl Into<WidgetId>) -> Option<MountedWidget> {
+        self.window.tree.widget(id.into())
+    }
+
     /// Provides cursor movement events to this window.
     pub fn cursor_moved<W>(
         &mut self,
@@ -4016,7 +4501,7 @@ impl VirtualWindow {
     /// `Err(Resized)` is returned and Cushy will immediately resize the
     /// graphics context and begin rendering again.
     pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
-        let now = Instant::now();
+        let now = clock::now();
         self.state.elapsed = self
             .last_rendered_at
             .map(|i| now.duration_since(i))
@@ -4181,6 +4666,110 @@ impl VirtualWindow {
         self.cushy.ime(&mut self.state, ime)
     }
 
+    /// Focuses `widget`.
+    pub fn focus(&mut self, widget: &MountedWidget) {
+        self.cushy.focus(&mut self.state, widget);
+    }
+
+    /// Returns the root widget of this window.
+    #[must_use]
+    pub fn root(&self) -> &MountedWidget {
+        self.cushy.root()
+    }
+
+    /// Returns all widgets mounted in this window, with parents before their
+    /// children.
+    #[must_use]
+    pub fn widgets(&self) -> Vec<MountedWidget> {
+        self.cushy.widgets()
+    }
+
+    /// Returns a snapshot of the widgets mounted in this window.
+    ///
+    /// The snapshot reflects the most recent call to
+    /// [`prepare()`](Self::prepare).
+    #[must_use]
+    pub fn dump_tree(&self) -> WidgetTreeDump {
+        self.cushy.dump_tree()
+    }
+
+    /// Returns the mounted widget with `id`, if found.
+    ///
+    /// A [`WidgetTag`](crate::widget::WidgetTag) can be used to find the widget
+    /// it was used to create.
+    #[must_use]
+    pub fn widget(&self, id: impl Into<WidgetId>) -> Option<MountedWidget> {
+        self.cushy.widget(id)
+    }
+
+    /// Returns the first mounted widget of type `W`, if found.
+    #[must_use]
+    pub fn find_widget<W>(&self) -> Option<MountedWidget>
+    where
+        W: Widget,
+    {
+        self.find_widgets::<W>().into_iter().next()
+    }
+
+    /// Returns all mounted widgets of type `W`.
+    #[must_use]
+    pub fn find_widgets<W>(&self) -> Vec<MountedWidget>
+    where
+        W: Widget,
+    {
+        self.widgets()
+            .into_iter()
+            .filter(|widget| widget.lock().downcast_ref::<W>().is_some())
+            .collect()
+    }
+
+    /// Returns the first mounted widget that last displayed `text`, if found.
+    ///
+    /// Only widgets that implement [`Widget::displayed_text`], such as
+    /// [`Label`](crate::widgets::Label), can be found. Text is only known
+    /// after the widget has been laid out.
+    #[must_use]
+    pub fn find_text(&self, text: &str) -> Option<MountedWidget> {
+        self.widgets()
+            .into_iter()
+            .find(|widget| widget.lock().as_widget().displayed_text() == Some(text))
+    }
+
+    /// Moves the cursor to the center of `widget` and clicks the primary
+    /// mouse button.
+    ///
+    /// Returns whether the button press was [`HANDLED`] or [`IGNORED`].
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `widget` has not been laid out.
+    pub fn click(&mut self, widget: &MountedWidget) -> EventHandling {
+        let center = widget_center(widget);
+        self.cursor_moved(DeviceId::Virtual(0), center);
+        let handled = self.mouse_input(
+            DeviceId::Virtual(0),
+            ElementState::Pressed,
+            MouseButton::Left,
+        );
+        let _ = self.mouse_input(
+            DeviceId::Virtual(0),
+            ElementState::Released,
+            MouseButton::Left,
+        );
+        handled
+    }
+
+    /// Focuses `widget` and enters each grapheme of `text` as keyboard input.
+    pub fn type_into(&mut self, widget: &MountedWidget, text: &str) {
+        self.focus(widget);
+        for grapheme in text.graphemes(true) {
+            let mut event = grapheme_key_event(grapheme);
+            let _handled = self.keyboard_input(DeviceId::Virtual(0), event.clone(), true);
+            event.state = ElementState::Released;
+            let _handled = self.keyboard_input(DeviceId::Virtual(0), event, true);
+        }
+    }
+
     /// Provides cursor movement events to this window.
     pub fn cursor_moved(&mut self, device_id: DeviceId, position: impl Into<Point<Px>>) {
         self.cushy
@@ -4300,6 +4889,8 @@ pub struct VirtualRecorderBuilder<Format> {
     scale: f32,
     format: PhantomData<Format>,
     resize_to_fit: bool,
+    #[cfg(feature = "replay")]
+    input_recorder: Option<InputRecorder>,
 }
 
 impl VirtualRecorderBuilder<Rgb8> {
@@ -4311,6 +4902,8 @@ impl VirtualRecorderBuilder<Rgb8> {
             scale: 1.0,
             format: PhantomData,
             resize_to_fit: false,
+            #[cfg(feature = "replay")]
+            input_recorder: None,
         }
     }
 
@@ -4324,6 +4917,8 @@ impl VirtualRecorderBuilder<Rgb8> {
             scale: self.scale,
             resize_to_fit: self.resize_to_fit,
             format: PhantomData,
+            #[cfg(feature = "replay")]
+            input_recorder: self.input_recorder,
         }
     }
 }
@@ -4362,9 +4957,31 @@ where
         self
     }
 
+    /// Records the input delivered to the virtual window using `recorder`.
+    ///
+    /// See [`replay`](crate::replay) for more information.
+    #[cfg(feature = "replay")]
+    #[must_use]
+    pub fn record_input(mut self, recorder: &InputRecorder) -> Self {
+        self.input_recorder = Some(recorder.clone());
+        self
+    }
+
     /// Returns an initialized [`VirtualRecorder`].
     pub fn finish(self) -> Result<VirtualRecorder<Format>, VirtualRecorderError> {
-        VirtualRecorder::new(self.size, self.scale, self.resize_to_fit, self.contents)
+        let window = self
+            .contents
+            .build_standalone_window()
+            .size(self.size)
+            .scale(self.scale)
+            .transparent()
+            .resize_to_fit(self.resize_to_fit);
+        #[cfg(feature = "replay")]
+        let window = match &self.input_recorder {
+            Some(recorder) => window.record_input(recorder),
+            None => window,
+        };
+        VirtualRecorder::from_standalone(window, self.resize_to_fit)
     }
 }
 
@@ -4461,27 +5078,23 @@ where
         resize_to_fit: bool,
         contents: impl MakeWidget,
     ) -> Result<Self, VirtualRecorderError> {
-        let wgpu = wgpu::Instance::default();
-        let adapter =
-            pollster::block_on(wgpu.request_adapter(&wgpu::RequestAdapterOptions::default()))
-                .ok_or(VirtualRecorderError::NoAdapter)?;
-        let (device, queue) = pollster::block_on(adapter.request_device(
-            &wgpu::DeviceDescriptor {
-                label: None,
-                required_features: Kludgine::REQURED_FEATURES,
-                required_limits: Kludgine::adjust_limits(wgpu::Limits::downlevel_webgl2_defaults()),
-                memory_hints: wgpu::MemoryHints::MemoryUsage,
-            },
-            None,
-        ))?;
+        Self::from_standalone(
+            contents
+                .build_standalone_window()
+                .size(size)
+                .scale(scale)
+                .transparent()
+                .resize_to_fit(resize_to_fit),
+            resize_to_fit,
+        )
+    }
 
-        let window = contents
-            .build_standalone_window()
-            .size(size)
-            .scale(scale)
-            .transparent()
-            .resize_to_fit(resize_to_fit)
-            .finish_virtual(&device, &queue);
+    fn from_standalone(
+        window: StandaloneWindowBuilder,
+        resize_to_fit: bool,
+    ) -> Result<Self, VirtualRecorderError> {
+        let (device, queue) = request_device()?;
+        let window = window.finish_virtual(&device, &queue);
 
         let mut recorder = Self {
             window,
@@ -4557,6 +5170,93 @@ where
         Format::load_image(self.bytes(), self.data_size)
     }
 
+    /// Asserts that the current contents match the snapshot named `name`,
+    /// allowing for slight color variations.
+    ///
+    /// See [`assert_snapshot_with_tolerance`](Self::assert_snapshot_with_tolerance)
+    /// for more information.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the contents do not match the snapshot.
+    #[track_caller]
+    pub fn assert_snapshot(&self, name: &str) {
+        self.assert_snapshot_with_tolerance(name, SnapshotTolerance::default());
+    }
+
+    /// Asserts that the current contents match the snapshot named `name`
+    /// within `tolerance`.
+    ///
+    /// Snapshots are PNG images stored in `tests/snapshots/{name}.png`,
+    /// relative to the directory of the crate being tested. When the
+    /// `CUSHY_UPDATE_SNAPSHOTS` environment variable is set, the snapshot is
+    /// overwritten with the current contents instead of being compared.
+    ///
+    /// When the contents do not match, three images are written next to the
+    /// snapshot before panicking:
+    ///
+    /// - `{name}.actual.png`: The current contents.
+    /// - `{name}.expected.png`: The stored snapshot.
+    /// - `{name}.diff.png`: The current contents faded, with each pixel
+    ///   that differs shown in red.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the snapshot does not exist, the contents do
+    /// not match the snapshot, or if an error occurs reading or writing the
+    /// images.
+    #[track_caller]
+    pub fn assert_snapshot_with_tolerance(&self, name: &str, tolerance: SnapshotTolerance) {
+        let directory = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map_or_else(PathBuf::new, PathBuf::from)
+            .join("tests")
+            .join("snapshots");
+        let path = directory.join(format!("{name}.png"));
+        let artifact = |kind: &str| directory.join(format!("{name}.{kind}.png"));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("error creating snapshot directory");
+        }
+
+        let actual = self.image().into_rgba8();
+        if std::env::var_os("CUSHY_UPDATE_SNAPSHOTS").is_some() {
+            actual.save(&path).expect("error writing snapshot");
+            for kind in ["actual", "expected", "diff"] {
+                let _result = std::fs::remove_file(artifact(kind));
+            }
+            return;
+        }
+
+        let expected = match image::open(&path) {
+            Ok(expected) => expected.into_rgba8(),
+            Err(image::ImageError::IoError(err)) if err.kind() == io::ErrorKind::NotFound => {
+                actual
+                    .save(artifact("actual"))
+                    .expect("error writing snapshot");
+                panic!(
+                    "snapshot {} does not exist. Set CUSHY_UPDATE_SNAPSHOTS=1 to create it.",
+                    path.display()
+                );
+            }
+            Err(err) => panic!("error reading snapshot {}: {err}", path.display()),
+        };
+
+        let (differing, diff) = diff_images(&expected, &actual, tolerance.channel);
+        if differing > tolerance.max_differing_pixels {
+            actual
+                .save(artifact("actual"))
+                .expect("error writing snapshot");
+            expected
+                .save(artifact("expected"))
+                .expect("error writing snapshot");
+            diff.save(artifact("diff")).expect("error writing snapshot");
+            panic!(
+                "snapshot {name} does not match: {differing} pixels differ (max {}). See {} for the differences.",
+                tolerance.max_differing_pixels,
+                artifact("diff").display()
+            );
+        }
+    }
+
     fn recreate_buffers_if_needed(&mut self, size: Size<UPx>, bytes: u64, bytes_per_row: u32) {
         if self
             .capture
@@ -4660,6 +5360,35 @@ where
         self.cursor.set(position);
     }
 
+    /// Moves the cursor to the center of `widget`, clicks the primary mouse
+    /// button, and redraws the contents.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `widget` has not been laid out.
+    pub fn click(&mut self, widget: &MountedWidget) -> Result<(), wgpu::BufferAsyncError> {
+        self.cursor.set(widget_center(widget));
+        let _handled = self.window.click(widget);
+        self.refresh()
+    }
+
+    /// Focuses `widget` and redraws the contents.
+    pub fn focus(&mut self, widget: &MountedWidget) -> Result<(), wgpu::BufferAsyncError> {
+        self.window.focus(widget);
+        self.refresh()
+    }
+
+    /// Focuses `widget`, enters each grapheme of `text` as keyboard input, and
+    /// redraws the contents.
+    pub fn type_into(
+        &mut self,
+        widget: &MountedWidget,
+        text: &str,
+    ) -> Result<(), wgpu::BufferAsyncError> {
+        self.window.type_into(widget, text);
+        self.refresh()
+    }
+
     /// Enables or disables drawing of the virtual cursor.
     pub fn set_cursor_visible(&mut self, visible: bool) {
         self.cursor_visible = visible;
@@ -4685,9 +5414,58 @@ where
             recorder: self,
         }
     }
+
+    /// Replays the input events in `recording` without capturing any frames,
+    /// and redraws the contents.
+    ///
+    /// To capture an animation of the replay, use
+    /// [`AnimationRecorder::replay`] with [`Self::record_animated_png`].
+    #[cfg(feature = "replay")]
+    pub fn replay(
+        &mut self,
+        recording: &crate::replay::InputRecording,
+    ) -> Result<(), VirtualRecorderError> {
+        self.simulate_animation().replay(recording)?;
+        self.refresh()?;
+        Ok(())
+    }
+}
+
+fn widget_center(widget: &MountedWidget) -> Point<Px> {
+    let layout = widget.last_layout().expect("widget has not been laid out");
+    layout.origin + Point::from(layout.size) / 2
+}
+
+fn grapheme_key_event(grapheme: &str) -> KeyEvent {
+    let grapheme = SmolStr::new(grapheme);
+    KeyEvent {
+        physical_key: PhysicalKey::Unidentified(NativeKeyCode::Xkb(0)),
+        logical_key: Key::Character(grapheme.clone()),
+        text: Some(grapheme),
+        location: KeyLocation::Standard,
+        state: ElementState::Pressed,
+        repeat: false,
+        modifiers: Modifiers::default(),
+    }
 }
 
-fn copy_buffer_aligned_bytes_per_row(width: u32) -> u32 {
+/// Requests a device and queue suitable for rendering virtual windows.
+pub(crate) fn request_device() -> Result<(wgpu::Device, wgpu::Queue), VirtualRecorderError> {
+    let wgpu = wgpu::Instance::default();
+    let adapter = pollster::block_on(wgpu.request_adapter(&wgpu::RequestAdapterOptions::default()))
+        .ok_or(VirtualRecorderError::NoAdapter)?;
+    Ok(pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: None,
+            required_features: Kludgine::REQURED_FEATURES,
+            required_limits: Kludgine::adjust_limits(wgpu::Limits::downlevel_webgl2_defaults()),
+            memory_hints: wgpu::MemoryHints::MemoryUsage,
+        },
+        None,
+    ))?)
+}
+
+pub(crate) fn copy_buffer_aligned_bytes_per_row(width: u32) -> u32 {
     (width + COPY_BYTES_PER_ROW_ALIGNMENT - 1) / COPY_BYTES_PER_ROW_ALIGNMENT
         * COPY_BYTES_PER_ROW_ALIGNMENT
 }
@@ -4780,16 +5558,7 @@ where
         let delay_per_event =
             Duration::from_nanos(duration.as_nanos().cast::<u64>() / graphemes.cast::<u64>() / 2);
         for grapheme in text.graphemes(true) {
-            let grapheme = SmolStr::new(grapheme);
-            let mut event = KeyEvent {
-                physical_key: PhysicalKey::Unidentified(NativeKeyCode::Xkb(0)),
-                logical_key: Key::Character(grapheme.clone()),
-                text: Some(SmolStr::new(grapheme)),
-                location: KeyLocation::Standard,
-                state: ElementState::Pressed,
-                repeat: false,
-                modifiers: Modifiers::default(),
-            };
+            let mut event = grapheme_key_event(grapheme);
             let _handled =
                 self.recorder
                     .window
@@ -4806,22 +5575,90 @@ where
         Ok(())
     }
 
+    /// Replays the input events in `recording`, waiting between each event
+    /// to reproduce the original timing.
+    ///
+    /// See [`replay`](crate::replay) for more information.
+    #[cfg(feature = "replay")]
+    pub fn replay(
+        &mut self,
+        recording: &crate::replay::InputRecording,
+    ) -> Result<(), VirtualRecorderError> {
+        let started_at = clock::now();
+        for event in &recording.events {
+            self.wait_until(started_at + event.at)?;
+            let window = &mut self.recorder.window;
+            match &event.input {
+                RecordedInput::Keyboard {
+                    logical_key,
+                    physical_key,
+                    text,
+                    location,
+                    state,
+                    repeat,
+                    modifiers,
+                    is_synthetic,
+                } => {
+                    let _handled = window.keyboard_input(
+                        DeviceId::Virtual(0),
+                        KeyEvent {
+                            logical_key: logical_key.clone(),
+                            physical_key: *physical_key,
+                            text: text.clone(),
+                            location: *location,
+                            state: *state,
+                            repeat: *repeat,
+                            modifiers: Modifiers::from(*modifiers),
+                        },
+                        *is_synthetic,
+                    );
+                }
+                RecordedInput::MouseWheel { delta, phase } => {
+                    let _handled = window.mouse_wheel(DeviceId::Virtual(0), *delta, *phase);
+                }
+                RecordedInput::Ime(ime) => {
+                    let _handled = window.ime(ime);
+                }
+                RecordedInput::CursorMoved(location) => {
+                    // The cursor position is sent to the window each frame.
+                    self.recorder.cursor.set(*location);
+                    window.cursor_moved(DeviceId::Virtual(0), *location);
+                }
+                RecordedInput::CursorLeft => window.cursor_left(),
+                RecordedInput::MouseInput { state, button } => {
+                    let _handled = window.mouse_input(DeviceId::Virtual(0), *state, *button);
+                }
+                RecordedInput::Resized(size) => {
+                    let scale = window.dpi_scale();
+                    window.resize(*size, scale, &self.recorder.queue);
+                }
+                RecordedInput::Focused(focused) => window.set_focused(*focused),
+            }
+        }
+        self.wait_for(Duration::ZERO)
+    }
+
     /// Waits for `duration`, rendering frames as needed.
     pub fn wait_for(&mut self, duration: Duration) -> Result<(), VirtualRecorderError> {
-        self.wait_until(Instant::now() + duration)
+        self.wait_until(clock::now() + duration)
     }
 
     /// Waits until `time`, rendering frames as needed.
+    ///
+    /// If a [`ManualClock`](crate::clock::ManualClock) is installed on the
+    /// current thread, the clock is advanced one frame at a time instead of
+    /// sleeping.
     pub fn wait_until(&mut self, time: Instant) -> Result<(), VirtualRecorderError> {
         let Some(assembler) = self.assembler.as_ref() else {
+            clock::advance(time.saturating_duration_since(clock::now()));
             return Ok(());
         };
 
         let frame_duration = Duration::from_micros(1_000_000 / u64::from(self.target_fps));
-        let mut last_frame = Instant::now();
+        let mut last_frame = clock::now();
 
         loop {
-            let now = Instant::now();
+            let now = clock::now();
             let final_frame = now > time;
 
             self.recorder
@@ -4853,67 +5690,335 @@ where
                 break;
             }
 
-            let render_duration = now.elapsed();
-            std::thread::sleep(frame_duration.saturating_sub(render_duration));
+            let render_duration = clock::now().saturating_duration_since(now);
+            let delay = frame_duration.saturating_sub(render_duration);
+            if !clock::advance(delay) {
+                std::thread::sleep(delay);
+            }
         }
 
         Ok(())
     }
 
-    /// Encodes the currently recorded frames into a new file at `path`.
+    /// Encodes the currently recorded frames into a new animated png file at
+    /// `path`.
     ///
     /// If this animation was created from
     /// [`VirtualRecorder::simulate_animation`], this function will do nothing.
     pub fn write_to(self, path: impl AsRef<Path>) -> Result<(), VirtualRecorderError> {
+        self.write_frames_to(&mut AnimatedPng::new(path))
+    }
+
+    /// Writes the currently recorded frames to `sink`.
+    ///
+    /// Consecutive identical frames are combined into a single frame with a
+    /// longer duration.
+    ///
+    /// If this animation was created from
+    /// [`VirtualRecorder::simulate_animation`], this function will do nothing.
+    pub fn write_frames_to<Sink>(self, sink: &mut Sink) -> Result<(), VirtualRecorderError>
+    where
+        Sink: FrameSink + ?Sized,
+    {
         let Some(frames) = self.assembler.map(FrameAssembler::finish).transpose()? else {
             return Ok(());
         };
-        let mut file = std::fs::OpenOptions::new()
+        let size = self.recorder.window.size();
+        sink.begin(size, frames.len())?;
+        for frame in frames {
+            sink.frame(&Format::load_image(&frame.data, size), frame.duration)?;
+        }
+        sink.finish()
+    }
+}
+
+struct Frame {
+    data: Vec<u8>,
+    duration: Duration,
+}
+
+/// A destination for the frames captured by an [`AnimationRecorder`].
+///
+/// Frames are written using [`AnimationRecorder::write_frames_to`].
+pub trait FrameSink {
+    /// Prepares to receive `frame_count` frames of `size`.
+    fn begin(&mut self, size: Size<UPx>, frame_count: usize) -> Result<(), VirtualRecorderError>;
+
+    /// Writes `image`, which should be displayed for `duration`.
+    fn frame(
+        &mut self,
+        image: &DynamicImage,
+        duration: Duration,
+    ) -> Result<(), VirtualRecorderError>;
+
+    /// Finishes writing all frames.
+    fn finish(&mut self) -> Result<(), VirtualRecorderError>;
+}
+
+/// A [`FrameSink`] that writes an animated png.
+pub struct AnimatedPng {
+    path: PathBuf,
+    state: Option<AnimatedPngState>,
+}
+
+struct AnimatedPngState {
+    file: File,
+    frame_count: usize,
+    size: Size<UPx>,
+    frame_delay: Duration,
+    writer: Option<png::Writer<File>>,
+}
+
+impl AnimatedPng {
+    /// Returns a sink that writes an animated png to `path`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            state: None,
+        }
+    }
+}
+
+impl FrameSink for AnimatedPng {
+    fn begin(&mut self, size: Size<UPx>, frame_count: usize) -> Result<(), VirtualRecorderError> {
+        let file = std::fs::OpenOptions::new()
             .create(true)
             .truncate(true)
             .write(true)
-            .open(path)?;
-        let mut encoder = png::Encoder::new(
-            &mut file,
-            self.recorder.window.size().width.get(),
-            self.recorder.window.size().height.get(),
-        );
-        encoder.set_color(if Format::HAS_ALPHA {
-            png::ColorType::Rgba
-        } else {
-            png::ColorType::Rgb
+            .open(&self.path)?;
+        self.state = Some(AnimatedPngState {
+            file,
+            frame_count,
+            size,
+            frame_delay: Duration::ZERO,
+            writer: None,
         });
-        encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
-        encoder.set_animated(u32::try_from(frames.len()).assert("too many frames"), 0)?;
-        encoder.set_compression(png::Compression::Best);
-
-        let mut current_frame_delay = Duration::ZERO;
-        let mut writer = encoder.write_header()?;
-        for frame in &frames {
-            if current_frame_delay != frame.duration && frames.len() > 1 {
-                current_frame_delay = frame.duration;
-                // This has a limitation that a single frame can't be longer
-                // than ~6.5 seconds, but it ensures frame timing is more
-                // accurate.
-                writer.set_frame_delay(
-                    u16::try_from(current_frame_delay.as_nanos() / 100_000).unwrap_or(u16::MAX),
-                    10_000,
-                )?;
-            }
-            writer.write_image_data(&frame.data)?;
+        Ok(())
+    }
+
+    fn frame(
+        &mut self,
+        image: &DynamicImage,
+        duration: Duration,
+    ) -> Result<(), VirtualRecorderError> {
+        let state = self.state.as_mut().assert("begin not called");
+        if state.writer.is_none() {
+            // The color type isn't known until the first frame is written.
+            let mut encoder = png::Encoder::new(
+                state.file.try_clone()?,
+                state.size.width.get(),
+                state.size.height.get(),
+            );
+            encoder.set_color(if image.color().has_alpha() {
+                png::ColorType::Rgba
+            } else {
+                png::ColorType::Rgb
+            });
+            encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+            encoder.set_animated(
+                u32::try_from(state.frame_count).assert("too many frames"),
+                0,
+            )?;
+            encoder.set_compression(png::Compression::Best);
+            state.writer = Some(encoder.write_header()?);
+        }
+        let writer = state.writer.as_mut().assert("initialized above");
+
+        if state.frame_delay != duration && state.frame_count > 1 {
+            state.frame_delay = duration;
+            // This has a limitation that a single frame can't be longer
+            // than ~6.5 seconds, but it ensures frame timing is more
+            // accurate.
+            writer.set_frame_delay(
+                u16::try_from(duration.as_nanos() / 100_000).unwrap_or(u16::MAX),
+                10_000,
+            )?;
+        }
+        writer.write_image_data(image.as_bytes())?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), VirtualRecorderError> {
+        let Some(state) = self.state.take() else {
+            return Ok(());
+        };
+        if let Some(writer) = state.writer {
+            writer.finish()?;
         }
+        state.file.sync_all()?;
+        Ok(())
+    }
+}
 
-        writer.finish()?;
+/// A [`FrameSink`] that writes an animated gif.
+///
+/// Each frame is quantized to a palette of at most 256 colors.
+#[cfg(feature = "gif")]
+pub struct AnimatedGif {
+    path: PathBuf,
+    speed: i32,
+    encoder: Option<image::codecs::gif::GifEncoder<File>>,
+}
 
-        file.sync_all()?;
+#[cfg(feature = "gif")]
+impl AnimatedGif {
+    /// Returns a sink that writes an animated gif to `path`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            speed: 10,
+            encoder: None,
+        }
+    }
 
+    /// Sets the speed of palette quantization, from 1 to 30.
+    ///
+    /// Lower values produce higher quality palettes more slowly. The default
+    /// speed is 10.
+    #[must_use]
+    pub fn quantization_speed(mut self, speed: i32) -> Self {
+        self.speed = speed.clamp(1, 30);
+        self
+    }
+}
+
+#[cfg(feature = "gif")]
+impl FrameSink for AnimatedGif {
+    fn begin(&mut self, _size: Size<UPx>, _frame_count: usize) -> Result<(), VirtualRecorderError> {
+        let mut encoder =
+            image::codecs::gif::GifEncoder::new_with_speed(File::create(&self.path)?, self.speed);
+        encoder.set_repeat(image::codecs::gif::Repeat::Infinite)?;
+        self.encoder = Some(encoder);
+        Ok(())
+    }
+
+    fn frame(
+        &mut self,
+        image: &DynamicImage,
+        duration: Duration,
+    ) -> Result<(), VirtualRecorderError> {
+        let encoder = self.encoder.as_mut().assert("begin not called");
+        encoder.encode_frame(image::Frame::from_parts(
+            image.to_rgba8(),
+            0,
+            0,
+            image::Delay::from_saturating_duration(duration),
+        ))?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), VirtualRecorderError> {
+        // The gif trailer is written when the encoder is dropped.
+        drop(self.encoder.take());
         Ok(())
     }
 }
 
-struct Frame {
-    data: Vec<u8>,
-    duration: Duration,
+/// A [`FrameSink`] that writes each frame as a numbered png file.
+///
+/// Files are named using a prefix followed by the zero-padded frame number,
+/// e.g. `frame-00000.png`. Frame durations are not preserved.
+pub struct PngSequence {
+    directory: PathBuf,
+    prefix: String,
+    next_frame: usize,
+}
+
+impl PngSequence {
+    /// Returns a sink that writes png files into `directory`, creating it if
+    /// needed.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+            prefix: String::from("frame-"),
+            next_frame: 0,
+        }
+    }
+
+    /// Sets the prefix of each file name. The default prefix is `frame-`.
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+}
+
+impl FrameSink for PngSequence {
+    fn begin(&mut self, _size: Size<UPx>, _frame_count: usize) -> Result<(), VirtualRecorderError> {
+        std::fs::create_dir_all(&self.directory)?;
+        self.next_frame = 0;
+        Ok(())
+    }
+
+    fn frame(
+        &mut self,
+        image: &DynamicImage,
+        _duration: Duration,
+    ) -> Result<(), VirtualRecorderError> {
+        let path = self
+            .directory
+            .join(format!("{}{:05}.png", self.prefix, self.next_frame));
+        self.next_frame += 1;
+        image.save_with_format(path, image::ImageFormat::Png)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), VirtualRecorderError> {
+        Ok(())
+    }
+}
+
+/// The allowed differences when comparing a [`VirtualRecorder`] against a
+/// snapshot.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SnapshotTolerance {
+    /// The maximum difference of each color channel for two pixels to be
+    /// considered equal.
+    ///
+    /// Small variations can occur due to colorspace conversion rounding and
+    /// differences between graphics drivers.
+    pub channel: u8,
+    /// The number of pixels that are allowed to differ.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self {
+            channel: 1,
+            max_differing_pixels: 0,
+        }
+    }
+}
+
+/// Compares `expected` and `actual`, returning the number of pixels that
+/// differ by more than `tolerance` in any channel and an image highlighting
+/// the differences.
+fn diff_images(expected: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> (usize, RgbaImage) {
+    let width = expected.width().max(actual.width());
+    let height = expected.height().max(actual.height());
+    let mut differing = 0;
+    let diff = RgbaImage::from_fn(width, height, |x, y| {
+        let expected = expected.get_pixel_checked(x, y);
+        let actual = actual.get_pixel_checked(x, y);
+        match (expected, actual) {
+            (Some(expected), Some(actual))
+                if expected
+                    .0
+                    .iter()
+                    .zip(actual.0)
+                    .all(|(expected, actual)| expected.abs_diff(actual) <= tolerance) =>
+            {
+                let [r, g, b, a] = actual.0;
+                image::Rgba([r / 4, g / 4, b / 4, a])
+            }
+            _ => {
+                differing += 1;
+                image::Rgba([255, 0, 0, 255])
+            }
+        }
+    });
+    (differing, diff)
 }
 
 /// An error from a [`VirtualRecorder`].
@@ -4930,6 +6035,14 @@ pub enum VirtualRecorderError {
     MapBuffer(wgpu::BufferAsyncError),
     /// An error occurred encoding a png image.
     PngEncode(png::EncodingError),
+    /// An error occurred encoding an image.
+    ImageEncode(image::ImageError),
+}
+
+impl From<image::ImageError> for VirtualRecorderError {
+    fn from(value: image::ImageError) -> Self {
+        Self::ImageEncode(value)
+    }
 }
 
 impl From<png::EncodingError> for VirtualRecorderError {
@@ -4978,6 +6091,7 @@ impl std::fmt::Display for VirtualRecorderError {
                 write!(f, "error reading rendered graphics data: {err}")
             }
             VirtualRecorderError::PngEncode(err) => write!(f, "error encoding png: {err}"),
+            VirtualRecorderError::ImageEncode(err) => write!(f, "error encoding image: {err}"),
         }
     }
 }
@@ -5127,3 +6241,140 @@ impl KeyEvent {
         }
     }
 }
+
+#[test]
+fn snapshot_diff_tolerance() {
+    let expected = RgbaImage::from_pixel(2, 2, image::Rgba([100, 100, 100, 255]));
+    let mut actual = expected.clone();
+    actual.put_pixel(0, 0, image::Rgba([101, 100, 100, 255]));
+    actual.put_pixel(1, 1, image::Rgba([0, 0, 0, 255]));
+    let (differing, diff) = diff_images(&expected, &actual, 1);
+    assert_eq!(differing, 1);
+    assert_eq!(diff.get_pixel(1, 1), &image::Rgba([255, 0, 0, 255]));
+
+    // Pixels outside of the overlapping area always differ.
+    let (differing, _) = diff_images(&expected, &RgbaImage::new(1, 2), u8::MAX);
+    assert_eq!(differing, 2);
+}
+
+#[cfg(test)]
+fn synthetic_frames() -> Vec<(DynamicImage, Duration)> {
+    [
+        ([255, 0, 0, 255], Duration::from_millis(100)),
+        ([0, 255, 0, 255], Duration::from_millis(100)),
+        ([0, 0, 255, 255], Duration::from_millis(250)),
+    ]
+    .into_iter()
+    .map(|(color, duration)| {
+        (
+            DynamicImage::from(RgbaImage::from_pixel(4, 3, image::Rgba(color))),
+            duration,
+        )
+    })
+    .collect()
+}
+
+#[cfg(test)]
+fn write_synthetic_frames(
+    sink: &mut impl FrameSink,
+    frames: &[(DynamicImage, Duration)],
+) -> Result<(), VirtualRecorderError> {
+    sink.begin(Size::upx(4, 3), frames.len())?;
+    for (image, duration) in frames {
+        sink.frame(image, *duration)?;
+    }
+    sink.finish()
+}
+
+#[cfg(test)]
+fn sink_test_directory(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("cushy-{name}-{}", std::process::id()));
+    let _result = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+#[test]
+fn frame_sink_png_sequence() {
+    let directory = sink_test_directory("png-sequence");
+    let frames = synthetic_frames();
+    let mut sink = PngSequence::new(directory.join("frames")).prefix("test-");
+    write_synthetic_frames(&mut sink, &frames).unwrap();
+
+    for (index, (expected, _)) in frames.iter().enumerate() {
+        let path = directory.join(format!("frames/test-{index:05}.png"));
+        let image = image::open(&path).unwrap();
+        assert_eq!(image.to_rgba8(), expected.to_rgba8());
+    }
+    assert!(!directory.join("frames/test-00003.png").exists());
+
+    // Each sequence begins numbering from 0 again.
+    write_synthetic_frames(&mut sink, &frames[2..]).unwrap();
+    let image = image::open(directory.join("frames/test-00000.png")).unwrap();
+    assert_eq!(image.to_rgba8(), frames[2].0.to_rgba8());
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn frame_sink_animated_png() {
+    let directory = sink_test_directory("apng");
+    let path = directory.join("animation.png");
+    let frames = synthetic_frames();
+    let mut sink = AnimatedPng::new(&path);
+    sink.begin(Size::upx(4, 3), frames.len()).unwrap();
+    // The header is written once the first frame's color type is known.
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+    for (image, duration) in &frames {
+        sink.frame(image, *duration).unwrap();
+    }
+    sink.finish().unwrap();
+
+    let mut reader = png::Decoder::new(File::open(&path).unwrap())
+        .read_info()
+        .unwrap();
+    assert_eq!(reader.info().color_type, png::ColorType::Rgba);
+    assert_eq!(
+        reader
+            .info()
+            .animation_control
+            .map(|control| control.num_frames),
+        Some(3)
+    );
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    for (expected, duration) in &frames {
+        reader.next_frame(&mut buffer).unwrap();
+        assert_eq!(&buffer, expected.as_bytes());
+        let control = reader.info().frame_control.unwrap();
+        assert_eq!(
+            Duration::from_secs(u64::from(control.delay_num)) / u32::from(control.delay_den),
+            *duration
+        );
+    }
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+#[cfg(feature = "gif")]
+fn frame_sink_animated_gif() {
+    use image::AnimationDecoder;
+
+    let directory = sink_test_directory("gif");
+    let path = directory.join("animation.gif");
+    let frames = synthetic_frames();
+    write_synthetic_frames(&mut AnimatedGif::new(&path), &frames).unwrap();
+
+    let decoder =
+        image::codecs::gif::GifDecoder::new(io::BufReader::new(File::open(&path).unwrap()))
+            .unwrap();
+    let decoded = decoder.into_frames().collect_frames().unwrap();
+    assert_eq!(decoded.len(), frames.len());
+    for (decoded, (expected, duration)) in decoded.iter().zip(&frames) {
+        // Solid colors survive palette quantization exactly.
+        assert_eq!(decoded.buffer(), &expected.to_rgba8());
+        assert_eq!(Duration::from(decoded.delay()), *duration);
+    }
+
+    std::fs::remove_dir_all(directory).unwrap();
+}